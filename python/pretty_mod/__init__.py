@@ -1,3 +1,21 @@
-from .explorer import display_signature, display_tree
+from .explorer import (
+    check_exports,
+    check_signature,
+    display_signature,
+    display_tree,
+    get_schema,
+    get_signature,
+    query_daemon,
+    sig_of,
+)
 
-__all__ = ["display_signature", "display_tree"]
+__all__ = [
+    "check_exports",
+    "check_signature",
+    "display_signature",
+    "display_tree",
+    "get_schema",
+    "get_signature",
+    "query_daemon",
+    "sig_of",
+]
@@ -1,9 +1,63 @@
 """Type stubs for the _pretty_mod Rust extension module."""
 
+import inspect
 from typing import Any
 
+class Parameter:
+    name: str
+    kind: str
+    annotation: str | None
+    default: str | None
+    def to_inspect_parameter(self) -> inspect.Parameter: ...
+
+class Signature:
+    name: str
+    parameters: list[Parameter]
+    return_type: str | None
+    source: str | None
+    def _repr_html_(self) -> str: ...
+    def to_inspect_signature(self) -> inspect.Signature: ...
+
+class CheckReport:
+    target: str
+    ok: bool
+    matched: list[str]
+    missing: list[str]
+    unexpected: list[str]
+    mismatched: list[str]
+    def __bool__(self) -> bool: ...
+
+class StubDrift:
+    module: str
+    implementation: str
+    stub: str
+    ok: bool
+    mismatched: list[str]
+    stub_only: list[str]
+    implementation_only: list[str]
+    def __bool__(self) -> bool: ...
+
+class ApiDiff:
+    local: str
+    release: str
+    ok: bool
+    added: list[str]
+    removed: list[str]
+    changed: list[str]
+    def __bool__(self) -> bool: ...
+    def to_json(self) -> str: ...
+
 class ModuleTreeExplorer:
-    def __init__(self, root_module_path: str, max_depth: int = 2) -> None: ...
+    def __init__(
+        self,
+        root_module_path: str,
+        max_depth: int = 2,
+        timeout_seconds: float | None = None,
+        include_imports: bool = False,
+        hide_empty: bool = False,
+        class_depth: int = 0,
+        signatures: bool = False,
+    ) -> None: ...
     @property
     def root_module_path(self) -> str: ...
     @property
@@ -11,15 +65,123 @@ class ModuleTreeExplorer:
     @property
     def tree(self) -> dict[str, Any]: ...
     def explore(self) -> dict[str, Any]: ...
-    def get_tree_string(self) -> str: ...
+    def get_tree_string(self, format: str = "pretty") -> str: ...
+    def _repr_html_(self) -> str: ...
 
 def display_tree(
     root_module_path: str,
     max_depth: int = 2,
     quiet: bool = False,
     format: str = "pretty",
+    verbose: bool = False,
+    python: str | None = None,
+    timeout_seconds: float | None = None,
+    include_imports: bool = False,
+    hide_empty: bool = False,
+    class_depth: int = 0,
+    signatures: bool = False,
+    json_out: str | None = None,
 ) -> None: ...
 def display_signature(
-    import_path: str, quiet: bool = False, format: str = "pretty"
+    import_path: str,
+    quiet: bool = False,
+    format: str = "pretty",
+    verbose: bool = False,
+    width: int | None = None,
+    python: str | None = None,
+    json_out: str | None = None,
+    entry_point: bool = False,
+    param_docs: bool = False,
+    show_source: bool = False,
+    source_lines: int = 20,
 ) -> str: ...
-def import_object(import_path: str) -> Any: ...
+def import_object(
+    import_path: str, download: bool = False, quiet: bool = False
+) -> Any: ...
+def explore_class(
+    import_path: str,
+    include_inherited: bool = False,
+    quiet: bool = False,
+    verbose: bool = False,
+    dunders: str = "hide",
+) -> dict[str, Any]: ...
+def display_methods(
+    import_path: str,
+    include_inherited: bool = False,
+    quiet: bool = False,
+    format: str = "pretty",
+    verbose: bool = False,
+    dunders: str = "hide",
+) -> str: ...
+def get_signature(
+    import_path: str, quiet: bool = False, verbose: bool = False
+) -> Signature | None: ...
+def sig_of(
+    obj: Any, quiet: bool = False, verbose: bool = False
+) -> Signature | None: ...
+def check_signature(
+    import_path: str,
+    params: list[str] | None = None,
+    return_type: str | None = None,
+    expected: str | None = None,
+    strict: bool = False,
+    exact: bool = False,
+    quiet: bool = False,
+) -> CheckReport: ...
+def check_exports(
+    module_path: str,
+    expected: list[str],
+    strict: bool = False,
+    exact: bool = False,
+) -> CheckReport: ...
+def check_stubs(
+    module_path: str,
+    max_depth: int = 2,
+    strict: bool = False,
+    quiet: bool = False,
+) -> list[StubDrift]: ...
+def diff_api(
+    local_path: str,
+    release: str,
+    max_depth: int = 2,
+    quiet: bool = False,
+    verbose: bool = False,
+) -> ApiDiff: ...
+def display_stub_drift(
+    module_path: str,
+    max_depth: int = 2,
+    quiet: bool = False,
+    format: str = "pretty",
+    verbose: bool = False,
+) -> str: ...
+def display_coverage(
+    module_path: str,
+    max_depth: int = 2,
+    quiet: bool = False,
+    format: str = "pretty",
+    verbose: bool = False,
+) -> str: ...
+def display_external_imports(
+    module_path: str,
+    max_depth: int = 2,
+    quiet: bool = False,
+    format: str = "pretty",
+    verbose: bool = False,
+) -> str: ...
+def display_deps(
+    path: str,
+    max_depth: int = 1,
+    quiet: bool = False,
+    format: str = "pretty",
+    verbose: bool = False,
+) -> str: ...
+def browse(
+    root_module_path: str, quiet: bool = False, verbose: bool = False
+) -> None: ...
+def get_schema(kind: str) -> dict[str, Any]: ...
+def serve(
+    socket: str | None = None, quiet: bool = False, verbose: bool = False
+) -> None: ...
+def query_daemon(
+    request: dict[str, Any], socket: str | None = None, spawn: bool = True
+) -> Any: ...
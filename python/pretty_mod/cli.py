@@ -3,9 +3,23 @@ Command-line interface for pretty-mod.
 """
 
 import argparse
+import os
 import sys
 
-from ._pretty_mod import display_signature, display_tree
+from ._pretty_mod import (
+    browse,
+    check_stubs,
+    diff_api,
+    display_coverage,
+    display_deps,
+    display_external_imports,
+    display_methods,
+    display_signature,
+    display_stub_drift,
+    display_tree,
+    query_daemon,
+    serve,
+)
 
 
 def main():
@@ -24,11 +38,53 @@ def main():
         "--depth", type=int, default=2, help="Maximum depth to explore (default: 2)"
     )
     tree_parser.add_argument(
+        "--timeout",
+        type=float,
+        default=None,
+        metavar="SECONDS",
+        help="Stop descending after this long and show what was explored",
+    )
+    tree_parser.add_argument(
+        "--imports",
+        action="store_true",
+        help="Include each module's import map (name -> source) in JSON output",
+    )
+    tree_parser.add_argument(
+        "--hide-empty",
+        action="store_true",
+        help="Leave out submodules with no public API",
+    )
+    tree_parser.add_argument(
+        "--class-depth",
+        type=int,
+        choices=[0, 1],
+        default=0,
+        help="Expand classes to list their public methods (default: 0)",
+    )
+    tree_parser.add_argument(
+        "--signatures",
+        action="store_true",
+        help="With --class-depth 1, list each method with its signature",
+    )
+    tree_parser.add_argument(
+        "--json-out",
+        default=None,
+        metavar="PATH",
+        help="Also write the JSON output to this file",
+    )
+    tree_verbosity = tree_parser.add_mutually_exclusive_group()
+    tree_verbosity.add_argument(
         "-q",
         "--quiet",
         action="store_true",
         help="Suppress warnings and informational messages",
     )
+    tree_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (modules explored, downloads)",
+    )
     tree_parser.add_argument(
         "-o",
         "--output",
@@ -37,18 +93,133 @@ def main():
         default="pretty",
         help="Output format (default: pretty)",
     )
+    tree_parser.add_argument(
+        "--python",
+        default=None,
+        help="Resolve modules in another interpreter's environment (path to python)",
+    )
+    tree_parser.add_argument(
+        "--no-sdist",
+        action="store_true",
+        help="Don't download the sdist when a wheel has only compiled modules",
+    )
+    tree_parser.add_argument(
+        "--prefer",
+        choices=["merge", "stubs", "implementation"],
+        default=None,
+        help="With an installed <name>-stubs package: merge its types into the "
+        "implementation (default), or use only one of them",
+    )
 
     sig_parser = subparsers.add_parser("sig", help="Display function signature")
     sig_parser.add_argument(
         "import_path", help="Import path to the function (e.g., 'json:loads')"
     )
-    sig_parser.add_argument(
+    sig_verbosity = sig_parser.add_mutually_exclusive_group()
+    sig_verbosity.add_argument(
         "-q",
         "--quiet",
         action="store_true",
         help="Suppress download messages",
     )
+    sig_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (import chain hops, downloads)",
+    )
+    sig_parser.add_argument(
+        "-o",
+        "--output",
+        type=str,
+        choices=["pretty", "json", "compact"],
+        default="pretty",
+        help="Output format (default: pretty)",
+    )
+    sig_parser.add_argument(
+        "--width",
+        type=int,
+        default=None,
+        help="Wrap long signatures at this width (default: terminal width)",
+    )
+    sig_parser.add_argument(
+        "--entry-point",
+        action="store_true",
+        help="Treat the path as distribution:script and show the function it runs",
+    )
+    sig_parser.add_argument(
+        "--param-docs",
+        action="store_true",
+        help="Show parameter descriptions from numpy- or Google-style docstrings",
+    )
+    sig_parser.add_argument(
+        "--source",
+        action="store_true",
+        help="Show the definition's source below the signature",
+    )
+    sig_parser.add_argument(
+        "--source-lines",
+        type=int,
+        default=20,
+        metavar="N",
+        help="Show at most N lines of --source, 0 for all (default: 20)",
+    )
+    sig_parser.add_argument(
+        "--json-out",
+        default=None,
+        metavar="PATH",
+        help="Also write the JSON output to this file",
+    )
     sig_parser.add_argument(
+        "--python",
+        default=None,
+        help="Resolve modules in another interpreter's environment (path to python)",
+    )
+    sig_parser.add_argument(
+        "--no-sdist",
+        action="store_true",
+        help="Don't download the sdist when a wheel has only compiled modules",
+    )
+    sig_parser.add_argument(
+        "--prefer",
+        choices=["merge", "stubs", "implementation"],
+        default=None,
+        help="With an installed <name>-stubs package: merge its types into the "
+        "implementation (default), or use only one of them",
+    )
+
+    methods_parser = subparsers.add_parser(
+        "methods", help="Display a class's methods grouped by kind"
+    )
+    methods_parser.add_argument(
+        "import_path", help="Import path to the class (e.g., 'requests:Session')"
+    )
+    methods_parser.add_argument(
+        "--inherited",
+        action="store_true",
+        help="Include members inherited from base classes",
+    )
+    methods_parser.add_argument(
+        "--dunders",
+        choices=["hide", "protocol", "all"],
+        default="hide",
+        help="Dunder methods to list: none, protocol ones (context manager, "
+        "iteration, container, comparison), or all (default: hide)",
+    )
+    methods_verbosity = methods_parser.add_mutually_exclusive_group()
+    methods_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress download messages",
+    )
+    methods_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (import chain hops, base classes)",
+    )
+    methods_parser.add_argument(
         "-o",
         "--output",
         type=str,
@@ -57,15 +228,337 @@ def main():
         help="Output format (default: pretty)",
     )
 
-    args = parser.parse_args()
+    deps_parser = subparsers.add_parser(
+        "deps", help="Display the API of each dependency of a project"
+    )
+    deps_parser.add_argument(
+        "path",
+        nargs="?",
+        default="pyproject.toml",
+        help="pyproject.toml or requirements file (default: pyproject.toml)",
+    )
+    deps_parser.add_argument(
+        "--depth", type=int, default=1, help="Maximum depth to explore (default: 1)"
+    )
+    deps_verbosity = deps_parser.add_mutually_exclusive_group()
+    deps_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress download messages",
+    )
+    deps_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (dependencies explored, downloads)",
+    )
+    deps_parser.add_argument(
+        "-o",
+        "--output",
+        type=str,
+        choices=["pretty", "json"],
+        default="pretty",
+        help="Output format (default: pretty)",
+    )
+
+    coverage_parser = subparsers.add_parser(
+        "coverage", help="Report how completely public functions are annotated"
+    )
+    coverage_parser.add_argument(
+        "module", help="Root module path (e.g., 'requests', 'mypkg')"
+    )
+    coverage_parser.add_argument(
+        "--depth", type=int, default=2, help="Maximum depth to explore (default: 2)"
+    )
+    coverage_verbosity = coverage_parser.add_mutually_exclusive_group()
+    coverage_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress warnings and informational messages",
+    )
+    coverage_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (modules explored)",
+    )
+    coverage_parser.add_argument(
+        "-o",
+        "--output",
+        type=str,
+        choices=["pretty", "json"],
+        default="pretty",
+        help="Output format (default: pretty)",
+    )
+
+    imports_parser = subparsers.add_parser(
+        "imports", help="Report which third-party packages a package imports"
+    )
+    imports_parser.add_argument(
+        "module", help="Root module path (e.g., 'requests', 'mypkg')"
+    )
+    imports_parser.add_argument(
+        "--depth", type=int, default=2, help="Maximum depth to explore (default: 2)"
+    )
+    imports_verbosity = imports_parser.add_mutually_exclusive_group()
+    imports_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress warnings and informational messages",
+    )
+    imports_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (modules explored)",
+    )
+    imports_parser.add_argument(
+        "-o",
+        "--output",
+        type=str,
+        choices=["pretty", "json"],
+        default="pretty",
+        help="Output format (default: pretty)",
+    )
+
+    stubcheck_parser = subparsers.add_parser(
+        "stubcheck", help="Report where .pyi stubs disagree with the implementation"
+    )
+    stubcheck_parser.add_argument(
+        "module", help="Root module path (e.g., 'yaml', 'requests')"
+    )
+    stubcheck_parser.add_argument(
+        "--depth", type=int, default=2, help="Maximum depth to explore (default: 2)"
+    )
+    stubcheck_verbosity = stubcheck_parser.add_mutually_exclusive_group()
+    stubcheck_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress warnings and informational messages",
+    )
+    stubcheck_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (files compared)",
+    )
+    stubcheck_parser.add_argument(
+        "-o",
+        "--output",
+        type=str,
+        choices=["pretty", "json"],
+        default="pretty",
+        help="Output format (default: pretty)",
+    )
+    stubcheck_parser.add_argument(
+        "--strict",
+        action="store_true",
+        help="Exit with status 1 when any module has drifted (for CI)",
+    )
+
+    diff_parser = subparsers.add_parser(
+        "diff", help="Compare a local project's public API with a published release"
+    )
+    diff_parser.add_argument("local", help="Project directory (e.g., './')")
+    diff_parser.add_argument(
+        "release", help="Published release to compare with (e.g., 'mypkg@latest')"
+    )
+    diff_parser.add_argument(
+        "--depth", type=int, default=2, help="Maximum depth to explore (default: 2)"
+    )
+    diff_verbosity = diff_parser.add_mutually_exclusive_group()
+    diff_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress warnings and informational messages",
+    )
+    diff_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (packages compared, downloads)",
+    )
+    diff_parser.add_argument(
+        "-o",
+        "--output",
+        type=str,
+        choices=["pretty", "json"],
+        default="pretty",
+        help="Output format (default: pretty)",
+    )
+
+    browse_parser = subparsers.add_parser(
+        "browse",
+        help="Browse a module interactively (prints the tree when not in a terminal)",
+    )
+    browse_parser.add_argument(
+        "module", help="Root module path (e.g., 'json', 'prefect.client')"
+    )
+    browse_verbosity = browse_parser.add_mutually_exclusive_group()
+    browse_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress download messages",
+    )
+    browse_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Show per-step details (modules explored, downloads)",
+    )
+
+    serve_parser = subparsers.add_parser(
+        "serve",
+        help="Answer tree/sig/search requests on a unix socket from warm caches",
+    )
+    serve_parser.add_argument(
+        "--socket",
+        default=None,
+        metavar="PATH",
+        help="Socket path (default: $PRETTY_MOD_SOCKET, else pretty-mod.sock "
+        "in the runtime or temp directory)",
+    )
+    serve_parser.add_argument(
+        "--stop",
+        action="store_true",
+        help="Shut down the daemon listening on the socket",
+    )
+    serve_verbosity = serve_parser.add_mutually_exclusive_group()
+    serve_verbosity.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Suppress the startup message",
+    )
+    serve_verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="store_true",
+        help="Log each request",
+    )
+
+    argv = sys.argv[1:]
+    # "-:name" (an object in source piped on stdin) would parse as an option;
+    # pass it after "--" so argparse takes it as the import path
+    stdin_paths = [arg for arg in argv if arg.startswith("-:")]
+    if stdin_paths:
+        argv = [arg for arg in argv if not arg.startswith("-:")] + ["--", *stdin_paths]
+    args = parser.parse_args(argv)
+
+    if getattr(args, "no_sdist", False):
+        # Read by the downloader when deciding whether to fetch sources
+        os.environ["PRETTY_MOD_NO_SDIST"] = "1"
+    if getattr(args, "prefer", None):
+        # Read by the explorer when a stubs package is installed
+        os.environ["PRETTY_MOD_PREFER"] = args.prefer
 
     try:
         if args.command == "tree":
             # Call display_tree with format parameter
-            display_tree(args.module, args.depth, args.quiet, args.output)
+            display_tree(
+                args.module,
+                args.depth,
+                args.quiet,
+                args.output,
+                verbose=args.verbose,
+                python=args.python,
+                timeout_seconds=args.timeout,
+                include_imports=args.imports,
+                hide_empty=args.hide_empty,
+                class_depth=args.class_depth,
+                signatures=args.signatures,
+                json_out=args.json_out,
+            )
         elif args.command == "sig":
             # Call display_signature with format parameter
-            result = display_signature(args.import_path, args.quiet, args.output)
+            try:
+                result = display_signature(
+                    args.import_path,
+                    args.quiet,
+                    args.output,
+                    verbose=args.verbose,
+                    width=args.width,
+                    python=args.python,
+                    json_out=args.json_out,
+                    entry_point=args.entry_point,
+                    param_docs=args.param_docs,
+                    show_source=args.source,
+                    source_lines=args.source_lines,
+                )
+            except OSError as e:
+                # Only writing --json-out failed; still show the signature
+                if hasattr(e, "output"):
+                    print(e.output)
+                raise
+            print(result)
+        elif args.command == "methods":
+            result = display_methods(
+                args.import_path,
+                args.inherited,
+                args.quiet,
+                args.output,
+                verbose=args.verbose,
+                dunders=args.dunders,
+            )
+            print(result)
+        elif args.command == "coverage":
+            result = display_coverage(
+                args.module,
+                args.depth,
+                args.quiet,
+                args.output,
+                verbose=args.verbose,
+            )
+            print(result)
+        elif args.command == "imports":
+            result = display_external_imports(
+                args.module,
+                args.depth,
+                args.quiet,
+                args.output,
+                verbose=args.verbose,
+            )
+            print(result)
+        elif args.command == "stubcheck":
+            result = display_stub_drift(
+                args.module,
+                args.depth,
+                args.quiet,
+                args.output,
+                verbose=args.verbose,
+            )
+            print(result)
+            if args.strict:
+                check_stubs(args.module, args.depth, strict=True, quiet=True)
+        elif args.command == "diff":
+            report = diff_api(
+                args.local,
+                args.release,
+                args.depth,
+                args.quiet,
+                verbose=args.verbose,
+            )
+            print(report.to_json() if args.output == "json" else report)
+            # Exit 1 when the public API changed (for pre-release CI gates)
+            if not report:
+                sys.exit(1)
+        elif args.command == "browse":
+            browse(args.module, args.quiet, verbose=args.verbose)
+        elif args.command == "serve":
+            if args.stop:
+                query_daemon({"op": "shutdown"}, args.socket, spawn=False)
+            else:
+                serve(args.socket, args.quiet, verbose=args.verbose)
+        elif args.command == "deps":
+            result = display_deps(
+                args.path, args.depth, args.quiet, args.output, verbose=args.verbose
+            )
             print(result)
         else:
             parser.print_help()
@@ -1,10 +1,55 @@
 """Public API for pretty-mod explorer functionality."""
 
 from ._pretty_mod import (
+    ApiDiff,
+    CheckReport,
     ModuleTreeExplorer,
+    Signature,
+    StubDrift,
+    browse,
+    check_exports,
+    check_signature,
+    check_stubs,
+    diff_api,
+    display_coverage,
+    display_deps,
+    display_external_imports,
+    display_methods,
     display_signature,
+    display_stub_drift,
     display_tree,
+    explore_class,
+    get_schema,
+    get_signature,
     import_object,
+    query_daemon,
+    serve,
+    sig_of,
 )
 
-__all__ = ["display_signature", "display_tree", "ModuleTreeExplorer", "import_object"]
+__all__ = [
+    "ApiDiff",
+    "CheckReport",
+    "Signature",
+    "StubDrift",
+    "browse",
+    "check_exports",
+    "check_signature",
+    "check_stubs",
+    "diff_api",
+    "display_coverage",
+    "display_deps",
+    "display_external_imports",
+    "display_methods",
+    "display_signature",
+    "display_stub_drift",
+    "display_tree",
+    "explore_class",
+    "get_schema",
+    "get_signature",
+    "ModuleTreeExplorer",
+    "import_object",
+    "query_daemon",
+    "serve",
+    "sig_of",
+]
@@ -0,0 +1,319 @@
+use crate::typed_dict::{import_target, module_level};
+use ruff_python_ast::{Expr, Operator, Stmt};
+use ruff_python_parser::parse_module;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How many modules deep to follow `__all__` built from other modules' `__all__`
+const MAX_MODULE_HOPS: usize = 4;
+
+/// What a module-level name refers to: (level, module, imported name)
+/// `import a.b as x` binds `x` to (0, None, "a.b"); `from .core import x` to (1, Some("core"), "x").
+type Binding = (u32, Option<String>, String);
+
+/// A module's `__all__` as far as it can be known without importing it
+#[derive(Clone, Debug, PartialEq)]
+pub enum StaticAll {
+    /// No `__all__` is assigned
+    Missing,
+    Names(Vec<String>),
+    /// Assigned from something that can't be evaluated statically
+    Dynamic,
+}
+
+/// Evaluate `__all__` in a module body, including the aggregation forms
+/// `list(_core_all) + [...]`, `core.__all__ + utils.__all__`, `__all__ += ...`,
+/// and `__all__.extend(...)` where the other lists come from imported modules.
+pub fn static_all(body: &[Stmt], file_path: &Path) -> StaticAll {
+    evaluate_module(body, file_path, 0)
+}
+
+fn evaluate_module(body: &[Stmt], file_path: &Path, hops: usize) -> StaticAll {
+    let bindings = collect_bindings(body);
+    let context = Context {
+        bindings: &bindings,
+        file_path,
+        hops,
+    };
+    let mut all = StaticAll::Missing;
+    let mut lists: HashMap<String, Vec<String>> = HashMap::new();
+
+    for stmt in module_level(body) {
+        match stmt {
+            Stmt::Assign(assign) => {
+                let [Expr::Name(target)] = assign.targets.as_slice() else {
+                    continue;
+                };
+                let value = context.evaluate(&assign.value, &lists, &all);
+                if target.id.as_str() == "__all__" {
+                    all = value.map_or(StaticAll::Dynamic, StaticAll::Names);
+                } else if let Some(names) = value {
+                    // Helper lists, e.g. `_core = [...]` later added to `__all__`
+                    lists.insert(target.id.to_string(), names);
+                }
+            }
+            Stmt::AugAssign(aug) if aug.op == Operator::Add && is_all(&aug.target) => {
+                all = extend(all, context.evaluate(&aug.value, &lists, &all));
+            }
+            Stmt::Expr(expr) => {
+                let Expr::Call(call) = expr.value.as_ref() else {
+                    continue;
+                };
+                let Expr::Attribute(method) = call.func.as_ref() else {
+                    continue;
+                };
+                if method.attr.as_str() == "extend" && is_all(&method.value) {
+                    let value = match call.arguments.args.as_ref() {
+                        [arg] => context.evaluate(arg, &lists, &all),
+                        _ => None,
+                    };
+                    all = extend(all, value);
+                }
+            }
+            _ => {}
+        }
+    }
+    all
+}
+
+fn is_all(expr: &Expr) -> bool {
+    matches!(expr, Expr::Name(name) if name.id.as_str() == "__all__")
+}
+
+/// Add names to `__all__` (extending one that was never assigned is a runtime error)
+fn extend(all: StaticAll, names: Option<Vec<String>>) -> StaticAll {
+    match (all, names) {
+        (StaticAll::Names(mut all), Some(names)) => {
+            all.extend(names);
+            StaticAll::Names(all)
+        }
+        _ => StaticAll::Dynamic,
+    }
+}
+
+struct Context<'a> {
+    bindings: &'a HashMap<String, Binding>,
+    file_path: &'a Path,
+    hops: usize,
+}
+
+impl Context<'_> {
+    /// The string list an expression evaluates to, if it can be known statically
+    fn evaluate(
+        &self,
+        expr: &Expr,
+        lists: &HashMap<String, Vec<String>>,
+        all: &StaticAll,
+    ) -> Option<Vec<String>> {
+        match expr {
+            Expr::List(list) => self.evaluate_elements(&list.elts, lists, all),
+            Expr::Tuple(tuple) => self.evaluate_elements(&tuple.elts, lists, all),
+            Expr::BinOp(bin_op) if bin_op.op == Operator::Add => {
+                let mut names = self.evaluate(&bin_op.left, lists, all)?;
+                names.extend(self.evaluate(&bin_op.right, lists, all)?);
+                Some(names)
+            }
+            // list(...) / tuple(...) copies
+            Expr::Call(call) => {
+                let Expr::Name(func) = call.func.as_ref() else {
+                    return None;
+                };
+                match (func.id.as_str(), call.arguments.args.as_ref()) {
+                    ("list" | "tuple", [arg]) if call.arguments.keywords.is_empty() => {
+                        self.evaluate(arg, lists, all)
+                    }
+                    _ => None,
+                }
+            }
+            Expr::Name(name) if name.id.as_str() == "__all__" => match all {
+                StaticAll::Names(names) => Some(names.clone()),
+                _ => None,
+            },
+            Expr::Name(name) => {
+                if let Some(names) = lists.get(name.id.as_str()) {
+                    return Some(names.clone());
+                }
+                // `from .core import __all__ as _core_all`
+                let (level, module, imported) = self.bindings.get(name.id.as_str())?;
+                if imported != "__all__" {
+                    return None;
+                }
+                self.module_all(*level, module.as_deref())
+            }
+            // `core.__all__`, `pkg.core.__all__`
+            Expr::Attribute(attribute) if attribute.attr.as_str() == "__all__" => {
+                let mut parts = dotted_parts(&attribute.value)?;
+                let (level, module, imported) = self.bindings.get(&parts.remove(0))?;
+                let mut target = match module {
+                    Some(module) => format!("{}.{}", module, imported),
+                    None => imported.clone(),
+                };
+                for part in parts {
+                    target = format!("{}.{}", target, part);
+                }
+                self.module_all(*level, Some(&target))
+            }
+            _ => None,
+        }
+    }
+
+    fn evaluate_elements(
+        &self,
+        elements: &[Expr],
+        lists: &HashMap<String, Vec<String>>,
+        all: &StaticAll,
+    ) -> Option<Vec<String>> {
+        let mut names = Vec::new();
+        for element in elements {
+            match element {
+                Expr::StringLiteral(literal) => {
+                    names.push(literal.as_single_part_string()?.as_str().to_string())
+                }
+                Expr::Starred(starred) => {
+                    names.extend(self.evaluate(&starred.value, lists, all)?)
+                }
+                _ => return None,
+            }
+        }
+        Some(names)
+    }
+
+    /// Another module's statically known `__all__`
+    fn module_all(&self, level: u32, module: Option<&str>) -> Option<Vec<String>> {
+        if self.hops >= MAX_MODULE_HOPS {
+            return None;
+        }
+        let target = import_target(self.file_path, level, module)?;
+        let source = std::fs::read_to_string(&target).ok()?;
+        let body = parse_module(&source).ok()?.into_syntax().body;
+        match evaluate_module(&body, &target, self.hops + 1) {
+            StaticAll::Names(names) => Some(names),
+            _ => None,
+        }
+    }
+}
+
+/// `a.b.c` as ["a", "b", "c"]
+fn dotted_parts(expr: &Expr) -> Option<Vec<String>> {
+    match expr {
+        Expr::Name(name) => Some(vec![name.id.to_string()]),
+        Expr::Attribute(attribute) => {
+            let mut parts = dotted_parts(&attribute.value)?;
+            parts.push(attribute.attr.to_string());
+            Some(parts)
+        }
+        _ => None,
+    }
+}
+
+/// Names bound by module-level imports
+fn collect_bindings(body: &[Stmt]) -> HashMap<String, Binding> {
+    let mut bindings = HashMap::new();
+    for stmt in module_level(body) {
+        match stmt {
+            Stmt::Import(import) => {
+                for alias in &import.names {
+                    // `import a.b` binds `a`; `import a.b as x` binds `x` to `a.b`
+                    let (bound, module) = match &alias.asname {
+                        Some(asname) => (asname.to_string(), alias.name.to_string()),
+                        None => {
+                            let top = alias.name.as_str().split('.').next().unwrap_or_default();
+                            (top.to_string(), top.to_string())
+                        }
+                    };
+                    bindings.insert(bound, (0, None, module));
+                }
+            }
+            Stmt::ImportFrom(import_from) => {
+                let module = import_from.module.as_ref().map(|m| m.to_string());
+                for alias in &import_from.names {
+                    let bound = alias.asname.as_ref().unwrap_or(&alias.name);
+                    bindings.insert(
+                        bound.to_string(),
+                        (import_from.level, module.clone(), alias.name.to_string()),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    bindings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn names(list: &[&str]) -> StaticAll {
+        StaticAll::Names(list.iter().map(|name| name.to_string()).collect())
+    }
+
+    fn package() -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("agg");
+        fs::create_dir(&package).unwrap();
+        fs::write(package.join("core.py"), "__all__ = ['load', 'dump']\n").unwrap();
+        fs::write(package.join("utils.py"), "__all__ = ('retry',)\n").unwrap();
+        (dir, package)
+    }
+
+    fn evaluate(package: &Path, source: &str) -> StaticAll {
+        let init = package.join("__init__.py");
+        fs::write(&init, source).unwrap();
+        static_all(&parse_module(source).unwrap().into_syntax().body, &init)
+    }
+
+    #[test]
+    fn test_literal_and_missing() {
+        let (_dir, package) = package();
+        assert_eq!(evaluate(&package, "x = 1\n"), StaticAll::Missing);
+        assert_eq!(
+            evaluate(&package, "__all__ = ['a'] + ['b']\n__all__ += ('c',)\n"),
+            names(&["a", "b", "c"])
+        );
+    }
+
+    #[test]
+    fn test_aggregated_from_submodules() {
+        let (_dir, package) = package();
+        assert_eq!(
+            evaluate(
+                &package,
+                "from .core import __all__ as _core_all\n\
+                 __all__ = list(_core_all) + ['extra']\n"
+            ),
+            names(&["load", "dump", "extra"])
+        );
+        assert_eq!(
+            evaluate(
+                &package,
+                "from . import core, utils\n__all__ = core.__all__ + utils.__all__\n"
+            ),
+            names(&["load", "dump", "retry"])
+        );
+        assert_eq!(
+            evaluate(
+                &package,
+                "import agg.utils\n__all__ = []\n__all__.extend(agg.utils.__all__)\n"
+            ),
+            names(&["retry"])
+        );
+    }
+
+    #[test]
+    fn test_unresolvable_is_dynamic() {
+        let (_dir, package) = package();
+        assert_eq!(
+            evaluate(
+                &package,
+                "from . import missing\n__all__ = missing.__all__\n"
+            ),
+            StaticAll::Dynamic
+        );
+        assert_eq!(
+            evaluate(&package, "__all__ = [name for name in dir()]\n"),
+            StaticAll::Dynamic
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use crate::check::normalize;
+use crate::config::{colorize, DisplayConfig};
+use crate::explorer::ModuleTreeExplorer;
+use crate::module_info::ModuleInfo;
+use crate::package_downloader::PackageDownloader;
+use crate::project::detect_project;
+use crate::utils::{with_downloaded_package, PathGuard};
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Public names mapped to their signature, e.g. "mypkg.client:Client.send" -> "(self, request) -> Response"
+/// Classes without a constructor and constants have no signature.
+type PublicApi = BTreeMap<String, Option<String>>;
+
+/// How a local working tree's public API differs from a published release
+#[pyclass(get_all, frozen)]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ApiDiff {
+    pub local: String,        // e.g., "./ (local)"
+    pub release: String,      // e.g., "mypkg 1.4.0 (PyPI)"
+    pub ok: bool,             // No public API changes
+    pub added: Vec<String>,   // Only in the local tree, e.g., "mypkg.client:Client.retry"
+    pub removed: Vec<String>, // Only in the release
+    pub changed: Vec<String>, // e.g., "mypkg:load: (path) -> dict → (path, *, strict=False) -> dict"
+}
+
+#[pymethods]
+impl ApiDiff {
+    fn __bool__(&self) -> bool {
+        self.ok
+    }
+
+    fn __str__(&self) -> String {
+        format_api_diff_display(self)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ApiDiff {} vs {} ok={} added={:?} removed={:?} changed={:?}>",
+            self.local,
+            self.release,
+            if self.ok { "True" } else { "False" },
+            self.added,
+            self.removed,
+            self.changed
+        )
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+}
+
+/// Compare a local project directory (src-layout aware) with a release downloaded from PyPI
+/// `release` is a download spec such as "mypkg@latest" or "mypkg@1.4.0".
+pub fn diff_local_release(
+    py: Python,
+    local_dir: &Path,
+    release: &str,
+    max_depth: usize,
+    verbosity: Verbosity,
+) -> PyResult<ApiDiff> {
+    let project = detect_project(local_dir)?;
+    verbosity.detail(&format!(
+        "comparing {} from {} with {}",
+        project.packages.join(", "),
+        project.source_root.display(),
+        release
+    ));
+
+    let local_api = {
+        let sys_path = py.import("sys")?.getattr("path")?;
+        let source_root = project.source_root.to_string_lossy().to_string();
+        let _guard = PathGuard::prepend(&sys_path, &source_root)?;
+        explore_api(py, &project.packages, max_depth, verbosity)
+    };
+
+    let mut downloader = PackageDownloader::new(release.to_string()).with_verbosity(verbosity);
+    downloader.download_and_extract()?;
+    let release_api = with_downloaded_package(py, &downloader, |_| {
+        Ok(explore_api(py, &project.packages, max_depth, verbosity))
+    })?;
+
+    let mut diff = compare_apis(&release_api, &local_api);
+    diff.local = format!("{} (local)", local_dir.display());
+    diff.release = match downloader.metadata() {
+        Some(metadata) => format!("{} {} (PyPI)", metadata.name, metadata.version),
+        None => format!("{} (PyPI)", release),
+    };
+    Ok(diff)
+}
+
+/// The public API of each package as found first on sys.path
+/// Packages that can't be explored (e.g., new since the release) contribute nothing.
+fn explore_api(
+    py: Python,
+    packages: &[String],
+    max_depth: usize,
+    verbosity: Verbosity,
+) -> PublicApi {
+    let mut api = PublicApi::new();
+    for package in packages {
+        let explorer =
+            ModuleTreeExplorer::new(package.clone(), max_depth).with_verbosity(verbosity);
+        match explorer.explore_module_pure_filesystem(py, package) {
+            Ok(info) => collect_api(&info, package, &mut api),
+            Err(e) => verbosity.detail(&format!("skipped {}: {}", package, e)),
+        }
+    }
+    api
+}
+
+/// Functions, classes, public methods, and constants of a module tree
+fn collect_api(info: &ModuleInfo, module_path: &str, api: &mut PublicApi) {
+    let signature = |name: &str| {
+        info.signatures.get(name).map(|sig| match &sig.return_type {
+            Some(return_type) => format!("({}) -> {}", sig.parameters, return_type),
+            None => format!("({})", sig.parameters),
+        })
+    };
+    for name in info.functions.iter().chain(&info.classes) {
+        api.insert(format!("{}:{}", module_path, name), signature(name));
+    }
+    for name in &info.constants {
+        api.insert(format!("{}:{}", module_path, name), None);
+    }
+    // Constructors are compared through the class signature
+    for class in &info.classes {
+        let Some(class_info) = info.class_info.get(class) else {
+            continue;
+        };
+        for method in &class_info.methods {
+            if method.name.starts_with('_') {
+                continue;
+            }
+            let name = format!("{}.{}", class, method.name);
+            api.insert(format!("{}:{}", module_path, name), signature(&name));
+        }
+    }
+
+    for (name, submodule) in &info.submodules {
+        collect_api(submodule, &format!("{}.{}", module_path, name), api);
+    }
+}
+
+/// Names added, removed, or with a different signature in `new` compared to `old`
+fn compare_apis(old: &PublicApi, new: &PublicApi) -> ApiDiff {
+    let mut diff = ApiDiff {
+        added: new
+            .keys()
+            .filter(|name| !old.contains_key(*name))
+            .cloned()
+            .collect(),
+        removed: old
+            .keys()
+            .filter(|name| !new.contains_key(*name))
+            .cloned()
+            .collect(),
+        ..ApiDiff::default()
+    };
+    for (name, before) in old {
+        if let (Some(before), Some(Some(after))) = (before, new.get(name)) {
+            if normalize(before) != normalize(after) {
+                diff.changed
+                    .push(format!("{}: {} → {}", name, before, after));
+            }
+        }
+    }
+    diff.ok = diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty();
+    diff
+}
+
+/// Format an API diff as a tree of added (+), removed (-), and changed (~) names
+pub fn format_api_diff_display(diff: &ApiDiff) -> String {
+    let config = DisplayConfig::get();
+    let mut result = format!(
+        "{} {} vs {}\n",
+        colorize(
+            &config.module_icon,
+            &config.color_scheme.module_color,
+            config
+        ),
+        colorize(&diff.local, &config.color_scheme.module_color, config),
+        diff.release
+    );
+    if diff.ok {
+        result.push_str("No public API changes");
+        return result;
+    }
+
+    let items: Vec<String> = diff
+        .added
+        .iter()
+        .map(|name| format!("+ {}", name))
+        .chain(diff.removed.iter().map(|name| {
+            colorize(
+                &format!("- {}", name),
+                &config.color_scheme.warning_color,
+                config,
+            )
+        }))
+        .chain(diff.changed.iter().map(|change| {
+            colorize(
+                &format!("~ {}", change),
+                &config.color_scheme.warning_color,
+                config,
+            )
+        }))
+        .collect();
+    for (i, item) in items.iter().enumerate() {
+        let prefix = if i == items.len() - 1 {
+            &config.tree_last
+        } else {
+            &config.tree_branch
+        };
+        result.push_str(&format!(
+            "{}{}\n",
+            colorize(prefix, &config.color_scheme.tree_color, config),
+            item
+        ));
+    }
+    result.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api(entries: &[(&str, Option<&str>)]) -> PublicApi {
+        entries
+            .iter()
+            .map(|(name, sig)| (name.to_string(), sig.map(str::to_string)))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_apis() {
+        let release = api(&[
+            ("pkg:load", Some("(path) -> dict")),
+            ("pkg:dump", Some("(obj, fp)")),
+            ("pkg:VERSION", None),
+        ]);
+        let local = api(&[
+            ("pkg:load", Some("(path, *, strict=False) -> dict")),
+            ("pkg:dump", Some("(obj,  fp)")),
+            ("pkg:VERSION", None),
+            ("pkg.client:Client.retry", Some("(self)")),
+        ]);
+        let diff = compare_apis(&release, &local);
+        assert!(!diff.ok);
+        assert_eq!(diff.added, vec!["pkg.client:Client.retry"]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.changed,
+            vec!["pkg:load: (path) -> dict → (path, *, strict=False) -> dict"]
+        );
+        assert!(compare_apis(&release, &release).ok);
+    }
+
+    #[test]
+    fn test_collect_api() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("module.py");
+        std::fs::write(
+            &path,
+            "LIMIT = 3\n\
+             def load(path: str) -> dict: ...\n\
+             class Client:\n    def __init__(self, url): ...\n    def send(self, data): ...\n    def _retry(self): ...\n",
+        )
+        .unwrap();
+        let info = ModuleInfo::from_python_file(&path).unwrap();
+        let mut collected = PublicApi::new();
+        collect_api(&info, "pkg", &mut collected);
+        assert_eq!(
+            collected,
+            api(&[
+                ("pkg:Client", Some("(self, url)")),
+                ("pkg:Client.send", Some("(self, data)")),
+                ("pkg:LIMIT", None),
+                ("pkg:load", Some("(path: str) -> dict")),
+            ])
+        );
+    }
+}
@@ -0,0 +1,458 @@
+use crate::config::{parse_hex_color, DisplayConfig};
+use crate::explorer::ModuleTreeExplorer;
+use crate::module_info::ModuleInfo;
+use crate::signature::format_signature_compact;
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::HashMap;
+
+/// Rows moved by PageUp/PageDown
+const PAGE: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Module,
+    Class,
+    Function,
+    Constant,
+}
+
+/// One line of the module tree
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    name: String,   // Full path for modules, the symbol name otherwise
+    module: String, // Module the row belongs to (itself for modules)
+    kind: Kind,
+    depth: usize,
+    expanded: bool,
+}
+
+impl Row {
+    fn module(path: &str, depth: usize) -> Self {
+        Self {
+            name: path.to_string(),
+            module: path.to_string(),
+            kind: Kind::Module,
+            depth,
+            expanded: false,
+        }
+    }
+
+    /// The name shown in the tree: the last dotted segment for modules
+    fn label(&self) -> &str {
+        match self.kind {
+            Kind::Module => self.name.rsplit('.').next().unwrap_or(&self.name),
+            _ => &self.name,
+        }
+    }
+}
+
+/// State of the interactive browser
+struct Browser {
+    rows: Vec<Row>,
+    modules: HashMap<String, Result<ModuleInfo, String>>, // Parsed on first expansion
+    list_state: ListState,
+    filter: String,
+    filtering: bool,
+    verbosity: Verbosity,
+}
+
+/// Browse a module interactively until the user quits
+pub fn browse(py: Python, root_module: &str, verbosity: Verbosity) -> PyResult<()> {
+    let mut browser = Browser::new(root_module, verbosity);
+    // Load the root before taking over the terminal so errors print normally
+    if let Err(e) = browser.load(py, root_module) {
+        return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(e));
+    }
+    browser.toggle(py, 0);
+
+    // Nothing may write to stderr while the terminal is in raw mode
+    browser.verbosity = Verbosity::Quiet;
+    let mut terminal = ratatui::init();
+    let result = browser.run(py, &mut terminal);
+    ratatui::restore();
+    result.map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))
+}
+
+impl Browser {
+    fn new(root_module: &str, verbosity: Verbosity) -> Self {
+        Self {
+            rows: vec![Row::module(root_module, 0)],
+            modules: HashMap::new(),
+            list_state: ListState::default().with_selected(Some(0)),
+            filter: String::new(),
+            filtering: false,
+            verbosity,
+        }
+    }
+
+    fn run(&mut self, py: Python, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                return Ok(());
+            }
+
+            if self.filtering {
+                match key.code {
+                    KeyCode::Char(c) => self.filter.push(c),
+                    KeyCode::Backspace => {
+                        self.filter.pop();
+                    }
+                    KeyCode::Esc => {
+                        self.filter.clear();
+                        self.filtering = false;
+                    }
+                    KeyCode::Enter => self.filtering = false,
+                    _ => {}
+                }
+                self.list_state.select(Some(0));
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char('/') => self.filtering = true,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.list_state.select(Some(0));
+                }
+                KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+                KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+                KeyCode::PageDown => self.move_by(PAGE as isize),
+                KeyCode::PageUp => self.move_by(-(PAGE as isize)),
+                KeyCode::Home => self.list_state.select(Some(0)),
+                KeyCode::End => self.move_by(isize::MAX / 2),
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter => {
+                    if let Some(index) = self.selected_row() {
+                        if !self.rows[index].expanded {
+                            self.toggle(py, index);
+                        }
+                    }
+                }
+                KeyCode::Left | KeyCode::Char('h') => {
+                    if let Some(index) = self.selected_row() {
+                        if self.rows[index].expanded {
+                            self.toggle(py, index);
+                        } else if let Some(parent) = parent_of(&self.rows, index) {
+                            self.select_row(parent);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Parse a module (and list its submodules) unless it was already loaded
+    fn load(&mut self, py: Python, module: &str) -> Result<&ModuleInfo, String> {
+        let verbosity = self.verbosity;
+        self.modules
+            .entry(module.to_string())
+            .or_insert_with(|| {
+                // Deep enough to list the submodules of the last dotted part
+                let depth = module.split('.').count();
+                ModuleTreeExplorer::new(module.to_string(), depth)
+                    .with_verbosity(verbosity)
+                    .explore_module_pure_filesystem(py, module)
+                    .map(|info| descend(info, module))
+                    .map_err(|e| e.to_string())
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Expand or collapse a module row
+    fn toggle(&mut self, py: Python, index: usize) {
+        let Row {
+            module,
+            kind,
+            depth,
+            expanded,
+            ..
+        } = self.rows[index].clone();
+        if kind != Kind::Module {
+            return;
+        }
+        if expanded {
+            collapse(&mut self.rows, index);
+            return;
+        }
+        let children = match self.load(py, &module) {
+            Ok(info) => child_rows(info, &module, depth + 1),
+            Err(_) => Vec::new(),
+        };
+        self.rows[index].expanded = true;
+        self.rows.splice(index + 1..index + 1, children);
+    }
+
+    /// Indices of the rows shown under the current filter
+    fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.to_lowercase();
+        (0..self.rows.len())
+            .filter(|&i| filter.is_empty() || self.rows[i].label().to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    fn selected_row(&self) -> Option<usize> {
+        self.visible().get(self.list_state.selected()?).copied()
+    }
+
+    fn select_row(&mut self, index: usize) {
+        if let Some(position) = self.visible().iter().position(|&i| i == index) {
+            self.list_state.select(Some(position));
+        }
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let count = self.visible().len();
+        if count == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = current.saturating_add(delta).clamp(0, count as isize - 1);
+        self.list_state.select(Some(next as usize));
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [tree_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(main);
+
+        let visible = self.visible();
+        match self.list_state.selected() {
+            Some(selected) if selected < visible.len() => {}
+            _ => self.list_state.select((!visible.is_empty()).then_some(0)),
+        }
+        let items: Vec<ListItem> = visible.iter().map(|&i| self.tree_item(&self.rows[i])).collect();
+        let tree = List::new(items)
+            .block(Block::bordered().title(format!(" {} ", self.rows[0].name)))
+            .highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(tree, tree_area, &mut self.list_state);
+
+        let width = detail_area.width.saturating_sub(2) as usize;
+        let (title, lines) = match self.selected_row() {
+            Some(index) => (self.rows[index].name.clone(), self.detail(&self.rows[index], width)),
+            None => (String::new(), vec!["No matches".to_string()]),
+        };
+        let detail = Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+            .block(Block::bordered().title(format!(" {} ", title)))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(detail, detail_area);
+
+        let status_line = if self.filtering || !self.filter.is_empty() {
+            format!("/{}", self.filter)
+        } else {
+            "↑/↓ move  →/enter expand  ← collapse  / filter  esc clear  q quit".to_string()
+        };
+        frame.render_widget(Paragraph::new(status_line).dim(), status);
+    }
+
+    fn tree_item(&self, row: &Row) -> ListItem<'static> {
+        let config = DisplayConfig::get();
+        let (icon, color) = match row.kind {
+            Kind::Module => (&config.module_icon, &config.color_scheme.module_color),
+            Kind::Class => (&config.class_icon, &config.color_scheme.class_color),
+            Kind::Function => (&config.function_icon, &config.color_scheme.function_color),
+            Kind::Constant => (&config.constant_icon, &config.color_scheme.constant_color),
+        };
+        let marker = match (row.kind, row.expanded) {
+            (Kind::Module, true) => "▾ ",
+            (Kind::Module, false) => "▸ ",
+            _ => "  ",
+        };
+        let text = format!("{}{}{} {}", "  ".repeat(row.depth), marker, icon, row.label());
+        let style = match parse_hex_color(color) {
+            Some((r, g, b)) if config.use_color => Style::new().fg(Color::Rgb(r, g, b)),
+            _ => Style::new(),
+        };
+        ListItem::new(text).style(style)
+    }
+
+    /// Lines for the detail pane
+    fn detail(&self, row: &Row, width: usize) -> Vec<String> {
+        let info = match self.modules.get(&row.module) {
+            Some(Ok(info)) => info,
+            Some(Err(e)) => return vec![format!("Could not load {}: {}", row.module, e)],
+            None => return vec!["Press → to load this module".to_string()],
+        };
+        match row.kind {
+            Kind::Module => module_detail(info),
+            Kind::Function => match info.signatures.get(&row.name) {
+                Some(sig) => format_signature_compact(sig, Some(width))
+                    .lines()
+                    .map(str::to_string)
+                    .collect(),
+                None => vec![format!("{} (signature not available)", row.name)],
+            },
+            Kind::Class => class_detail(info, &row.name, width),
+            Kind::Constant => vec![format!("{} (constant)", row.name)],
+        }
+    }
+}
+
+/// The part of an explored tree describing `module` itself
+/// (the explorer returns the root package with the dotted path nested in `submodules`)
+fn descend(info: ModuleInfo, module: &str) -> ModuleInfo {
+    let mut current = info;
+    for part in module.split('.').skip(1) {
+        match current.submodules.remove(part) {
+            Some(sub) => current = sub,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Rows for a module's API (classes, functions, constants) followed by its submodules
+fn child_rows(info: &ModuleInfo, module: &str, depth: usize) -> Vec<Row> {
+    let symbol = |name: &String, kind: Kind| Row {
+        name: name.clone(),
+        module: module.to_string(),
+        kind,
+        depth,
+        expanded: false,
+    };
+    let mut rows: Vec<Row> = Vec::new();
+    rows.extend(info.classes.iter().map(|name| symbol(name, Kind::Class)));
+    rows.extend(info.functions.iter().map(|name| symbol(name, Kind::Function)));
+    rows.extend(info.constants.iter().map(|name| symbol(name, Kind::Constant)));
+    rows.extend(
+        info.submodules
+            .keys()
+            .map(|sub| Row::module(&format!("{}.{}", module, sub), depth)),
+    );
+    rows
+}
+
+/// Remove the rows nested under an expanded row
+fn collapse(rows: &mut Vec<Row>, index: usize) {
+    let depth = rows[index].depth;
+    let end = rows[index + 1..]
+        .iter()
+        .position(|row| row.depth <= depth)
+        .map_or(rows.len(), |offset| index + 1 + offset);
+    rows.drain(index + 1..end);
+    rows[index].expanded = false;
+}
+
+/// The nearest row above `index` with a smaller depth
+fn parent_of(rows: &[Row], index: usize) -> Option<usize> {
+    let depth = rows[index].depth;
+    (0..index).rev().find(|&i| rows[i].depth < depth)
+}
+
+fn module_detail(info: &ModuleInfo) -> Vec<String> {
+    let mut lines = vec![
+        format!("{} classes", info.classes.len()),
+        format!("{} functions", info.functions.len()),
+        format!("{} constants", info.constants.len()),
+        format!("{} submodules", info.submodules.len()),
+    ];
+    if let Some(all) = &info.all_exports {
+        lines.push(String::new());
+        lines.push(format!("__all__: {}", all.join(", ")));
+    }
+    if !info.reexports.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("re-exports: {}", info.reexports.join(", ")));
+    }
+    lines
+}
+
+fn class_detail(info: &ModuleInfo, class_name: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(sig) = info.signatures.get(class_name) {
+        lines.extend(format_signature_compact(sig, Some(width)).lines().map(str::to_string));
+    } else {
+        lines.push(format!("class {}", class_name));
+    }
+    let Some(class_info) = info.class_info.get(class_name) else {
+        return lines;
+    };
+    if !class_info.bases.is_empty() {
+        lines.push(format!("bases: {}", class_info.bases.join(", ")));
+    }
+    let methods: Vec<_> = class_info
+        .methods
+        .iter()
+        .filter(|method| !method.name.starts_with('_'))
+        .filter_map(|method| info.signatures.get(&format!("{}.{}", class_name, method.name)))
+        .collect();
+    if !methods.is_empty() {
+        lines.push(String::new());
+        lines.push("Methods:".to_string());
+        for sig in methods {
+            lines.extend(format_signature_compact(sig, Some(width)).lines().map(str::to_string));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module_info() -> ModuleInfo {
+        let mut info = ModuleInfo::new();
+        info.classes = vec!["Client".to_string()];
+        info.functions = vec!["get".to_string()];
+        info.submodules.insert("models".to_string(), ModuleInfo::new());
+        info
+    }
+
+    #[test]
+    fn test_child_rows() {
+        let rows = child_rows(&module_info(), "httpx", 1);
+        let labels: Vec<(&str, Kind)> = rows.iter().map(|row| (row.label(), row.kind)).collect();
+        assert_eq!(
+            labels,
+            vec![("Client", Kind::Class), ("get", Kind::Function), ("models", Kind::Module)]
+        );
+        assert_eq!(rows[2].name, "httpx.models");
+        assert!(rows.iter().all(|row| row.depth == 1));
+    }
+
+    #[test]
+    fn test_collapse_and_parent() {
+        let mut rows = vec![Row::module("httpx", 0)];
+        rows[0].expanded = true;
+        rows.extend(child_rows(&module_info(), "httpx", 1));
+        rows[3].expanded = true;
+        rows.extend(child_rows(&module_info(), "httpx.models", 2));
+        rows.push(Row::module("other", 0));
+
+        assert_eq!(parent_of(&rows, 4), Some(3));
+        collapse(&mut rows, 3);
+        assert_eq!(rows.len(), 5);
+        assert!(!rows[3].expanded);
+        collapse(&mut rows, 0);
+        assert_eq!(
+            rows.iter().map(|row| row.name.as_str()).collect::<Vec<_>>(),
+            vec!["httpx", "other"]
+        );
+    }
+
+    #[test]
+    fn test_descend() {
+        let mut root = ModuleInfo::new();
+        let mut models = ModuleInfo::new();
+        models.classes = vec!["Request".to_string()];
+        root.submodules.insert("models".to_string(), models);
+        assert_eq!(descend(root, "httpx.models").classes, vec!["Request"]);
+    }
+}
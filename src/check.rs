@@ -0,0 +1,267 @@
+use crate::signature_object::{parse_parameters, Parameter, SignatureObject};
+use pyo3::prelude::*;
+use serde::Serialize;
+
+/// Result of comparing an API against expectations
+#[pyclass(get_all, frozen)]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CheckReport {
+    pub target: String,
+    pub ok: bool,
+    pub matched: Vec<String>,
+    pub missing: Vec<String>,
+    pub unexpected: Vec<String>,
+    pub mismatched: Vec<String>, // e.g., "retries: expected annotation 'int', found 'int | None'"
+}
+
+#[pymethods]
+impl CheckReport {
+    fn __bool__(&self) -> bool {
+        self.ok
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<CheckReport {} ok={} matched={} missing={:?} unexpected={:?} mismatched={:?}>",
+            self.target,
+            if self.ok { "True" } else { "False" },
+            self.matched.len(),
+            self.missing,
+            self.unexpected,
+            self.mismatched
+        )
+    }
+}
+
+impl CheckReport {
+    /// One-line summary of what failed, for assertion messages
+    pub fn summary(&self) -> String {
+        let mut problems = Vec::new();
+        if !self.missing.is_empty() {
+            problems.push(format!("missing: {}", self.missing.join(", ")));
+        }
+        if !self.unexpected.is_empty() {
+            problems.push(format!("unexpected: {}", self.unexpected.join(", ")));
+        }
+        if !self.mismatched.is_empty() {
+            problems.push(format!("mismatched: {}", self.mismatched.join("; ")));
+        }
+        format!("{} does not match expectations ({})", self.target, problems.join("; "))
+    }
+
+    /// Raise AssertionError in strict mode when the check failed
+    pub fn enforce(self, strict: bool) -> PyResult<Self> {
+        if strict && !self.ok {
+            return Err(PyErr::new::<pyo3::exceptions::PyAssertionError, _>(self.summary()));
+        }
+        Ok(self)
+    }
+}
+
+/// What a signature is expected to look like
+pub struct ExpectedSignature {
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<String>,
+    /// Compare kinds for every parameter (a full signature or explicit `*`/`/` markers)
+    pub kinds_specified: bool,
+    /// Extra actual parameters count as failures
+    pub exact: bool,
+}
+
+impl ExpectedSignature {
+    /// Expectations from a list of parameters, e.g. ["func", "*", "name: str", "**kwargs"]
+    pub fn from_params(params: &[String], return_type: Option<String>, exact: bool) -> Self {
+        Self {
+            parameters: parse_parameters(&params.join(", ")),
+            return_type,
+            kinds_specified: params.iter().any(|p| p.trim() == "*" || p.trim() == "/"),
+            exact,
+        }
+    }
+
+    /// Expectations from a full compact signature, e.g. "loads(s, *, cls=None, **kw)"
+    pub fn from_compact(expected: &str) -> PyResult<Self> {
+        let (_name, parameters, return_type) = crate::signature::parse_call_signature(expected)
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid expected signature '{}': use 'name(params) -> return'",
+                    expected
+                ))
+            })?;
+        Ok(Self {
+            parameters: parse_parameters(&parameters),
+            return_type,
+            kinds_specified: true,
+            exact: true,
+        })
+    }
+}
+
+/// Compare a resolved signature against expectations
+pub fn compare_signature(
+    target: &str,
+    actual: &SignatureObject,
+    expected: &ExpectedSignature,
+) -> CheckReport {
+    let mut report = CheckReport {
+        target: target.to_string(),
+        ..Default::default()
+    };
+
+    for want in &expected.parameters {
+        let Some(have) = actual.parameters.iter().find(|p| p.name == want.name) else {
+            report.missing.push(want.to_text());
+            continue;
+        };
+        let mut problems = Vec::new();
+        let compare_kind = expected.kinds_specified || want.kind.starts_with("var_");
+        if compare_kind && have.kind != want.kind {
+            problems.push(format!("expected {}, found {}", want.kind, have.kind));
+        }
+        if let Some(annotation) = &want.annotation {
+            let found = have.annotation.as_deref().unwrap_or("");
+            if normalize(annotation) != normalize(found) {
+                problems.push(format!(
+                    "expected annotation '{}', found '{}'",
+                    annotation, found
+                ));
+            }
+        }
+        if let Some(default) = &want.default {
+            let found = have.default.as_deref().unwrap_or("");
+            if normalize(default) != normalize(found) {
+                problems.push(format!("expected default '{}', found '{}'", default, found));
+            }
+        }
+        if problems.is_empty() {
+            report.matched.push(want.name.clone());
+        } else {
+            report
+                .mismatched
+                .push(format!("{}: {}", want.name, problems.join(", ")));
+        }
+    }
+
+    for have in &actual.parameters {
+        if !expected.parameters.iter().any(|p| p.name == have.name) {
+            report.unexpected.push(have.to_text());
+        }
+    }
+
+    if let Some(want) = &expected.return_type {
+        let found = actual.return_type.as_deref().unwrap_or("");
+        if normalize(want) != normalize(found) {
+            report.mismatched.push(format!(
+                "return: expected '{}', found '{}'",
+                want, found
+            ));
+        }
+    }
+
+    report.ok = report.missing.is_empty()
+        && report.mismatched.is_empty()
+        && (!expected.exact || report.unexpected.is_empty());
+    report
+}
+
+/// Compare a module's exports against an expected list
+pub fn compare_exports(
+    target: &str,
+    actual: &[String],
+    expected: &[String],
+    exact: bool,
+) -> CheckReport {
+    let mut report = CheckReport {
+        target: target.to_string(),
+        ..Default::default()
+    };
+    for name in expected {
+        if actual.contains(name) {
+            report.matched.push(name.clone());
+        } else {
+            report.missing.push(name.clone());
+        }
+    }
+    for name in actual {
+        if !expected.contains(name) {
+            report.unexpected.push(name.clone());
+        }
+    }
+    report.ok = report.missing.is_empty() && (!exact || report.unexpected.is_empty());
+    report
+}
+
+/// Annotation/default text with whitespace removed, so "int|None" equals "int | None"
+pub fn normalize(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module_info::FunctionSignature;
+
+    fn actual(parameters: &str, return_type: Option<&str>) -> SignatureObject {
+        SignatureObject::new(FunctionSignature {
+            name: "flow".to_string(),
+            parameters: parameters.to_string(),
+            return_type: return_type.map(str::to_string),
+            source: None,
+            kwargs: None,
+            param_docs: None,
+        })
+    }
+
+    #[test]
+    fn test_partial_params_ignore_extras() {
+        let sig = actual("func=None, *, name: str | None=None, retries: int=0", None);
+        let expected = ExpectedSignature::from_params(
+            &["func".to_string(), "name: str|None".to_string()],
+            None,
+            false,
+        );
+        let report = compare_signature("m:flow", &sig, &expected);
+        assert!(report.ok);
+        assert_eq!(report.matched, vec!["func", "name"]);
+        assert_eq!(report.unexpected, vec!["retries: int=0"]);
+    }
+
+    #[test]
+    fn test_missing_and_kind_mismatch() {
+        let sig = actual("func=None, *, name=None", None);
+        let expected = ExpectedSignature::from_params(
+            &["func".to_string(), "name".to_string(), "/".to_string(), "timeout".to_string()],
+            None,
+            false,
+        );
+        let report = compare_signature("m:flow", &sig, &expected);
+        assert!(!report.ok);
+        assert_eq!(report.missing, vec!["timeout"]);
+        assert_eq!(
+            report.mismatched,
+            vec![
+                "func: expected positional_only, found positional_or_keyword",
+                "name: expected positional_only, found keyword_only",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_full_compact_signature_is_exact() {
+        let sig = actual("s, *, cls=None, **kw", Some("Any"));
+        let expected = ExpectedSignature::from_compact("loads(s, *, cls=None) -> Any").unwrap();
+        let report = compare_signature("json:loads", &sig, &expected);
+        assert!(!report.ok);
+        assert_eq!(report.unexpected, vec!["**kw"]);
+    }
+
+    #[test]
+    fn test_compare_exports() {
+        let actual = vec!["dump".to_string(), "dumps".to_string(), "load".to_string()];
+        let expected = vec!["dump".to_string(), "loads".to_string()];
+        let report = compare_exports("json", &actual, &expected, false);
+        assert!(!report.ok);
+        assert_eq!(report.missing, vec!["loads"]);
+        assert_eq!(report.unexpected, vec!["dumps", "load"]);
+    }
+}
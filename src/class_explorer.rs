@@ -0,0 +1,526 @@
+use crate::config::{colorize, DisplayConfig};
+use crate::explorer::ModuleTreeExplorer;
+use crate::logging::debug_log;
+use crate::module_info::{ClassInfo, FunctionSignature, ImportInfo, ModuleInfo};
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// How many import hops or base classes to follow before giving up
+const MAX_RESOLUTION_DEPTH: usize = 8;
+
+/// Dunder methods that define how an instance is used, by protocol
+/// The single source for what `Dunders::Protocol` shows.
+const PROTOCOL_DUNDERS: &[(&str, &[&str])] = &[
+    ("context manager", &["__enter__", "__exit__", "__aenter__", "__aexit__"]),
+    ("iteration", &["__iter__", "__next__", "__aiter__", "__anext__", "__reversed__"]),
+    (
+        "container",
+        &["__len__", "__contains__", "__getitem__", "__setitem__", "__delitem__", "__missing__"],
+    ),
+    ("comparison", &["__eq__", "__ne__", "__lt__", "__le__", "__gt__", "__ge__", "__hash__"]),
+];
+
+/// Which dunder methods a class view lists (constructors are always listed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dunders {
+    #[default]
+    Hide,
+    Protocol,
+    All,
+}
+
+impl Dunders {
+    /// Parse the public `dunders` option: "hide", "protocol", or "all"
+    pub fn parse(value: &str) -> PyResult<Self> {
+        match value.to_lowercase().as_str() {
+            "hide" => Ok(Dunders::Hide),
+            "protocol" => Ok(Dunders::Protocol),
+            "all" => Ok(Dunders::All),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid dunders option '{}': use 'hide', 'protocol', or 'all'",
+                value
+            ))),
+        }
+    }
+
+    /// Whether a dunder method is listed at this level
+    fn shows(self, name: &str) -> bool {
+        match self {
+            Dunders::Hide => false,
+            Dunders::Protocol => protocol_of(name).is_some(),
+            Dunders::All => true,
+        }
+    }
+}
+
+/// The protocol a dunder method belongs to, e.g. "__exit__" -> "context manager"
+fn protocol_of(name: &str) -> Option<&'static str> {
+    PROTOCOL_DUNDERS
+        .iter()
+        .find(|(_, names)| names.contains(&name))
+        .map(|(protocol, _)| *protocol)
+}
+
+fn is_dunder(name: &str) -> bool {
+    name.len() > 4 && name.starts_with("__") && name.ends_with("__")
+}
+
+/// A method as shown in the class view
+#[derive(Serialize, Clone, Debug, IntoPyObject)]
+pub struct MethodEntry {
+    pub name: String,
+    pub kind: String, // "method", "property", "classmethod", or "staticmethod"
+    pub signature: FunctionSignature,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defined_in: Option<String>, // Base class that defines an inherited member
+}
+
+/// Methods of a class grouped the way they are used
+#[derive(Serialize, Clone, Debug, Default, IntoPyObject)]
+pub struct ClassView {
+    pub name: String,
+    pub module: String,
+    pub bases: Vec<String>,
+    pub is_abstract: bool,
+    pub constructors: Vec<MethodEntry>,
+    pub methods: Vec<MethodEntry>,
+    pub properties: Vec<MethodEntry>,
+    pub class_methods: Vec<MethodEntry>, // classmethods and staticmethods
+    pub inherited: Vec<MethodEntry>,
+}
+
+/// A class located in the filesystem, with the module it was defined in
+struct LocatedClass {
+    module_path: String,
+    class_name: String,
+    class_info: ClassInfo,
+    module_info: ModuleInfo,
+}
+
+/// Explore a class by import path (module:Class or module.Class)
+pub fn explore_class(
+    py: Python,
+    import_path: &str,
+    include_inherited: bool,
+    dunders: Dunders,
+    verbosity: Verbosity,
+) -> PyResult<ClassView> {
+    let (package_override, module_path, class_name, version) =
+        crate::utils::parse_object_spec(import_path).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid class path '{}': use 'module:Class' or 'module.Class'",
+                import_path
+            ))
+        })?;
+    debug_log!("Exploring class {}:{}", module_path, class_name);
+
+    // Map installed distribution names to import names (beautifulsoup4 -> bs4)
+    let local_candidate = if package_override.is_none() {
+        crate::dist_metadata::local_import_candidates(py, module_path)
+            .into_iter()
+            .next()
+    } else {
+        None
+    };
+    let requested_module = module_path;
+    let module_path = local_candidate.as_deref().unwrap_or(module_path);
+
+    if let Some(located) = locate_class(py, module_path, class_name, verbosity, 0) {
+        return Ok(build_view(py, located, include_inherited, dunders, verbosity));
+    }
+
+    let not_found = || {
+        PyErr::new::<pyo3::exceptions::PyImportError, _>(format!(
+            "cannot find class '{}' in '{}'",
+            class_name, module_path
+        ))
+    };
+    if crate::stdlib::is_stdlib_module(module_path) {
+        return Err(not_found());
+    }
+
+    // Not available locally, so try downloading the package
+    crate::utils::try_download_module(
+        py,
+        requested_module,
+        package_override,
+        version,
+        false,
+        verbosity,
+        |_| {
+            // A namespace package's candidate distributions may not contain the module
+            if !crate::utils::provides_module(py, module_path) {
+                return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(
+                    format!("No module named '{}'", module_path),
+                ));
+            }
+            locate_class(py, module_path, class_name, verbosity, 0)
+                .map(|located| build_view(py, located, include_inherited, dunders, verbosity))
+                .ok_or_else(not_found)
+        },
+    )
+}
+
+/// Find where a class is defined, following re-exports through import chains
+fn locate_class(
+    py: Python,
+    module_path: &str,
+    class_name: &str,
+    verbosity: Verbosity,
+    depth: usize,
+) -> Option<LocatedClass> {
+    if depth > MAX_RESOLUTION_DEPTH {
+        return None;
+    }
+    let explorer = ModuleTreeExplorer::new(module_path.to_string(), 0);
+    let module_info = explorer.explore_module_pure_filesystem(py, module_path).ok()?;
+
+    if let Some(class_info) = module_info.class_info.get(class_name) {
+        return Some(LocatedClass {
+            module_path: module_path.to_string(),
+            class_name: class_name.to_string(),
+            class_info: class_info.clone(),
+            module_info,
+        });
+    }
+
+    // Follow "from x import Class" re-exports
+    let import_info = module_info.import_map.get(class_name)?;
+    let target = import_target(module_path, module_info.is_package(), import_info)?;
+    verbosity.detail(&format!(
+        "chain hop: {}:{} -> {}:{}",
+        module_path, class_name, target, import_info.import_name
+    ));
+    locate_class(py, &target, &import_info.import_name, verbosity, depth + 1)
+}
+
+/// The module an import refers to (the imported module itself for `import x`)
+fn import_target(module_path: &str, is_package: bool, import_info: &ImportInfo) -> Option<String> {
+    if import_info.level == 0 && import_info.from_module.is_none() {
+        return Some(import_info.import_name.clone());
+    }
+    import_info.source_module(module_path, is_package)
+}
+
+/// Resolve a base class expression (as written in the class statement) to its definition
+fn locate_base(
+    py: Python,
+    context: &LocatedClass,
+    base: &str,
+    verbosity: Verbosity,
+) -> Option<LocatedClass> {
+    // Drop generic parameters, e.g. "Generic[T]" -> "Generic"
+    let base = base.split('[').next().unwrap_or(base).trim();
+
+    if let Some((qualifier, name)) = base.rsplit_once('.') {
+        // "module.Base" where module was imported (possibly under an alias)
+        let head = qualifier.split('.').next().unwrap_or(qualifier);
+        let rest = &qualifier[head.len()..]; // e.g., ".sub" or ""
+        let module_path = match context.module_info.import_map.get(head) {
+            Some(import_info) if import_info.from_module.is_none() => {
+                format!("{}{}", import_info.import_name, rest)
+            }
+            Some(import_info) => format!(
+                "{}.{}{}",
+                import_targets(&context.module_path, import_info).first()?,
+                import_info.import_name,
+                rest
+            ),
+            None => qualifier.to_string(),
+        };
+        return locate_class(py, &module_path, name, verbosity, 0);
+    }
+
+    if context.module_info.class_info.contains_key(base) {
+        return locate_class(py, &context.module_path, base, verbosity, 0);
+    }
+    let import_info = context.module_info.import_map.get(base)?;
+    import_targets(&context.module_path, import_info)
+        .into_iter()
+        .find_map(|target| locate_class(py, &target, &import_info.import_name, verbosity, 1))
+}
+
+/// Classify and collect the methods of a located class (and optionally its bases)
+fn build_view(
+    py: Python,
+    located: LocatedClass,
+    include_inherited: bool,
+    dunders: Dunders,
+    verbosity: Verbosity,
+) -> ClassView {
+    let mut view = ClassView {
+        name: located.class_name.clone(),
+        module: located.module_path.clone(),
+        bases: located.class_info.bases.clone(),
+        is_abstract: located.class_info.is_abstract,
+        ..Default::default()
+    };
+
+    let mut seen: HashSet<String> = HashSet::new();
+    for entry in method_entries(&located, None, dunders) {
+        seen.insert(entry.name.clone());
+        match entry.kind.as_str() {
+            "property" => view.properties.push(entry),
+            "classmethod" | "staticmethod" => view.class_methods.push(entry),
+            _ if matches!(entry.name.as_str(), "__init__" | "__new__") => {
+                view.constructors.push(entry)
+            }
+            _ => view.methods.push(entry),
+        }
+    }
+
+    if include_inherited {
+        // Depth-first walk of the bases; the nearest definition wins (an MRO approximation)
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut stack: Vec<(LocatedClass, usize)> = vec![(located, 0)];
+        while let Some((current, depth)) = stack.pop() {
+            if depth > 0 {
+                for entry in method_entries(&current, Some(current.class_name.as_str()), dunders) {
+                    if seen.insert(entry.name.clone()) {
+                        view.inherited.push(entry);
+                    }
+                }
+            }
+            if depth >= MAX_RESOLUTION_DEPTH {
+                continue;
+            }
+            // Push in reverse so the first base is explored first
+            for base in current.class_info.bases.iter().rev() {
+                match locate_base(py, &current, base, verbosity) {
+                    Some(base_class) => {
+                        let key = (base_class.module_path.clone(), base_class.class_name.clone());
+                        if visited.insert(key) {
+                            stack.push((base_class, depth + 1));
+                        }
+                    }
+                    None => verbosity.detail(&format!("could not resolve base class {}", base)),
+                }
+            }
+        }
+    }
+
+    view
+}
+
+/// Build entries for the public members of one class (plus constructors and
+/// whichever dunder methods `dunders` lets through)
+fn method_entries(
+    located: &LocatedClass,
+    defined_in: Option<&str>,
+    dunders: Dunders,
+) -> Vec<MethodEntry> {
+    let mut entries: Vec<MethodEntry> = Vec::new();
+    for method in &located.class_info.methods {
+        let name = method.name.as_str();
+        let is_constructor = matches!(name, "__init__" | "__new__");
+        let shown_dunder = is_dunder(name) && dunders.shows(name);
+        if name.starts_with('_') && !is_constructor && !shown_dunder {
+            continue;
+        }
+        // Setters and deleters share the property's name; list the property once
+        if entries.iter().any(|entry| entry.name == name) {
+            continue;
+        }
+        let qualified = format!("{}.{}", located.class_name, name);
+        let Some(signature) = located.module_info.signatures.get(&qualified) else {
+            continue;
+        };
+        entries.push(MethodEntry {
+            name: name.to_string(),
+            kind: method_kind(&method.decorators).to_string(),
+            signature: signature.clone(),
+            defined_in: defined_in.map(str::to_string),
+        });
+    }
+    entries
+}
+
+/// Classify a method by its decorators
+fn method_kind(decorators: &[String]) -> &'static str {
+    for decorator in decorators {
+        let last = decorator.rsplit('.').next().unwrap_or(decorator);
+        match last {
+            "property" | "cached_property" | "setter" | "getter" | "deleter" => {
+                return "property"
+            }
+            "classmethod" => return "classmethod",
+            "staticmethod" => return "staticmethod",
+            _ => {}
+        }
+    }
+    "method"
+}
+
+/// Render a class view as a tree
+pub fn format_class_display(view: &ClassView) -> String {
+    let config = DisplayConfig::get();
+    let mut result = format!(
+        "{} {}{} ({})\n",
+        colorize(&config.class_icon, &config.color_scheme.class_color, config),
+        colorize(&view.name, &config.color_scheme.class_color, config),
+        if view.is_abstract { " (abstract)" } else { "" },
+        colorize(&view.module, &config.color_scheme.module_color, config)
+    );
+
+    let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+    if !view.bases.is_empty() {
+        sections.push((format!("Bases: {}", view.bases.join(", ")), Vec::new()));
+    }
+    let groups = [
+        ("Constructors", &view.constructors),
+        ("Methods", &view.methods),
+        ("Properties", &view.properties),
+        ("Class & static methods", &view.class_methods),
+        ("Inherited", &view.inherited),
+    ];
+    for (title, entries) in groups {
+        if !entries.is_empty() {
+            let lines = entries.iter().map(format_entry).collect();
+            sections.push((format!("{}:", title), lines));
+        }
+    }
+
+    if sections.is_empty() {
+        result.push_str(&format!(
+            "{} (no public methods)",
+            colorize(&config.tree_last, &config.color_scheme.tree_color, config)
+        ));
+        return result;
+    }
+
+    let section_count = sections.len();
+    for (i, (title, lines)) in sections.into_iter().enumerate() {
+        let is_last_section = i == section_count - 1;
+        let (prefix, child_prefix) = if is_last_section {
+            (&config.tree_last, &config.tree_empty)
+        } else {
+            (&config.tree_branch, &config.tree_vertical)
+        };
+        result.push_str(&format!(
+            "{}{}\n",
+            colorize(prefix, &config.color_scheme.tree_color, config),
+            title
+        ));
+        let line_count = lines.len();
+        for (j, line) in lines.into_iter().enumerate() {
+            let connector = if j == line_count - 1 {
+                &config.tree_last
+            } else {
+                &config.tree_branch
+            };
+            result.push_str(&format!(
+                "{}{}{}\n",
+                colorize(child_prefix, &config.color_scheme.tree_color, config),
+                colorize(connector, &config.color_scheme.tree_color, config),
+                line
+            ));
+        }
+    }
+
+    result.trim_end().to_string()
+}
+
+/// One line per member: `name(params) -> return`, `name: type` for properties
+fn format_entry(entry: &MethodEntry) -> String {
+    let config = DisplayConfig::get();
+    let sig = &entry.signature;
+    let mut line = match entry.kind.as_str() {
+        "property" => match &sig.return_type {
+            Some(return_type) => format!("{}: {}", entry.name, return_type),
+            None => entry.name.clone(),
+        },
+        _ => {
+            let mut text = format!("{}({})", entry.name, sig.parameters);
+            if let Some(return_type) = &sig.return_type {
+                text.push_str(&format!(" -> {}", return_type));
+            }
+            text
+        }
+    };
+    line = colorize(&line, &config.color_scheme.function_color, config);
+    if matches!(entry.kind.as_str(), "classmethod" | "staticmethod") {
+        line.push_str(&format!(" [{}]", entry.kind));
+    }
+    if let Some(defined_in) = &entry.defined_in {
+        line.push_str(&format!(" (from {})", defined_in));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_method_kind() {
+        assert_eq!(method_kind(&["property".to_string()]), "property");
+        assert_eq!(method_kind(&["functools.cached_property".to_string()]), "property");
+        assert_eq!(method_kind(&["name.setter".to_string()]), "property");
+        assert_eq!(method_kind(&["classmethod".to_string()]), "classmethod");
+        assert_eq!(method_kind(&["staticmethod".to_string()]), "staticmethod");
+        assert_eq!(method_kind(&[]), "method");
+    }
+
+    #[test]
+    fn test_dunder_visibility() {
+        assert!(!Dunders::Hide.shows("__enter__"));
+        assert!(Dunders::Protocol.shows("__enter__"));
+        assert!(Dunders::Protocol.shows("__getitem__"));
+        assert!(!Dunders::Protocol.shows("__repr__"));
+        assert!(Dunders::All.shows("__repr__"));
+        assert_eq!(protocol_of("__lt__"), Some("comparison"));
+        assert!(is_dunder("__iter__"));
+        assert!(!is_dunder("_private") && !is_dunder("____"));
+        assert_eq!(Dunders::parse("Protocol").unwrap(), Dunders::Protocol);
+    }
+
+    #[test]
+    fn test_import_target() {
+        let import = |from_module: Option<&str>, import_name: &str, level: u32| ImportInfo {
+            from_module: from_module.map(str::to_string),
+            import_name: import_name.to_string(),
+            as_name: None,
+            is_relative: level > 0,
+            level,
+        };
+
+        let sibling = import(Some("sessions"), "Session", 1);
+        assert_eq!(
+            import_target("requests", true, &sibling).as_deref(),
+            Some("requests.sessions")
+        );
+        assert_eq!(
+            import_target("requests.api", false, &sibling).as_deref(),
+            Some("requests.sessions")
+        );
+
+        let two_up = import(Some("models"), "Base", 2);
+        assert_eq!(
+            import_target("pkg.sub.impl", false, &two_up).as_deref(),
+            Some("pkg.models")
+        );
+        assert_eq!(
+            import_target("pkg.sub", true, &two_up).as_deref(),
+            Some("pkg.models")
+        );
+        assert_eq!(import_target("pkg.sub", false, &two_up), None);
+
+        let from_package = import(None, "Session", 1);
+        assert_eq!(
+            import_target("requests.api", false, &from_package).as_deref(),
+            Some("requests")
+        );
+
+        let absolute = import(Some("collections"), "OrderedDict", 0);
+        assert_eq!(
+            import_target("x", false, &absolute).as_deref(),
+            Some("collections")
+        );
+        assert_eq!(
+            import_target("x", false, &import(None, "abc", 0)).as_deref(),
+            Some("abc")
+        );
+    }
+}
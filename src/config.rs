@@ -1,4 +1,7 @@
+use pyo3::prelude::*;
 use std::env;
+use std::io::IsTerminal;
+use std::path::Path;
 use std::sync::OnceLock;
 
 /// Configuration for display characters and styling
@@ -22,7 +25,19 @@ pub struct DisplayConfig {
 
     // Color configuration
     pub use_color: bool,
+    pub color_depth: ColorDepth,
     pub color_scheme: ColorScheme,
+
+    // OSC 8 links from module names to their source files
+    pub hyperlinks: bool,
+}
+
+/// How many colors the terminal can show; hex colors are matched to the nearest one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    Ansi16,
+    Ansi256,
+    TrueColor,
 }
 
 #[derive(Debug, Clone)]
@@ -78,7 +93,11 @@ impl Default for DisplayConfig {
 
             // Color enabled by default
             use_color: true,
+            color_depth: ColorDepth::TrueColor,
             color_scheme: ColorScheme::default(),
+
+            // Opt-in: not every terminal renders OSC 8
+            hyperlinks: false,
         }
     }
 }
@@ -95,8 +114,13 @@ impl DisplayConfig {
     fn from_env() -> Self {
         let mut config = Self::default();
 
-        // Check if we should use ASCII-only mode
-        if env::var("PRETTY_MOD_ASCII").is_ok() {
+        // Check if we should use ASCII-only mode: PRETTY_MOD_ASCII=0/1 decides,
+        // otherwise follow what the output encoding can represent
+        let ascii = match env::var("PRETTY_MOD_ASCII") {
+            Ok(val) => val.trim() != "0",
+            Err(_) => !output_supports_unicode(),
+        };
+        if ascii {
             config.use_ascii_mode();
         }
 
@@ -135,6 +159,19 @@ impl DisplayConfig {
         if env::var("PRETTY_MOD_NO_COLOR").is_ok() || env::var("NO_COLOR").is_ok() {
             config.use_color = false;
         }
+        if config.use_color {
+            match detect_color_depth() {
+                Some(depth) => config.color_depth = depth,
+                None => config.use_color = false,
+            }
+        }
+
+        // Hyperlinks are opt-in and only written to a terminal
+        if env::var("PRETTY_MOD_HYPERLINKS").is_ok_and(|val| val != "0")
+            && std::io::stdout().is_terminal()
+        {
+            config.hyperlinks = true;
+        }
 
         // Color scheme overrides
         if let Ok(val) = env::var("PRETTY_MOD_MODULE_COLOR") {
@@ -190,22 +227,277 @@ impl DisplayConfig {
     }
 }
 
+/// Whether stdout can encode the Unicode icons
+/// Python's `sys.stdout.encoding` reflects redirection, PYTHONIOENCODING and UTF-8 mode;
+/// the locale variables are the fallback. Unknown encodings are assumed capable.
+fn output_supports_unicode() -> bool {
+    match python_stdout_encoding().or_else(locale_encoding) {
+        Some(encoding) => is_unicode_encoding(&encoding),
+        None => true,
+    }
+}
+
+fn python_stdout_encoding() -> Option<String> {
+    // SAFETY: only reads interpreter state; the config may be built before Python is
+    // running (e.g., in Rust unit tests), where acquiring the GIL would panic
+    if unsafe { pyo3::ffi::Py_IsInitialized() } == 0 {
+        return None;
+    }
+    Python::with_gil(|py| {
+        py.import("sys")
+            .and_then(|sys| sys.getattr("stdout"))
+            .and_then(|stdout| stdout.getattr("encoding"))
+            .and_then(|encoding| encoding.extract::<String>())
+            .ok()
+    })
+}
+
+/// The character encoding named by LC_ALL, LC_CTYPE, or LANG (first one set)
+fn locale_encoding() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map(|locale| encoding_of_locale(&locale))
+}
+
+/// e.g., "en_US.UTF-8" -> "UTF-8", "C" -> "ascii"
+fn encoding_of_locale(locale: &str) -> String {
+    match locale.split_once('.') {
+        Some((_, encoding)) => encoding.split('@').next().unwrap_or(encoding).to_string(),
+        None if matches!(locale, "C" | "POSIX") => "ascii".to_string(),
+        None => "UTF-8".to_string(),
+    }
+}
+
+fn is_unicode_encoding(encoding: &str) -> bool {
+    let normalized: String = encoding
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    normalized.starts_with("utf") || normalized == "cp65001"
+}
+
+/// The colors the terminal supports, or None when it can't show color at all
+/// PRETTY_MOD_COLOR_DEPTH (16, 256, truecolor, or none) decides; otherwise COLORTERM and
+/// TERM are consulted, and terminals that don't say are assumed to support truecolor.
+fn detect_color_depth() -> Option<ColorDepth> {
+    if let Some(depth) = env::var("PRETTY_MOD_COLOR_DEPTH")
+        .ok()
+        .and_then(|val| parse_color_depth(&val))
+    {
+        return depth;
+    }
+
+    // Legacy Windows consoles print escape sequences literally
+    if std::io::stdout().is_terminal() && !enable_virtual_terminal() {
+        return None;
+    }
+
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+    let term = env::var("TERM").unwrap_or_default();
+    term_color_depth(&colorterm, &term)
+}
+
+/// e.g., "256" -> Some(Some(Ansi256)), "none" -> Some(None); unrecognized -> None
+fn parse_color_depth(value: &str) -> Option<Option<ColorDepth>> {
+    match value.trim().to_lowercase().as_str() {
+        "truecolor" | "24bit" | "24" => Some(Some(ColorDepth::TrueColor)),
+        "256" | "8bit" => Some(Some(ColorDepth::Ansi256)),
+        "16" | "8" | "ansi" => Some(Some(ColorDepth::Ansi16)),
+        "none" | "0" => Some(None),
+        _ => None,
+    }
+}
+
+/// The color depth advertised by the COLORTERM and TERM variables
+fn term_color_depth(colorterm: &str, term: &str) -> Option<ColorDepth> {
+    if matches!(colorterm, "truecolor" | "24bit") || term.ends_with("-direct") {
+        return Some(ColorDepth::TrueColor);
+    }
+    if term == "dumb" {
+        return None;
+    }
+    if term.contains("256color") {
+        return Some(ColorDepth::Ansi256);
+    }
+    let base = term.split('-').next().unwrap_or(term);
+    if matches!(
+        base,
+        "linux" | "xterm" | "vt100" | "vt220" | "ansi" | "cygwin" | "screen" | "tmux" | "rxvt"
+    ) && !term.contains("color")
+    {
+        return Some(ColorDepth::Ansi16);
+    }
+    Some(ColorDepth::TrueColor)
+}
+
+/// Turn on ANSI escape processing for a Windows console; false for consoles without it
+#[cfg(windows)]
+fn enable_virtual_terminal() -> bool {
+    type Handle = *mut std::ffi::c_void;
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+    }
+
+    // SAFETY: console mode queries on this process's own stdout handle
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        let mut mode = 0;
+        if handle.is_null() || handle as isize == -1 || GetConsoleMode(handle, &mut mode) == 0 {
+            return false;
+        }
+        mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+            || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
+
+/// Terminals elsewhere process escape sequences natively
+#[cfg(not(windows))]
+fn enable_virtual_terminal() -> bool {
+    true
+}
+
+/// Detect the terminal width for wrapping output
+/// Honors `COLUMNS`; returns None when stdout is not a terminal (no wrapping)
+pub fn terminal_width() -> Option<usize> {
+    if let Some(columns) = env::var("COLUMNS").ok().and_then(|v| v.trim().parse().ok()) {
+        return Some(columns);
+    }
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    Python::with_gil(|py| {
+        py.import("shutil")
+            .and_then(|shutil| shutil.call_method0("get_terminal_size"))
+            .and_then(|size| size.getattr("columns"))
+            .and_then(|columns| columns.extract::<usize>())
+            .ok()
+    })
+    .filter(|&columns| columns > 0)
+}
+
 /// helper to format text with color if enabled
 pub fn colorize(text: &str, color: &str, config: &DisplayConfig) -> String {
     if !config.use_color {
         return text.to_string();
     }
 
-    // Convert hex color to ANSI escape code
-    if let Some(rgb) = parse_hex_color(color) {
-        format!("\x1b[38;2;{};{};{}m{}\x1b[0m", rgb.0, rgb.1, rgb.2, text)
+    // Convert hex color to ANSI escape code the terminal understands
+    let Some(rgb) = parse_hex_color(color) else {
+        return text.to_string();
+    };
+    match config.color_depth {
+        ColorDepth::TrueColor => {
+            format!("\x1b[38;2;{};{};{}m{}\x1b[0m", rgb.0, rgb.1, rgb.2, text)
+        }
+        ColorDepth::Ansi256 => format!("\x1b[38;5;{}m{}\x1b[0m", nearest_ansi256(rgb), text),
+        ColorDepth::Ansi16 => format!("\x1b[{}m{}\x1b[0m", nearest_ansi16(rgb), text),
+    }
+}
+
+/// The xterm palette of the 16 basic colors (SGR 30-37, then bright 90-97)
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Levels of each channel in the 256-color palette's 6x6x6 cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+/// The SGR foreground code of the closest basic color, e.g. (205, 0, 0) -> 31
+fn nearest_ansi16(rgb: (u8, u8, u8)) -> u8 {
+    let index = (0..ANSI16_PALETTE.len())
+        .min_by_key(|&i| color_distance(rgb, ANSI16_PALETTE[i]))
+        .unwrap_or(7) as u8;
+    if index < 8 {
+        30 + index
     } else {
-        text.to_string()
+        90 + index - 8
     }
 }
 
+/// The closest 256-color palette index: a color cube entry or a grayscale step
+fn nearest_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - c as i32).abs())
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    // Grayscale ramp 232-255 covers 8, 18, ..., 238
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * step;
+
+    if color_distance(rgb, (gray, gray, gray)) < color_distance(rgb, cube) {
+        232 + step
+    } else {
+        16 + 36 * r as u8 + 6 * g as u8 + b as u8
+    }
+}
+
+/// helper to wrap (possibly colorized) text in an OSC 8 link to a file, if enabled
+pub fn hyperlink(text: &str, path: &Path, config: &DisplayConfig) -> String {
+    if !config.hyperlinks {
+        return text.to_string();
+    }
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", file_url(path), text)
+}
+
+/// A `file://` URL for a path, percent-encoding anything outside the unreserved set
+fn file_url(path: &Path) -> String {
+    let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut text = absolute.to_string_lossy().replace('\\', "/");
+    // Windows verbatim prefix from canonicalize
+    if let Some(stripped) = text.strip_prefix("//?/") {
+        text = stripped.to_string();
+    }
+    if !text.starts_with('/') {
+        text.insert(0, '/');
+    }
+    let mut url = String::from("file://");
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    url
+}
+
 /// parse hex color string to RGB values
-fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+pub fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
     let color = color.trim_start_matches('#');
     if color.len() != 6 {
         return None;
@@ -217,3 +509,84 @@ fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
 
     Some((r, g, b))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unicode_encoding_detection() {
+        assert!(is_unicode_encoding("utf-8"));
+        assert!(is_unicode_encoding("UTF8"));
+        assert!(is_unicode_encoding("cp65001"));
+        assert!(!is_unicode_encoding("cp1252"));
+        assert!(!is_unicode_encoding("ANSI_X3.4-1968"));
+
+        assert_eq!(encoding_of_locale("en_US.UTF-8"), "UTF-8");
+        assert_eq!(encoding_of_locale("de_DE.ISO-8859-15@euro"), "ISO-8859-15");
+        assert_eq!(encoding_of_locale("POSIX"), "ascii");
+    }
+
+    #[test]
+    fn test_color_depth_detection() {
+        assert_eq!(parse_color_depth("256"), Some(Some(ColorDepth::Ansi256)));
+        assert_eq!(
+            parse_color_depth("TrueColor"),
+            Some(Some(ColorDepth::TrueColor))
+        );
+        assert_eq!(parse_color_depth("none"), Some(None));
+        assert_eq!(parse_color_depth("auto"), None);
+
+        let depth = term_color_depth;
+        assert_eq!(
+            depth("truecolor", "xterm-256color"),
+            Some(ColorDepth::TrueColor)
+        );
+        assert_eq!(depth("", "xterm-256color"), Some(ColorDepth::Ansi256));
+        assert_eq!(depth("", "xterm"), Some(ColorDepth::Ansi16));
+        assert_eq!(depth("", "linux"), Some(ColorDepth::Ansi16));
+        assert_eq!(depth("", "dumb"), None);
+        assert_eq!(depth("", "alacritty"), Some(ColorDepth::TrueColor));
+    }
+
+    #[test]
+    fn test_downconverted_colors() {
+        assert_eq!(nearest_ansi256((0, 0, 0)), 16);
+        assert_eq!(nearest_ansi256((255, 255, 255)), 231);
+        assert_eq!(nearest_ansi256((128, 128, 128)), 244);
+        assert_eq!(nearest_ansi256((95, 135, 175)), 67);
+        assert_eq!(nearest_ansi16((205, 0, 0)), 31);
+        assert_eq!(nearest_ansi16((250, 250, 250)), 97);
+
+        let config = DisplayConfig {
+            color_depth: ColorDepth::Ansi256,
+            ..DisplayConfig::default()
+        };
+        assert_eq!(colorize("x", "#808080", &config), "\x1b[38;5;244mx\x1b[0m");
+        let config = DisplayConfig {
+            color_depth: ColorDepth::Ansi16,
+            ..DisplayConfig::default()
+        };
+        assert_eq!(colorize("x", "#CD0000", &config), "\x1b[31mx\x1b[0m");
+    }
+
+    #[test]
+    fn test_hyperlink_wraps_colored_text() {
+        let config = DisplayConfig {
+            hyperlinks: true,
+            ..DisplayConfig::default()
+        };
+        let label = colorize("json", "#8B7355", &config);
+        let linked = hyperlink(&label, Path::new("/usr/lib/my lib/json.py"), &config);
+        assert_eq!(
+            linked,
+            format!(
+                "\x1b]8;;file:///usr/lib/my%20lib/json.py\x1b\\{}\x1b]8;;\x1b\\",
+                label
+            )
+        );
+
+        let plain = DisplayConfig::default();
+        assert_eq!(hyperlink("json", Path::new("/json.py"), &plain), "json");
+    }
+}
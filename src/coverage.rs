@@ -0,0 +1,275 @@
+use crate::config::{colorize, DisplayConfig};
+use crate::explorer::ModuleTreeExplorer;
+use crate::module_info::{is_public_name, FunctionSignature, ModuleInfo};
+use crate::signature_object::parse_parameters;
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use serde::Serialize;
+
+/// How many of the least annotated functions to list
+const WORST_OFFENDERS: usize = 10;
+
+/// Functions by how completely they are annotated
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Coverage {
+    pub full: usize,
+    pub partial: usize,
+    pub unannotated: usize,
+}
+
+impl Coverage {
+    pub fn total(&self) -> usize {
+        self.full + self.partial + self.unannotated
+    }
+
+    /// Share of fully annotated functions, in percent (100 when there are none)
+    pub fn percent(&self) -> f64 {
+        match self.total() {
+            0 => 100.0,
+            total => self.full as f64 * 100.0 / total as f64,
+        }
+    }
+
+    fn add(&mut self, other: &Coverage) {
+        self.full += other.full;
+        self.partial += other.partial;
+        self.unannotated += other.unannotated;
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ModuleCoverage {
+    pub module: String,
+    #[serde(flatten)]
+    pub coverage: Coverage,
+}
+
+/// A function missing annotations, e.g. "mypkg.codec:encode" missing 3
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Offender {
+    pub name: String,
+    pub missing: usize,
+}
+
+/// Annotation coverage of a module tree
+#[derive(Clone, Debug, Serialize)]
+pub struct CoverageReport {
+    pub module: String,
+    pub total: Coverage,
+    pub modules: Vec<ModuleCoverage>,
+    pub least_annotated: Vec<Offender>,
+}
+
+/// Walk a module tree and count annotated public functions and methods
+pub fn module_coverage(
+    py: Python,
+    module_path: &str,
+    max_depth: usize,
+    verbosity: Verbosity,
+) -> PyResult<CoverageReport> {
+    let explorer =
+        ModuleTreeExplorer::new(module_path.to_string(), max_depth).with_verbosity(verbosity);
+    let tree = explorer.explore_module_pure_filesystem(py, module_path)?;
+    Ok(coverage_report(module_path, &tree))
+}
+
+/// Aggregate per-module and total coverage for an explored tree
+pub fn coverage_report(module_path: &str, tree: &ModuleInfo) -> CoverageReport {
+    let mut report = CoverageReport {
+        module: module_path.to_string(),
+        total: Coverage::default(),
+        modules: Vec::new(),
+        least_annotated: Vec::new(),
+    };
+    collect(tree, module_path, &mut report);
+    report
+        .least_annotated
+        .sort_by(|a, b| b.missing.cmp(&a.missing).then_with(|| a.name.cmp(&b.name)));
+    report.least_annotated.truncate(WORST_OFFENDERS);
+    report
+}
+
+fn collect(info: &ModuleInfo, module_path: &str, report: &mut CoverageReport) {
+    let mut coverage = Coverage::default();
+    for (name, sig) in &info.signatures {
+        if !is_public_name(name) {
+            continue;
+        }
+        let (annotated, missing) = annotation_counts(name, sig);
+        if missing == 0 {
+            coverage.full += 1;
+        } else if annotated > 0 {
+            coverage.partial += 1;
+        } else {
+            coverage.unannotated += 1;
+        }
+        if missing > 0 {
+            report.least_annotated.push(Offender {
+                name: format!("{}:{}", module_path, name),
+                missing,
+            });
+        }
+    }
+    report.total.add(&coverage);
+    report.modules.push(ModuleCoverage {
+        module: module_path.to_string(),
+        coverage,
+    });
+
+    for (name, submodule) in &info.submodules {
+        collect(submodule, &format!("{}.{}", module_path, name), report);
+    }
+}
+
+/// (annotated, missing) counts over a signature's parameters and return type
+/// `self`/`cls` and `*args`/`**kwargs` aren't counted, nor is the return type of `__init__`.
+fn annotation_counts(name: &str, sig: &FunctionSignature) -> (usize, usize) {
+    let is_method = name.contains('.');
+    let mut annotated = 0;
+    let mut missing = 0;
+    for (i, param) in parse_parameters(&sig.parameters).iter().enumerate() {
+        if param.kind.starts_with("var_")
+            || (is_method && i == 0 && matches!(param.name.as_str(), "self" | "cls"))
+        {
+            continue;
+        }
+        if param.annotation.is_some() {
+            annotated += 1;
+        } else {
+            missing += 1;
+        }
+    }
+    if sig.return_type.is_some() {
+        annotated += 1;
+    } else if !name.ends_with("__init__") {
+        missing += 1;
+    }
+    (annotated, missing)
+}
+
+/// Format a coverage report as a table with the least annotated functions
+pub fn format_coverage_display(report: &CoverageReport) -> String {
+    let config = DisplayConfig::get();
+    let width = report
+        .modules
+        .iter()
+        .map(|m| m.module.len())
+        .max()
+        .unwrap_or(0)
+        .max("total".len());
+
+    let mut result = format!(
+        "{} {} annotation coverage\n\n",
+        colorize(
+            &config.module_icon,
+            &config.color_scheme.module_color,
+            config
+        ),
+        colorize(&report.module, &config.color_scheme.module_color, config)
+    );
+    result.push_str(&format!(
+        "{:<width$}  {:>5}  {:>7}  {:>4}  {:>6}\n",
+        "module", "full", "partial", "none", "typed"
+    ));
+    let row = |name: &str, coverage: &Coverage| {
+        format!(
+            "{:<width$}  {:>5}  {:>7}  {:>4}  {:>5.0}%\n",
+            name,
+            coverage.full,
+            coverage.partial,
+            coverage.unannotated,
+            coverage.percent()
+        )
+    };
+    for module in &report.modules {
+        result.push_str(&row(&module.module, &module.coverage));
+    }
+    result.push_str(&row("total", &report.total));
+
+    if !report.least_annotated.is_empty() {
+        result.push_str("\nleast annotated:\n");
+        for (i, offender) in report.least_annotated.iter().enumerate() {
+            let prefix = if i == report.least_annotated.len() - 1 {
+                &config.tree_last
+            } else {
+                &config.tree_branch
+            };
+            result.push_str(&format!(
+                "{}{} ({} missing)\n",
+                colorize(prefix, &config.color_scheme.tree_color, config),
+                colorize(&offender.name, &config.color_scheme.warning_color, config),
+                offender.missing
+            ));
+        }
+    }
+    result.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sig(parameters: &str, return_type: Option<&str>) -> FunctionSignature {
+        FunctionSignature {
+            name: "f".to_string(),
+            parameters: parameters.to_string(),
+            return_type: return_type.map(str::to_string),
+            source: None,
+            kwargs: None,
+            param_docs: None,
+        }
+    }
+
+    #[test]
+    fn test_annotation_counts() {
+        assert_eq!(
+            annotation_counts("f", &sig("a: int, *args, **kw", Some("int"))),
+            (2, 0)
+        );
+        assert_eq!(annotation_counts("C.m", &sig("self, a", None)), (0, 2));
+        assert_eq!(
+            annotation_counts("C.__init__", &sig("self, a: int", None)),
+            (1, 0)
+        );
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        let mut codec = ModuleInfo::new();
+        codec
+            .signatures
+            .insert("encode".to_string(), sig("data, level=1", None));
+        let mut root = ModuleInfo::new();
+        root.signatures
+            .insert("load".to_string(), sig("path: str", Some("dict")));
+        root.signatures
+            .insert("dump".to_string(), sig("obj: object, fp", None));
+        root.signatures
+            .insert("_private".to_string(), sig("x", None));
+        root.submodules.insert("codec".to_string(), codec);
+
+        let report = coverage_report("pkg", &root);
+        assert_eq!(
+            report.total,
+            Coverage {
+                full: 1,
+                partial: 1,
+                unannotated: 1
+            }
+        );
+        assert_eq!(report.modules[1].module, "pkg.codec");
+        assert_eq!(
+            report.least_annotated,
+            vec![
+                Offender {
+                    name: "pkg.codec:encode".to_string(),
+                    missing: 3
+                },
+                Offender {
+                    name: "pkg:dump".to_string(),
+                    missing: 2
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,567 @@
+use crate::explorer::ModuleTreeExplorer;
+use crate::module_info::ModuleInfo;
+use crate::package_downloader::PackageDownloader;
+use crate::source_snippet::DEFAULT_SOURCE_LINES;
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use serde_json::{json, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// How long a client waits for a daemon it started to accept connections
+const SPAWN_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often the server checks for Ctrl-C while no client is connected
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a connection may go without sending a request before it is closed
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Connections served at once
+const WORKERS: usize = 4;
+/// Connections that may wait for a free worker before new ones are turned away
+const QUEUED_CONNECTIONS: usize = 32;
+
+/// When a file was parsed: its modification time and size
+type FileStamp = (SystemTime, u64);
+
+/// Parsed modules by file, shared by the requests a server answers
+type ParseCache = Mutex<BTreeMap<PathBuf, (FileStamp, ModuleInfo)>>;
+
+thread_local! {
+    /// The server's parse cache, while this thread answers a request (None otherwise)
+    /// One-shot calls never set it, so they always parse afresh.
+    static REQUEST_PARSES: RefCell<Option<Arc<ParseCache>>> = const { RefCell::new(None) };
+    /// Set once the parse in progress on this thread looked at other modules' files
+    static READ_OTHER_FILES: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Extracted downloads by requirement, kept while serving (None otherwise)
+static DOWNLOADS: Mutex<Option<BTreeMap<String, Arc<PackageDownloader>>>> = Mutex::new(None);
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Parse a module file, reusing an earlier request's parse while answering a request
+/// A file whose modification time or size changed since is parsed again. Modules that
+/// read other files (`__all__ += sub.__all__`, imported TypedDicts) are never kept, as
+/// edits to those files wouldn't be noticed.
+pub fn cached_parse(
+    path: &Path,
+    parse: impl FnOnce() -> PyResult<ModuleInfo>,
+) -> PyResult<ModuleInfo> {
+    let Some(parsed) = REQUEST_PARSES.with(|cache| cache.borrow().clone()) else {
+        return parse();
+    };
+    let stamp = file_stamp(path);
+    let cached = parsed
+        .lock()
+        .unwrap()
+        .get(path)
+        .and_then(|(parsed_stamp, info)| (Some(*parsed_stamp) == stamp).then(|| info.clone()));
+    if let Some(info) = cached {
+        return Ok(info);
+    }
+
+    READ_OTHER_FILES.with(|read| read.set(false));
+    let info = parse()?;
+    if let Some(stamp) = stamp.filter(|_| !READ_OTHER_FILES.with(Cell::get)) {
+        parsed
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), (stamp, info.clone()));
+    }
+    Ok(info)
+}
+
+/// Note that the module being parsed depends on another module's file
+pub fn reading_other_file() {
+    READ_OTHER_FILES.with(|read| read.set(true));
+}
+
+/// Makes the server's parse cache this thread's until dropped, for one request
+struct RequestParses;
+
+impl RequestParses {
+    fn enter(parsed: &Arc<ParseCache>) -> Self {
+        REQUEST_PARSES.with(|cache| *cache.borrow_mut() = Some(parsed.clone()));
+        RequestParses
+    }
+}
+
+impl Drop for RequestParses {
+    fn drop(&mut self) {
+        REQUEST_PARSES.with(|cache| *cache.borrow_mut() = None);
+    }
+}
+
+/// An earlier request's extracted download of `requirement`, while serving
+pub fn cached_download(requirement: &str) -> Option<Arc<PackageDownloader>> {
+    DOWNLOADS
+        .lock()
+        .unwrap()
+        .as_ref()?
+        .get(requirement)
+        .cloned()
+}
+
+/// Share a finished download; while serving, its extracted files stay for later requests
+pub fn keep_download(requirement: &str, downloader: PackageDownloader) -> Arc<PackageDownloader> {
+    let downloader = Arc::new(downloader);
+    if let Some(downloads) = DOWNLOADS.lock().unwrap().as_mut() {
+        downloads.insert(requirement.to_string(), downloader.clone());
+    }
+    downloader
+}
+
+/// The daemon's socket: `socket`, else `PRETTY_MOD_SOCKET`, else `pretty-mod.sock`
+/// in the user's runtime directory (or a private directory under the temp directory)
+pub fn socket_path(socket: Option<&str>) -> PathBuf {
+    if let Some(socket) = socket {
+        return PathBuf::from(socket);
+    }
+    if let Some(socket) = std::env::var_os("PRETTY_MOD_SOCKET") {
+        return PathBuf::from(socket);
+    }
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join("pretty-mod.sock"),
+        None => private_socket_dir().join("pretty-mod.sock"),
+    }
+}
+
+/// Where the socket goes without a runtime directory, e.g. /tmp/pretty-mod-alice
+/// Created with mode 0700, and not used unless the user owns it and no one else can enter.
+fn private_socket_dir() -> PathBuf {
+    let user = std::env::var("USER").unwrap_or_default();
+    std::env::temp_dir().join(format!("pretty-mod-{}", user))
+}
+
+/// Answer one request; the flag asks the server to stop
+fn respond(py: Python, request: &Value) -> (Value, bool) {
+    let op = request["op"].as_str().unwrap_or_default();
+    let result = match op {
+        "ping" => Ok(json!("pong")),
+        "shutdown" => return (json!({"ok": true, "result": "shutting down"}), true),
+        "tree" => tree(py, request).map(Value::from),
+        "sig" => signature(py, request).map(Value::from),
+        "search" => search(py, request).map(Value::from),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unknown op '{}': expected tree, sig, search, ping or shutdown",
+            op
+        ))),
+    };
+    let response = match result {
+        Ok(result) => json!({"ok": true, "result": result}),
+        Err(e) => json!({"ok": false, "error": e.to_string()}),
+    };
+    (response, false)
+}
+
+fn required<'a>(request: &'a Value, key: &str) -> PyResult<&'a str> {
+    request[key].as_str().ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "'{}' requests need a \"{}\" string",
+            request["op"].as_str().unwrap_or_default(),
+            key
+        ))
+    })
+}
+
+fn flag(request: &Value, key: &str) -> bool {
+    request[key].as_bool().unwrap_or(false)
+}
+
+/// Explore an installed module, downloading it when it isn't installed
+fn explore<R>(py: Python, module: &str, f: impl Fn(Python) -> PyResult<R>) -> PyResult<R> {
+    match f(py) {
+        Err(e)
+            if e.is_instance_of::<pyo3::exceptions::PyModuleNotFoundError>(py)
+                && !crate::stdlib::is_stdlib_module(module) =>
+        {
+            crate::utils::try_download_module(
+                py,
+                module,
+                None,
+                None,
+                false,
+                Verbosity::Quiet,
+                |_| f(py),
+            )
+        }
+        result => result,
+    }
+}
+
+/// `{"op": "tree", "module": ..., "depth": 2, "format": "pretty", ...}`, as `pretty-mod tree`
+fn tree(py: Python, request: &Value) -> PyResult<String> {
+    let module = required(request, "module")?;
+    let depth = request["depth"].as_u64().unwrap_or(2) as usize;
+    let format = request["format"].as_str().unwrap_or("pretty");
+    let class_depth = request["class_depth"].as_u64().unwrap_or(0) as usize;
+    let explorer = ModuleTreeExplorer::new(module.to_string(), depth)
+        .with_verbosity(Verbosity::Quiet)
+        .with_imports(flag(request, "imports"))
+        .with_hide_empty(flag(request, "hide_empty"))
+        .with_class_depth(crate::explorer::parse_class_depth(class_depth)?)
+        .with_signatures(flag(request, "signatures"));
+    explore(py, module, |py| explorer.get_tree_string(py, format))
+}
+
+/// `{"op": "sig", "path": ..., "format": "pretty", "width": null}`, as `pretty-mod sig`
+fn signature(py: Python, request: &Value) -> PyResult<String> {
+    let path = required(request, "path")?;
+    let format = request["format"].as_str().unwrap_or("pretty");
+    let width = request["width"].as_u64().map(|width| width as usize);
+    let param_docs = flag(request, "param_docs");
+    let show_source = flag(request, "show_source");
+    let source_lines = request["source_lines"]
+        .as_u64()
+        .map_or(DEFAULT_SOURCE_LINES, |lines| lines as usize);
+    crate::display_signature(
+        py,
+        path,
+        true,
+        format,
+        false,
+        width,
+        None,
+        None,
+        false,
+        param_docs,
+        show_source,
+        source_lines,
+    )
+}
+
+/// `{"op": "search", "module": ..., "query": ..., "depth": 3}`: the functions, classes and
+/// constants whose names contain `query` (ignoring case), as `module:name`
+fn search(py: Python, request: &Value) -> PyResult<Vec<String>> {
+    let module = required(request, "module")?;
+    let query = required(request, "query")?.to_lowercase();
+    let depth = request["depth"].as_u64().unwrap_or(3) as usize;
+    let explorer = ModuleTreeExplorer::new(module.to_string(), depth);
+    let info = explore(py, module, |py| {
+        explorer.explore_module_pure_filesystem(py, module)
+    })?;
+    let mut matches = Vec::new();
+    search_module(&info, module, &query, &mut matches);
+    Ok(matches)
+}
+
+fn search_module(info: &ModuleInfo, module_path: &str, query: &str, matches: &mut Vec<String>) {
+    for name in info
+        .functions
+        .iter()
+        .chain(&info.classes)
+        .chain(&info.constants)
+    {
+        if name.to_lowercase().contains(query) {
+            matches.push(format!("{}:{}", module_path, name));
+        }
+    }
+    for (name, submodule) in &info.submodules {
+        let submodule_path = format!("{}.{}", module_path, name);
+        search_module(submodule, &submodule_path, query, matches);
+    }
+}
+
+/// Unix domain sockets carry the requests
+#[cfg(unix)]
+mod transport {
+    use super::*;
+    use std::io::{BufRead, BufReader, ErrorKind, Write};
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::os::unix::process::CommandExt;
+    use std::process::{Command, Stdio};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::{self, SyncSender, TrySendError};
+    use std::time::Instant;
+
+    /// Caches live as long as the server; dropping this also removes the socket
+    struct Serving<'a> {
+        socket: &'a Path,
+        parsed: Arc<ParseCache>,
+    }
+
+    impl Serving<'_> {
+        fn start(socket: &Path) -> Serving<'_> {
+            *DOWNLOADS.lock().unwrap() = Some(BTreeMap::new());
+            Serving {
+                socket,
+                parsed: Arc::default(),
+            }
+        }
+    }
+
+    impl Drop for Serving<'_> {
+        fn drop(&mut self) {
+            *DOWNLOADS.lock().unwrap() = None;
+            let _ = fs::remove_file(self.socket);
+        }
+    }
+
+    /// Make sure no one else can swap in a socket of their own through its directory
+    /// The default directory is created mode 0700 and must stay so; any other (`--socket`,
+    /// `PRETTY_MOD_SOCKET`, `$XDG_RUNTIME_DIR`) must be the user's (or root's) and
+    /// writable by no one else.
+    fn check_socket_dir(py: Python, socket: &Path) -> PyResult<()> {
+        let default_dir = private_socket_dir();
+        let dir = match socket.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let is_default = dir == default_dir.as_path();
+        if is_default {
+            match fs::DirBuilder::new().mode(0o700).create(dir) {
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+                created => created?,
+            }
+        }
+        let uid: u32 = py.import("os")?.call_method0("getuid")?.extract()?;
+        let private = if is_default {
+            let metadata = fs::symlink_metadata(dir)?;
+            metadata.is_dir() && metadata.uid() == uid && metadata.mode() & 0o077 == 0
+        } else {
+            let metadata = fs::metadata(dir)?;
+            metadata.is_dir()
+                && (metadata.uid() == uid || metadata.uid() == 0)
+                && metadata.mode() & 0o022 == 0
+        };
+        if !private {
+            let requirement = if is_default {
+                "only you can access (mode 0700)"
+            } else {
+                "only you can write to"
+            };
+            return Err(PyErr::new::<pyo3::exceptions::PyPermissionError, _>(
+                format!("{} must be a directory {}", dir.display(), requirement),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn serve(py: Python, socket: &Path, verbosity: Verbosity) -> PyResult<()> {
+        check_socket_dir(py, socket)?;
+        if socket.exists() {
+            if UnixStream::connect(socket).is_ok() {
+                return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                    "pretty-mod is already serving on {}",
+                    socket.display()
+                )));
+            }
+            // Left behind by a server that didn't shut down cleanly
+            fs::remove_file(socket)?;
+        }
+        // Requests run code as this user: the socket is created mode 0600, so no one else
+        // can connect, not even before a chmod
+        let os = py.import("os")?;
+        let umask = os.call_method1("umask", (0o177,))?;
+        let bound = UnixListener::bind(socket);
+        os.call_method1("umask", (umask,))?;
+        let listener = bound?;
+        let serving = Serving::start(socket);
+        // Non-blocking, so Ctrl-C is noticed between clients
+        listener.set_nonblocking(true)?;
+        verbosity.notice(&format!("pretty-mod serving on {}", socket.display()));
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let connections = start_workers(verbosity, &serving.parsed, &stop);
+        while !stop.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false)?;
+                    // Workers do the socket I/O, so a slow client doesn't block the others
+                    if let Err(TrySendError::Full(mut stream)) = connections.try_send(stream) {
+                        let busy = json!({"ok": false, "error": "pretty-mod is busy, try again"});
+                        let _ = writeln!(stream, "{}", busy);
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    py.check_signals()?;
+                    py.allow_threads(|| std::thread::sleep(POLL_INTERVAL));
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Start `WORKERS` threads serving the connections sent on the returned channel
+    /// They exit once it is dropped, after finishing the connection at hand.
+    fn start_workers(
+        verbosity: Verbosity,
+        parsed: &Arc<ParseCache>,
+        stop: &Arc<AtomicBool>,
+    ) -> SyncSender<UnixStream> {
+        let (sender, receiver) = mpsc::sync_channel::<UnixStream>(QUEUED_CONNECTIONS);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..WORKERS {
+            let receiver = receiver.clone();
+            let parsed = parsed.clone();
+            let stop = stop.clone();
+            std::thread::spawn(move || loop {
+                // The lock is held only while waiting for the next connection
+                let next = receiver.lock().unwrap().recv();
+                let Ok(stream) = next else {
+                    return;
+                };
+                if let Err(e) = handle(stream, verbosity, &parsed, &stop) {
+                    verbosity.notice(&format!("connection failed: {}", e));
+                }
+            });
+        }
+        sender
+    }
+
+    /// Answer each line of a connection, setting `stop` once a shutdown was requested
+    /// A connection left idle for `IDLE_TIMEOUT` is closed.
+    fn handle(
+        stream: UnixStream,
+        verbosity: Verbosity,
+        parsed: &Arc<ParseCache>,
+        stop: &AtomicBool,
+    ) -> std::io::Result<()> {
+        stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        for line in BufReader::new(stream).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    verbosity.detail("closed an idle connection");
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (response, shutdown) = match serde_json::from_str::<Value>(&line) {
+                Ok(request) => {
+                    verbosity.detail(&line);
+                    let _parses = RequestParses::enter(parsed);
+                    Python::with_gil(|py| respond(py, &request))
+                }
+                Err(e) => (
+                    json!({"ok": false, "error": format!("Invalid request: {}", e)}),
+                    false,
+                ),
+            };
+            writeln!(writer, "{}", response)?;
+            if shutdown {
+                stop.store(true, Ordering::SeqCst);
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Start `python -m pretty_mod serve` in the background and wait for its socket
+    fn spawn(py: Python, socket: &Path) -> PyResult<UnixStream> {
+        let python: String = py.import("sys")?.getattr("executable")?.extract()?;
+        Command::new(python)
+            .args(["-m", "pretty_mod", "serve", "-q", "--socket"])
+            .arg(socket)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            // Its own process group, so Ctrl-C in the client's terminal leaves it running
+            .process_group(0)
+            .spawn()?;
+        let started = Instant::now();
+        loop {
+            if let Ok(stream) = UnixStream::connect(socket) {
+                return Ok(stream);
+            }
+            if started.elapsed() > SPAWN_TIMEOUT {
+                return Err(PyErr::new::<pyo3::exceptions::PyTimeoutError, _>(format!(
+                    "pretty-mod serve did not start on {}",
+                    socket.display()
+                )));
+            }
+            py.allow_threads(|| std::thread::sleep(POLL_INTERVAL));
+        }
+    }
+
+    pub fn request(
+        py: Python,
+        socket: &Path,
+        request: &str,
+        spawn_daemon: bool,
+    ) -> PyResult<String> {
+        check_socket_dir(py, socket)?;
+        let stream = match UnixStream::connect(socket) {
+            Ok(stream) => stream,
+            Err(_) if spawn_daemon => spawn(py, socket)?,
+            Err(e) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyConnectionError, _>(
+                    format!("No pretty-mod daemon on {}: {}", socket.display(), e),
+                ))
+            }
+        };
+        let response = py.allow_threads(|| -> std::io::Result<String> {
+            let mut writer = stream.try_clone()?;
+            writeln!(writer, "{}", request)?;
+            let mut response = String::new();
+            BufReader::new(stream).read_line(&mut response)?;
+            Ok(response)
+        })?;
+        if response.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyConnectionError, _>(
+                format!(
+                    "The pretty-mod daemon on {} closed the connection",
+                    socket.display()
+                ),
+            ));
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(not(unix))]
+mod transport {
+    use super::*;
+
+    fn unsupported() -> PyErr {
+        PyErr::new::<pyo3::exceptions::PyNotImplementedError, _>(
+            "pretty-mod serve needs unix domain sockets",
+        )
+    }
+
+    pub fn serve(_py: Python, _socket: &Path, _verbosity: Verbosity) -> PyResult<()> {
+        Err(unsupported())
+    }
+
+    pub fn request(_py: Python, _socket: &Path, _request: &str, _spawn: bool) -> PyResult<String> {
+        Err(unsupported())
+    }
+}
+
+/// Serve JSON requests on a unix socket, one per line, until a shutdown request
+/// Parsed modules (re-parsed when their file changes) and downloads stay warm meanwhile.
+pub fn serve(py: Python, socket: &Path, verbosity: Verbosity) -> PyResult<()> {
+    transport::serve(py, socket, verbosity)
+}
+
+/// Send a request to the daemon on `socket`, starting one first when `spawn` is set
+/// Returns the request's result, raising RuntimeError with the daemon's error message.
+pub fn query(py: Python, socket: &Path, request: &Bound<PyAny>, spawn: bool) -> PyResult<PyObject> {
+    let json = py.import("json")?;
+    let request: String = json.call_method1("dumps", (request,))?.extract()?;
+    let op = serde_json::from_str::<Value>(&request)
+        .ok()
+        .and_then(|request| request["op"].as_str().map(str::to_string));
+    // Starting a daemon just to stop it is pointless
+    let spawn = spawn && op.as_deref() != Some("shutdown");
+    let response = transport::request(py, socket, &request, spawn)?;
+    let response = json.call_method1("loads", (response,))?;
+    if response.get_item("ok")?.is_truthy()? {
+        return Ok(response.get_item("result")?.unbind());
+    }
+    Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+        response.get_item("error")?.str()?.to_string(),
+    ))
+}
@@ -0,0 +1,336 @@
+use crate::config::{colorize, DisplayConfig};
+use crate::dist_metadata::local_import_names;
+use crate::explorer::ModuleTreeExplorer;
+use crate::output_format::OutputFormatter;
+use crate::package_downloader::{download_all, max_concurrent_downloads};
+use crate::requirement::{normalize_name, parse_requirement};
+use crate::utils::with_downloaded_package;
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/// Read the dependency list of a `pyproject.toml` (`[project] dependencies`)
+/// or a requirements file
+pub fn read_dependencies(path: &Path) -> PyResult<Vec<String>> {
+    let text = fs::read_to_string(path).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyFileNotFoundError, _>(format!(
+            "Failed to read {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let requirements = if path.extension().is_some_and(|ext| ext == "toml") {
+        parse_pyproject_dependencies(&text).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid {}: {}",
+                path.display(),
+                e
+            ))
+        })?
+    } else {
+        parse_requirements_txt(&text, path.parent().unwrap_or(Path::new(".")))?
+    };
+
+    // Keep the first mention of each distribution
+    let mut seen = Vec::new();
+    Ok(requirements
+        .into_iter()
+        .filter(|req| {
+            let name = normalize_name(parse_requirement(req).name);
+            if seen.contains(&name) {
+                false
+            } else {
+                seen.push(name);
+                true
+            }
+        })
+        .collect())
+}
+
+/// PEP 621 dependencies from pyproject.toml text
+fn parse_pyproject_dependencies(text: &str) -> Result<Vec<String>, String> {
+    let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+    let project = table
+        .get("project")
+        .and_then(|project| project.as_table())
+        .ok_or("missing [project] table")?;
+    Ok(project
+        .get("dependencies")
+        .and_then(|deps| deps.as_array())
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|dep| dep.as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Requirement lines from a requirements file, following `-r other.txt` includes
+/// Other options (`-e`, `--index-url`, ...) are skipped.
+fn parse_requirements_txt(text: &str, base_dir: &Path) -> PyResult<Vec<String>> {
+    let mut requirements = Vec::new();
+    // Backslash continuations join physical lines
+    let joined = text.replace("\\\r\n", " ").replace("\\\n", " ");
+
+    for line in joined.lines() {
+        // Comments start at "#" at the beginning or after whitespace (URLs keep their fragments)
+        let line = match line.find(" #") {
+            Some(pos) => &line[..pos],
+            None => line,
+        };
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let line = line.as_str();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let include = line
+            .strip_prefix("-r ")
+            .or_else(|| line.strip_prefix("--requirement "))
+            .or_else(|| line.strip_prefix("--requirement="));
+        if let Some(include) = include {
+            let included = read_dependencies(&base_dir.join(include.trim()))?;
+            requirements.extend(included);
+            continue;
+        }
+        if line.starts_with('-') {
+            continue;
+        }
+
+        requirements.push(line.to_string());
+    }
+
+    Ok(requirements)
+}
+
+/// Outcome of exploring one dependency
+struct DependencyResult {
+    requirement: String,
+    distribution: String,
+    outcome: Result<(String, PyObject), String>, // (module explored, tree) or the failure
+}
+
+/// Explore every dependency and format a combined report
+/// Failures are collected per dependency rather than aborting the batch.
+pub fn display_dependencies(
+    py: Python,
+    requirements: &[String],
+    max_depth: usize,
+    formatter: &dyn OutputFormatter,
+    json: bool,
+    verbosity: Verbosity,
+) -> PyResult<String> {
+    // Installed dependencies first; whatever is missing is then downloaded in one batch
+    let mut outcomes: Vec<Option<PyResult<(String, PyObject)>>> = requirements
+        .iter()
+        .map(|requirement| {
+            verbosity.detail(&format!("exploring dependency {}", requirement));
+            explore_installed(py, requirement, max_depth, verbosity).map(Ok)
+        })
+        .collect();
+    let missing: Vec<usize> = (0..requirements.len())
+        .filter(|&index| outcomes[index].is_none())
+        .collect();
+    if !missing.is_empty() {
+        let specs: Vec<String> = missing.iter().map(|&index| requirements[index].clone()).collect();
+        for (index, outcome) in missing
+            .iter()
+            .zip(download_missing(py, &specs, max_depth, verbosity))
+        {
+            outcomes[*index] = Some(outcome);
+        }
+    }
+
+    let results: Vec<DependencyResult> = requirements
+        .iter()
+        .zip(outcomes)
+        .map(|(requirement, outcome)| DependencyResult {
+            requirement: requirement.clone(),
+            distribution: parse_requirement(requirement).name.to_string(),
+            outcome: outcome
+                .unwrap_or_else(|| {
+                    Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                        "dependency was not explored",
+                    ))
+                })
+                .map_err(|e| e.to_string()),
+        })
+        .collect();
+
+    if json {
+        let mut entries = Vec::new();
+        for result in &results {
+            let mut entry = serde_json::Map::new();
+            entry.insert("distribution".to_string(), result.distribution.clone().into());
+            entry.insert("requirement".to_string(), result.requirement.clone().into());
+            match &result.outcome {
+                Ok((module, tree)) => {
+                    let formatted = formatter.format_tree(py, tree, module)?;
+                    if let Ok(serde_json::Value::Object(tree_json)) =
+                        serde_json::from_str(&formatted)
+                    {
+                        entry.extend(tree_json);
+                    }
+                }
+                Err(error) => {
+                    entry.insert("error".to_string(), error.clone().into());
+                }
+            }
+            entries.push(serde_json::Value::Object(entry));
+        }
+        return serde_json::to_string_pretty(&entries)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()));
+    }
+
+    let mut sections = Vec::new();
+    let mut failures = Vec::new();
+    for result in &results {
+        match &result.outcome {
+            Ok((module, tree)) => sections.push(formatter.format_tree(py, tree, module)?),
+            Err(error) => failures.push(format!("  {}: {}", result.requirement, error)),
+        }
+    }
+    if !failures.is_empty() {
+        let config = DisplayConfig::get();
+        sections.push(format!(
+            "{} Could not explore {} of {} dependencies:\n{}",
+            colorize("⚠️ ", &config.color_scheme.warning_color, config),
+            failures.len(),
+            results.len(),
+            failures.join("\n")
+        ));
+    }
+    if sections.is_empty() {
+        return Ok("No dependencies found".to_string());
+    }
+    Ok(sections.join("\n"))
+}
+
+/// Module to explore for a distribution when nothing better is known
+fn default_module(distribution: &str) -> String {
+    distribution.replace(['-', '.'], "_").to_lowercase()
+}
+
+/// Explore the installed version of a dependency, if there is one
+fn explore_installed(
+    py: Python,
+    requirement: &str,
+    max_depth: usize,
+    verbosity: Verbosity,
+) -> Option<(String, PyObject)> {
+    let distribution = parse_requirement(requirement).name;
+    let mut candidates = local_import_names(py, distribution);
+    if candidates.is_empty() {
+        candidates.push(default_module(distribution));
+    }
+
+    candidates.into_iter().find_map(|module| {
+        let explorer =
+            ModuleTreeExplorer::new(module.clone(), max_depth).with_verbosity(verbosity);
+        explorer.explore(py).ok().map(|tree| (module, tree))
+    })
+}
+
+/// Download the dependencies that are not installed, concurrently, then explore each one
+fn download_missing(
+    py: Python,
+    specs: &[String],
+    max_depth: usize,
+    verbosity: Verbosity,
+) -> Vec<PyResult<(String, PyObject)>> {
+    if let Some(target) = crate::interpreter::target() {
+        return specs
+            .iter()
+            .map(|spec| {
+                Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(format!(
+                    "{} is not installed in {} (downloads are disabled with --python)",
+                    spec, target.executable
+                )))
+            })
+            .collect();
+    }
+
+    let config = DisplayConfig::get();
+    verbosity.notice(&format!(
+        "{} {} not found locally. Downloading from PyPI...",
+        colorize("⚠️ ", &config.color_scheme.warning_color, config),
+        if specs.len() == 1 {
+            "1 dependency".to_string()
+        } else {
+            format!("{} dependencies", specs.len())
+        }
+    ));
+
+    let max_concurrency = max_concurrent_downloads();
+    let downloads = py.allow_threads(|| download_all(specs, verbosity, max_concurrency));
+
+    downloads
+        .into_iter()
+        .map(|download| {
+            let downloader = download?;
+            let module = default_module(downloader.package_name());
+            with_downloaded_package(py, &downloader, |_| {
+                let explorer =
+                    ModuleTreeExplorer::new(module.clone(), max_depth).with_verbosity(verbosity);
+                Ok((module.clone(), explorer.explore(py)?))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pyproject_dependencies() {
+        let text = r#"
+[project]
+name = "demo"
+dependencies = ["httpx>=0.27", "pydantic[email]>=2; python_version >= '3.9'"]
+
+[project.optional-dependencies]
+dev = ["pytest"]
+"#;
+        assert_eq!(
+            parse_pyproject_dependencies(text).unwrap(),
+            vec!["httpx>=0.27", "pydantic[email]>=2; python_version >= '3.9'"]
+        );
+        assert!(parse_pyproject_dependencies("[tool.ruff]\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_requirements_txt() {
+        let text = "\
+# pinned for reproducibility
+requests==2.32.3  # http
+-e ./local-package
+--index-url https://example.com/simple
+pkg @ https://example.com/pkg-1.0-py3-none-any.whl#sha256=abc
+uvicorn[standard] \\
+    >=0.30
+";
+        assert_eq!(
+            parse_requirements_txt(text, Path::new(".")).unwrap(),
+            vec![
+                "requests==2.32.3",
+                "pkg @ https://example.com/pkg-1.0-py3-none-any.whl#sha256=abc",
+                "uvicorn[standard] >=0.30",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_dependencies_follows_includes_and_dedupes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("base.txt"), "Requests>=2\nclick\n").unwrap();
+        fs::write(dir.path().join("dev.txt"), "-r base.txt\nrequests\npytest\n").unwrap();
+        assert_eq!(
+            read_dependencies(&dir.path().join("dev.txt")).unwrap(),
+            vec!["Requests>=2", "click", "pytest"]
+        );
+    }
+}
@@ -0,0 +1,492 @@
+use pyo3::prelude::*;
+use crate::package_downloader::PackageDownloader;
+use crate::requirement::normalize_name;
+use crate::verbosity::Verbosity;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Script names mapped to their targets, e.g. {"httpx": "httpx:main"}
+pub type Scripts = BTreeMap<String, String>;
+
+/// Core metadata of a distribution (from `.dist-info/METADATA` or `PKG-INFO`)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, IntoPyObject)]
+pub struct DistMetadata {
+    pub name: String,
+    pub version: String,
+    pub summary: Option<String>,
+    pub requires_python: Option<String>,
+    pub license: Option<String>, // e.g., "OSI Approved :: MIT License"
+}
+
+impl DistMetadata {
+    /// Parse the RFC 822-style header block of a METADATA file
+    pub fn from_metadata_text(text: &str) -> Option<Self> {
+        let mut metadata = DistMetadata::default();
+
+        for line in text.lines() {
+            // Headers end at the first blank line; the body is the long description
+            if line.trim().is_empty() {
+                break;
+            }
+            // Skip folded continuation lines
+            if line.starts_with(' ') || line.starts_with('\t') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().to_string();
+
+            match key.trim() {
+                "Name" => metadata.name = value,
+                "Version" => metadata.version = value,
+                "Summary" if !value.is_empty() => metadata.summary = Some(value),
+                "Requires-Python" if !value.is_empty() => metadata.requires_python = Some(value),
+                "Classifier" if metadata.license.is_none() => {
+                    if let Some(license) = value.strip_prefix("License :: ") {
+                        metadata.license = Some(license.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if metadata.name.is_empty() {
+            None
+        } else {
+            Some(metadata)
+        }
+    }
+
+    /// Read metadata from an extracted wheel (`*.dist-info/METADATA`) or sdist (`PKG-INFO`)
+    pub fn from_extracted(extract_dir: &Path) -> Option<Self> {
+        let metadata_path = find_metadata_file(extract_dir)?;
+        let text = fs::read_to_string(metadata_path).ok()?;
+        Self::from_metadata_text(&text)
+    }
+
+    /// Read metadata for the installed distribution that provides a top-level module
+    pub fn from_installed(py: Python, module_name: &str) -> Option<Self> {
+        read_installed(py, module_name, "METADATA", Self::from_metadata_text)
+    }
+
+    /// Read metadata for an installed distribution by its name
+    pub fn from_installed_dist(py: Python, dist_name: &str) -> Option<Self> {
+        read_installed_dist(py, dist_name, "METADATA", Self::from_metadata_text)
+    }
+}
+
+/// Parse a dist-info file of the installed distribution that provides a top-level module
+fn read_installed<T>(
+    py: Python,
+    module_name: &str,
+    file_name: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    // importlib.metadata only sees the current interpreter's environment
+    if crate::interpreter::is_foreign() {
+        return None;
+    }
+    let top_level = module_name.split('.').next().unwrap_or(module_name);
+    let metadata = py.import("importlib.metadata").ok()?;
+
+    // Map the import name to distribution names (Python 3.10+)
+    let mut candidates: Vec<String> = metadata
+        .call_method0("packages_distributions")
+        .ok()
+        .and_then(|mapping| mapping.get_item(top_level).ok())
+        .and_then(|dists| dists.extract().ok())
+        .unwrap_or_default();
+    // Fall back to assuming the distribution shares the import name
+    candidates.push(top_level.to_string());
+
+    candidates
+        .iter()
+        .find_map(|candidate| read_installed_dist(py, candidate, file_name, &parse))
+}
+
+/// Parse a dist-info file of an installed distribution
+fn read_installed_dist<T>(
+    py: Python,
+    dist_name: &str,
+    file_name: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    if crate::interpreter::is_foreign() {
+        return None;
+    }
+    let text: String = py
+        .import("importlib.metadata")
+        .ok()?
+        .call_method1("distribution", (dist_name,))
+        .ok()?
+        .call_method1("read_text", (file_name,))
+        .ok()?
+        .extract()
+        .ok()?;
+    parse(&text)
+}
+
+/// Parse the `[console_scripts]` and `[gui_scripts]` sections of an entry_points.txt
+pub fn scripts_from_entry_points_text(text: &str) -> Scripts {
+    let mut scripts = Scripts::new();
+    let mut in_scripts = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_scripts = matches!(section.trim(), "console_scripts" | "gui_scripts");
+            continue;
+        }
+        if let (true, Some((name, target))) = (in_scripts, line.split_once('=')) {
+            // Extras ("pkg.cli:main [color]") don't change what runs
+            let target = target.split('[').next().unwrap_or(target).trim();
+            scripts.insert(name.trim().to_string(), target.to_string());
+        }
+    }
+    scripts
+}
+
+/// Scripts of the installed distribution that provides a top-level module
+pub fn installed_scripts(py: Python, module_name: &str) -> Scripts {
+    read_installed(py, module_name, "entry_points.txt", |text| {
+        Some(scripts_from_entry_points_text(text))
+    })
+    .unwrap_or_default()
+}
+
+/// Scripts of an installed distribution by its name
+pub fn installed_dist_scripts(py: Python, dist_name: &str) -> Scripts {
+    read_installed_dist(py, dist_name, "entry_points.txt", |text| {
+        Some(scripts_from_entry_points_text(text))
+    })
+    .unwrap_or_default()
+}
+
+/// Scripts of an extracted wheel (sdists only declare them in their build configuration)
+pub fn extracted_scripts(extract_dir: &Path) -> Scripts {
+    let Ok(entries) = fs::read_dir(extract_dir) else {
+        return Scripts::new();
+    };
+    entries
+        .flatten()
+        .find(|entry| entry.file_name().to_string_lossy().ends_with(".dist-info"))
+        .and_then(|entry| fs::read_to_string(entry.path().join("entry_points.txt")).ok())
+        .map(|text| scripts_from_entry_points_text(&text))
+        .unwrap_or_default()
+}
+
+/// Resolve "distribution:script" to the import path the script runs, e.g. "black:black" ->
+/// "black:patched_main"; distributions that aren't installed are downloaded to read them
+pub fn resolve_entry_point(py: Python, spec: &str, verbosity: Verbosity) -> PyResult<String> {
+    let Some((dist_name, script)) = spec.split_once(':') else {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid entry point '{}': expected 'distribution:script'",
+            spec
+        )));
+    };
+
+    let installed: Option<String> = if crate::interpreter::is_foreign() {
+        None
+    } else {
+        py.import("importlib.metadata")
+            .and_then(|metadata| metadata.call_method1("distribution", (dist_name,)))
+            .ok()
+            .map(|dist| {
+                dist.call_method1("read_text", ("entry_points.txt",))
+                    .and_then(|text| text.extract::<Option<String>>())
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default()
+            })
+    };
+    let scripts = match installed {
+        Some(text) => scripts_from_entry_points_text(&text),
+        None => {
+            let mut downloader =
+                PackageDownloader::new(dist_name.to_string()).with_verbosity(verbosity);
+            downloader.download_and_extract()?;
+            downloader.scripts().clone()
+        }
+    };
+
+    let Some(target) = scripts.get(script) else {
+        let available: Vec<&str> = scripts.keys().map(String::as_str).collect();
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{} has no script '{}' (scripts: {})",
+            dist_name,
+            script,
+            if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            }
+        )));
+    };
+    verbosity.detail(&format!("{} runs {}", script, target));
+    Ok(target.clone())
+}
+
+/// Import names provided by a locally installed distribution
+/// e.g., "beautifulsoup4" -> ["bs4"]
+pub fn local_import_names(py: Python, dist_name: &str) -> Vec<String> {
+    let Ok(metadata) = py.import("importlib.metadata") else {
+        return Vec::new();
+    };
+    let wanted = normalize_name(dist_name);
+
+    // Invert packages_distributions() (import name -> distribution names)
+    let mut names: Vec<String> = metadata
+        .call_method0("packages_distributions")
+        .and_then(|mapping| mapping.extract::<HashMap<String, Vec<String>>>())
+        .map(|mapping| {
+            mapping
+                .into_iter()
+                .filter(|(_, dists)| dists.iter().any(|d| normalize_name(d) == wanted))
+                .map(|(import_name, _)| import_name)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Fall back to the distribution's top_level.txt
+    if names.is_empty() {
+        let top_level: Option<String> = metadata
+            .call_method1("distribution", (dist_name,))
+            .and_then(|dist| dist.call_method1("read_text", ("top_level.txt",)))
+            .ok()
+            .and_then(|text| text.extract().ok());
+        if let Some(top_level) = top_level {
+            names = top_level
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+    }
+
+    // Private helpers (e.g., "_yaml") are rarely what was meant
+    names.retain(|name| name != dist_name && !name.starts_with('_'));
+    names.sort();
+    names
+}
+
+/// Importable packages a distribution installs, from the paths in its RECORD
+/// Directories without an `__init__.py` are namespaces and are looked into, so an
+/// "azure-identity" wheel provides "azure.identity" rather than the shared "azure".
+pub fn packages_from_files<'a>(files: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let sources: Vec<Vec<&str>> = files
+        .into_iter()
+        .map(|file| file.split('/').collect::<Vec<_>>())
+        .filter(|parts| {
+            let file_name = parts[parts.len() - 1];
+            let is_source = [".py", ".pyi", ".so", ".pyd"]
+                .iter()
+                .any(|ext| file_name.ends_with(ext));
+            // Metadata, scripts installed outside site-packages ("../../bin"), private modules
+            is_source
+                && !parts[0].starts_with(['_', '.'])
+                && !parts[0].ends_with(".dist-info")
+                && !parts.contains(&"__pycache__")
+        })
+        .collect();
+    // Directories that are regular packages, e.g. "azure/identity"
+    let package_dirs: HashSet<String> = sources
+        .iter()
+        .filter(|parts| matches!(parts[parts.len() - 1], "__init__.py" | "__init__.pyi"))
+        .map(|parts| parts[..parts.len() - 1].join("/"))
+        .collect();
+    let packages: BTreeSet<String> = sources
+        .iter()
+        .filter_map(|parts| {
+            let (file_name, dirs) = parts.split_last()?;
+            let package_depth =
+                (1..=dirs.len()).find(|&depth| package_dirs.contains(&dirs[..depth].join("/")));
+            let module: Vec<&str> = match package_depth {
+                Some(depth) => dirs[..depth].to_vec(),
+                // A module in a namespace, or a single-file top-level module
+                None => {
+                    let stem = file_name.split('.').next().unwrap_or(file_name);
+                    dirs.iter().copied().chain([stem]).collect()
+                }
+            };
+            let is_importable = module.iter().all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+            });
+            is_importable.then(|| module.join("."))
+        })
+        .collect();
+    packages.into_iter().collect()
+}
+
+/// Importable packages of a locally installed distribution
+/// e.g., "azure-identity" -> ["azure.identity"]
+pub fn local_dist_packages(py: Python, dist_name: &str) -> Vec<String> {
+    if crate::interpreter::is_foreign() {
+        return Vec::new();
+    }
+    let files: Vec<String> = py
+        .import("importlib.metadata")
+        .and_then(|metadata| metadata.call_method1("distribution", (dist_name,)))
+        .and_then(|dist| dist.getattr("files"))
+        .and_then(|files| {
+            if files.is_none() {
+                return Ok(Vec::new());
+            }
+            files
+                .try_iter()?
+                .map(|file| Ok(file?.str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    packages_from_files(files.iter().map(String::as_str))
+}
+
+/// Map a module path whose top-level name is a locally installed distribution
+/// (not an importable module) onto that distribution's import names
+/// e.g., "beautifulsoup4.element" -> ["bs4.element"]
+pub fn local_import_candidates(py: Python, module_path: &str) -> Vec<String> {
+    let (top_level, rest) = match module_path.split_once('.') {
+        Some((top_level, rest)) => (top_level, Some(rest)),
+        None => (module_path, None),
+    };
+    if crate::interpreter::is_foreign() || is_importable(py, top_level) {
+        return Vec::new();
+    }
+    local_import_names(py, top_level)
+        .into_iter()
+        .map(|name| match rest {
+            Some(rest) => format!("{}.{}", name, rest),
+            None => name,
+        })
+        .collect()
+}
+
+/// Whether a top-level module can be found without importing it
+fn is_importable(py: Python, top_level: &str) -> bool {
+    py.import("importlib.util")
+        .and_then(|util| util.call_method1("find_spec", (top_level,)))
+        .map(|spec| !spec.is_none())
+        .unwrap_or(false)
+}
+
+/// Locate the METADATA (wheel) or PKG-INFO (sdist) file in an extracted archive
+fn find_metadata_file(extract_dir: &Path) -> Option<PathBuf> {
+    let entries = fs::read_dir(extract_dir).ok()?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.ends_with(".dist-info") && path.join("METADATA").is_file() {
+            return Some(path.join("METADATA"));
+        }
+        // Source distributions extract into a single "<name>-<version>/" directory
+        if path.join("PKG-INFO").is_file() {
+            return Some(path.join("PKG-INFO"));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_metadata_text() {
+        let text = "Metadata-Version: 2.1\n\
+                    Name: toml\n\
+                    Version: 0.10.2\n\
+                    Summary: Python Library for Tom's Obvious, Minimal Language\n\
+                    Classifier: Development Status :: 5 - Production/Stable\n\
+                    Classifier: License :: OSI Approved :: MIT License\n\
+                    Requires-Python: >=2.6, !=3.0.*, !=3.1.*, !=3.2.*\n\
+                    \n\
+                    Name: not a header\n";
+
+        let metadata = DistMetadata::from_metadata_text(text).unwrap();
+        assert_eq!(metadata.name, "toml");
+        assert_eq!(metadata.version, "0.10.2");
+        assert_eq!(
+            metadata.summary.as_deref(),
+            Some("Python Library for Tom's Obvious, Minimal Language")
+        );
+        assert_eq!(metadata.license.as_deref(), Some("OSI Approved :: MIT License"));
+        assert_eq!(
+            metadata.requires_python.as_deref(),
+            Some(">=2.6, !=3.0.*, !=3.1.*, !=3.2.*")
+        );
+    }
+
+    #[test]
+    fn test_from_metadata_text_requires_name() {
+        assert_eq!(DistMetadata::from_metadata_text("Version: 1.0\n"), None);
+    }
+
+    #[test]
+    fn test_scripts_from_entry_points_text() {
+        let text = "[console_scripts]\n\
+                    black = black:patched_main\n\
+                    blackd = blackd:patched_main [d]\n\
+                    \n\
+                    [gui_scripts]\n\
+                    black-gui=black.gui:run\n\
+                    \n\
+                    [pytest11]\n\
+                    black = black.plugin\n";
+        let scripts = scripts_from_entry_points_text(text);
+        assert_eq!(
+            scripts.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("black".to_string(), "black:patched_main".to_string()),
+                ("black-gui".to_string(), "black.gui:run".to_string()),
+                ("blackd".to_string(), "blackd:patched_main".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_packages_from_files() {
+        let record = [
+            "azure/identity/__init__.py",
+            "azure/identity/_credentials/default.py",
+            "azure/identity/py.typed",
+            "azure_identity-1.19.0.dist-info/RECORD",
+            "six.py",
+            "_cffi_backend.cpython-312-x86_64-linux-gnu.so",
+            "yaml/__init__.py",
+            "yaml/__pycache__/__init__.cpython-312.pyc",
+            "../../bin/tool",
+        ];
+        assert_eq!(
+            packages_from_files(record),
+            vec!["azure.identity", "six", "yaml"]
+        );
+    }
+
+    #[test]
+    fn test_extracted_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(extracted_scripts(dir.path()).is_empty());
+        let dist_info = dir.path().join("tool-1.0.dist-info");
+        fs::create_dir(&dist_info).unwrap();
+        fs::write(
+            dist_info.join("entry_points.txt"),
+            "[console_scripts]\ntool = tool.cli:main\n",
+        )
+        .unwrap();
+        assert_eq!(
+            extracted_scripts(dir.path())
+                .get("tool")
+                .map(String::as_str),
+            Some("tool.cli:main")
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use ruff_python_ast::{Expr, Stmt};
+use ruff_python_parser::parse_module;
+use std::collections::BTreeMap;
+
+/// Google-style section headers listing parameters
+const GOOGLE_SECTIONS: &[&str] = &[
+    "Args:",
+    "Arguments:",
+    "Parameters:",
+    "Params:",
+    "Keyword Args:",
+    "Keyword Arguments:",
+    "Kwargs:",
+];
+
+/// numpy-style section titles (underlined with dashes) listing parameters
+const NUMPY_SECTIONS: &[&str] = &[
+    "Parameters",
+    "Other Parameters",
+    "Keyword Arguments",
+    "Keyword Args",
+];
+
+/// Parameter descriptions in a function or class body's docstring (None when there are none)
+pub fn body_param_docs(body: &[Stmt]) -> Option<BTreeMap<String, String>> {
+    let descriptions = param_descriptions(&body_docstring(body)?);
+    (!descriptions.is_empty()).then_some(descriptions)
+}
+
+/// The first line of a module's docstring, e.g. "JSON (JavaScript Object Notation) ..."
+pub fn module_summary_line(source: &str) -> Option<String> {
+    let body = parse_module(source).ok()?.into_syntax().body;
+    body_docstring(&body)?
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// The docstring of a function, class or module body
+fn body_docstring(body: &[Stmt]) -> Option<String> {
+    let Some(Stmt::Expr(expr)) = body.first() else {
+        return None;
+    };
+    match expr.value.as_ref() {
+        Expr::StringLiteral(literal) => Some(literal.value.to_str().to_string()),
+        _ => None,
+    }
+}
+
+/// One-line parameter descriptions from a numpy- or Google-style docstring
+/// Other styles yield nothing. Names are as documented, without `*`/`**` or backticks.
+pub fn param_descriptions(doc: &str) -> BTreeMap<String, String> {
+    let lines: Vec<&str> = doc.lines().collect();
+    let mut descriptions = BTreeMap::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let title = line.trim();
+        if GOOGLE_SECTIONS.contains(&title) {
+            i = google_section(&lines, i, &mut descriptions);
+        } else if NUMPY_SECTIONS.contains(&title) && lines.get(i + 1).is_some_and(|l| is_rule(l)) {
+            i = numpy_section(&lines, i, &mut descriptions);
+        } else {
+            i += 1;
+        }
+    }
+    descriptions
+}
+
+/// `name (type): description` entries indented under the header
+/// Returns the index of the first line after the section.
+fn google_section(
+    lines: &[&str],
+    header: usize,
+    descriptions: &mut BTreeMap<String, String>,
+) -> usize {
+    let header_indent = indent(lines[header]);
+    let mut entry_indent = None;
+    let mut current: Option<(String, String)> = None;
+    let mut i = header + 1;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+        let line_indent = indent(line);
+        if line_indent <= header_indent {
+            break;
+        }
+        let entry_indent = *entry_indent.get_or_insert(line_indent);
+        if line_indent == entry_indent {
+            flush(&mut current, descriptions);
+            if let Some((name, description)) = line.trim().split_once(':') {
+                // "name (int, optional)" -> "name"
+                let name = name.split('(').next().unwrap_or(name);
+                current = Some((name.to_string(), description.trim().to_string()));
+            }
+        } else if let Some((_, description)) = &mut current {
+            // The description starts on the next line
+            if description.is_empty() {
+                *description = line.trim().to_string();
+            }
+        }
+        i += 1;
+    }
+    flush(&mut current, descriptions);
+    i
+}
+
+/// `name : type` entries at the title's indentation, described on the lines below
+/// Returns the index of the first line after the section.
+fn numpy_section(
+    lines: &[&str],
+    title: usize,
+    descriptions: &mut BTreeMap<String, String>,
+) -> usize {
+    let title_indent = indent(lines[title]);
+    let mut names: Vec<String> = Vec::new();
+    let mut i = title + 2;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+        let line_indent = indent(line);
+        if line_indent < title_indent || lines.get(i + 1).is_some_and(|l| is_rule(l)) {
+            break;
+        }
+        if line_indent == title_indent {
+            // "x, y : array_like" documents both
+            let declared = line.trim().split(" :").next().unwrap_or_default();
+            let declared = declared.split(':').next().unwrap_or(declared);
+            names = declared.split(',').map(str::to_string).collect();
+        } else {
+            for name in names.drain(..) {
+                insert(descriptions, &name, line.trim());
+            }
+        }
+        i += 1;
+    }
+    i
+}
+
+fn flush(current: &mut Option<(String, String)>, descriptions: &mut BTreeMap<String, String>) {
+    if let Some((name, description)) = current.take() {
+        insert(descriptions, &name, &description);
+    }
+}
+
+fn insert(descriptions: &mut BTreeMap<String, String>, name: &str, description: &str) {
+    let name = name.trim().trim_matches('`').trim_start_matches('*');
+    let is_identifier = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if is_identifier && !description.is_empty() {
+        descriptions
+            .entry(name.to_string())
+            .or_insert_with(|| description.to_string());
+    }
+}
+
+/// A numpy-style underline, e.g. "----------"
+fn is_rule(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && line.chars().all(|c| c == '-')
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn described(doc: &str) -> Vec<(String, String)> {
+        param_descriptions(doc).into_iter().collect()
+    }
+
+    fn pair(name: &str, description: &str) -> (String, String) {
+        (name.to_string(), description.to_string())
+    }
+
+    #[test]
+    fn test_google_style() {
+        let doc = "Send a request.
+
+        Args:
+            url (str): Where to send it.
+            retries: How often to retry
+                before giving up.
+            timeout:
+                Seconds to wait.
+            **kwargs: Passed to the transport.
+
+        Returns:
+            The response.
+        ";
+        assert_eq!(
+            described(doc),
+            vec![
+                pair("kwargs", "Passed to the transport."),
+                pair("retries", "How often to retry"),
+                pair("timeout", "Seconds to wait."),
+                pair("url", "Where to send it."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numpy_style() {
+        let doc = "Join arrays.
+
+        Parameters
+        ----------
+        a1, a2 : array_like
+            The arrays to join.
+        axis : int, optional
+            The axis along which to join.
+        out
+
+        Returns
+        -------
+        res : ndarray
+            The joined array.
+        ";
+        assert_eq!(
+            described(doc),
+            vec![
+                pair("a1", "The arrays to join."),
+                pair("a2", "The arrays to join."),
+                pair("axis", "The axis along which to join."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_summary_line() {
+        let source = "\"\"\"\n    Flows: the unit of orchestration.\n\n    More detail.\n\"\"\"\nimport os\n";
+        assert_eq!(
+            module_summary_line(source).as_deref(),
+            Some("Flows: the unit of orchestration.")
+        );
+        assert_eq!(module_summary_line("import os\n"), None);
+    }
+
+    #[test]
+    fn test_unrecognized_style() {
+        assert!(described("Send a request.\n\n:param url: Where to send it.\n").is_empty());
+        assert!(described("Just a summary line.").is_empty());
+    }
+}
@@ -1,28 +1,58 @@
+use crate::dist_metadata::DistMetadata;
+use crate::html_formatter::HtmlFormatter;
+use crate::logging::info_log;
 use crate::module_info::ModuleInfo;
-use crate::tree_formatter::format_tree_display;
+use crate::output_format::{create_formatter, OutputFormatter};
+use crate::stubs::{merge_adjacent_stub, merge_stub, stub_file, stubs_root, StubPreference};
+use crate::verbosity::Verbosity;
 use pyo3::prelude::*;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How many method names to list per class when classes are expanded
+const MAX_CLASS_METHODS: usize = 30;
+
+/// Modules assigned at runtime under a name no file has (os.path is posixpath or ntpath)
+const RUNTIME_ALIASES: &[&str] = &["os.path"];
 
 /// A Rust implementation of module tree exploration
 #[pyclass]
 pub struct ModuleTreeExplorer {
     root_module_path: String,
     max_depth: usize,
+    verbosity: Verbosity,
+    prefer: StubPreference,
+    include_imports: bool,
+    hide_empty: bool,
+    class_depth: usize,
+    signatures: bool,
+    timeout: Option<Duration>,
+    deadline: Mutex<Option<Instant>>,
     tree: Mutex<Option<PyObject>>,
 }
 
 #[pymethods]
 impl ModuleTreeExplorer {
     #[new]
-    #[pyo3(signature = (root_module_path, max_depth = 2))]
-    pub fn new(root_module_path: String, max_depth: usize) -> Self {
-        Self {
-            root_module_path,
-            max_depth,
-            tree: Mutex::new(None),
-        }
+    #[pyo3(signature = (root_module_path, max_depth = 2, timeout_seconds = None, include_imports = false, hide_empty = false, class_depth = 0, signatures = false))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn py_new(
+        root_module_path: String,
+        max_depth: usize,
+        timeout_seconds: Option<f64>,
+        include_imports: bool,
+        hide_empty: bool,
+        class_depth: usize,
+        signatures: bool,
+    ) -> PyResult<Self> {
+        Ok(Self::new(root_module_path, max_depth)
+            .with_timeout(parse_timeout(timeout_seconds)?)
+            .with_imports(include_imports)
+            .with_hide_empty(hide_empty)
+            .with_class_depth(parse_class_depth(class_depth)?)
+            .with_signatures(signatures))
     }
 
     #[getter]
@@ -50,7 +80,32 @@ impl ModuleTreeExplorer {
 
     pub fn explore(&self, py: Python) -> PyResult<PyObject> {
         // ALWAYS use pure file-based discovery (like ty/ruff)
-        let module_info = self.explore_module_pure_filesystem(py, &self.root_module_path)?;
+        let mut module_info = self.explore_module_pure_filesystem(py, &self.root_module_path)?;
+        let hidden = if self.hide_empty {
+            module_info.prune_empty()
+        } else {
+            0
+        };
+        info_log!(
+            "Explored {} (max_depth={}): {} modules, {} functions, {} classes",
+            self.root_module_path,
+            self.max_depth,
+            module_info.count_modules(),
+            module_info.count_items(|info| info.functions.len()),
+            module_info.count_items(|info| info.classes.len())
+        );
+        self.verbosity.detail(&format!(
+            "explored {} modules under {}",
+            module_info.count_modules(),
+            self.root_module_path
+        ));
+        let truncated = module_info.count_items(|info| usize::from(info.truncated));
+        if truncated > 0 {
+            self.verbosity.notice(&format!(
+                "Time budget exhausted: {} modules under {} were not fully explored",
+                truncated, self.root_module_path
+            ));
+        }
 
         // Create the wrapped format that tests expect: {"api": {...}, "submodules": {...}}
         let tree_dict = pyo3::types::PyDict::new(py);
@@ -63,8 +118,23 @@ impl ModuleTreeExplorer {
         )?;
         api_dict.set_item("functions", &module_info.functions)?;
         api_dict.set_item("classes", &module_info.classes)?;
+        api_dict.set_item("abstract_classes", module_info.abstract_classes())?;
         api_dict.set_item("constants", &module_info.constants)?;
+        if !module_info.reexports.is_empty() {
+            api_dict.set_item("reexports", &module_info.reexports)?;
+        }
+        if module_info.dynamic_all {
+            api_dict.set_item("dynamic_all", true)?;
+        }
+        self.set_optional_api(py, &module_info, &api_dict)?;
+        set_locations(&module_info, &tree_dict, &api_dict)?;
         tree_dict.set_item("api", api_dict)?;
+        if module_info.truncated {
+            tree_dict.set_item("truncated", true)?;
+        }
+        if hidden > 0 {
+            tree_dict.set_item("hidden_empty", hidden)?;
+        }
 
         // Convert submodules to the expected format
         let submodules_dict = pyo3::types::PyDict::new(py);
@@ -79,13 +149,25 @@ impl ModuleTreeExplorer {
             )?;
             sub_api_dict.set_item("functions", &submodule_info.functions)?;
             sub_api_dict.set_item("classes", &submodule_info.classes)?;
+            sub_api_dict.set_item("abstract_classes", submodule_info.abstract_classes())?;
             sub_api_dict.set_item("constants", &submodule_info.constants)?;
+            if !submodule_info.reexports.is_empty() {
+                sub_api_dict.set_item("reexports", &submodule_info.reexports)?;
+            }
+            if submodule_info.dynamic_all {
+                sub_api_dict.set_item("dynamic_all", true)?;
+            }
+            self.set_optional_api(py, &submodule_info, &sub_api_dict)?;
+            set_locations(&submodule_info, &submodule_dict, &sub_api_dict)?;
             submodule_dict.set_item("api", sub_api_dict)?;
+            if submodule_info.truncated {
+                submodule_dict.set_item("truncated", true)?;
+            }
 
             // Convert nested submodules recursively
             let nested_submodules_dict = pyo3::types::PyDict::new(py);
             for (nested_name, nested_info) in submodule_info.submodules {
-                let nested_dict = convert_module_info_to_dict(py, &nested_info)?;
+                let nested_dict = convert_module_info_to_dict(py, &nested_info, self)?;
                 nested_submodules_dict.set_item(nested_name, nested_dict)?;
             }
             submodule_dict.set_item("submodules", nested_submodules_dict)?;
@@ -103,7 +185,11 @@ impl ModuleTreeExplorer {
         Ok(py_tree)
     }
 
-    pub fn get_tree_string(&self, py: Python) -> PyResult<String> {
+    /// The tree in any output format (the same formats as display_tree)
+    #[pyo3(signature = (format = "pretty"))]
+    pub fn get_tree_string(&self, py: Python, format: &str) -> PyResult<String> {
+        let formatter = create_formatter(format)?;
+
         // Get the tree, exploring if necessary
         let tree_obj = {
             let tree_guard = self.tree.lock().unwrap();
@@ -116,13 +202,57 @@ impl ModuleTreeExplorer {
             }
         };
 
-        // Use the display_tree formatting logic, which expects the wrapped format
-        format_tree_display(py, &tree_obj, &self.root_module_path)
+        // Distribution metadata goes on a copy so the stored tree stays as explored
+        if formatter.include_dist_metadata() {
+            if let Some(metadata) = DistMetadata::from_installed(py, &self.root_module_path) {
+                let tree = tree_obj
+                    .bind(py)
+                    .downcast::<pyo3::types::PyDict>()?
+                    .copy()?;
+                tree.set_item("dist", metadata)?;
+                return formatter.format_tree(
+                    py,
+                    &tree.into_any().unbind(),
+                    &self.root_module_path,
+                );
+            }
+        }
+        formatter.format_tree(py, &tree_obj, &self.root_module_path)
+    }
+
+    /// The plain tree once explored (repr doesn't explore on its own)
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        if self.tree.lock().unwrap().is_some() {
+            return self.get_tree_string(py, "pretty");
+        }
+        Ok(format!(
+            "ModuleTreeExplorer('{}', max_depth={})",
+            self.root_module_path, self.max_depth
+        ))
+    }
+
+    /// Rich display in notebooks, exploring if necessary
+    fn _repr_html_(&self, py: Python) -> PyResult<String> {
+        let explored = self
+            .tree
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|tree| tree.clone_ref(py));
+        let tree_obj = match explored {
+            Some(tree) => tree,
+            None => self.explore(py)?,
+        };
+        HtmlFormatter.format_tree(py, &tree_obj, &self.root_module_path)
     }
 }
 
 /// Convert a ModuleInfo struct to a Python dict
-fn convert_module_info_to_dict(py: Python, info: &ModuleInfo) -> PyResult<PyObject> {
+fn convert_module_info_to_dict(
+    py: Python,
+    info: &ModuleInfo,
+    explorer: &ModuleTreeExplorer,
+) -> PyResult<PyObject> {
     let dict = pyo3::types::PyDict::new(py);
 
     // Create api dict
@@ -130,13 +260,25 @@ fn convert_module_info_to_dict(py: Python, info: &ModuleInfo) -> PyResult<PyObje
     api_dict.set_item("all", info.all_exports.as_ref().unwrap_or(&Vec::new()))?;
     api_dict.set_item("functions", &info.functions)?;
     api_dict.set_item("classes", &info.classes)?;
+    api_dict.set_item("abstract_classes", info.abstract_classes())?;
     api_dict.set_item("constants", &info.constants)?;
+    if !info.reexports.is_empty() {
+        api_dict.set_item("reexports", &info.reexports)?;
+    }
+    if info.dynamic_all {
+        api_dict.set_item("dynamic_all", true)?;
+    }
+    explorer.set_optional_api(py, info, &api_dict)?;
+    set_locations(info, &dict, &api_dict)?;
     dict.set_item("api", api_dict)?;
+    if info.truncated {
+        dict.set_item("truncated", true)?;
+    }
 
     // Convert submodules recursively
     let submodules_dict = pyo3::types::PyDict::new(py);
     for (name, sub_info) in &info.submodules {
-        let sub_dict = convert_module_info_to_dict(py, sub_info)?;
+        let sub_dict = convert_module_info_to_dict(py, sub_info, explorer)?;
         submodules_dict.set_item(name, sub_dict)?;
     }
     dict.set_item("submodules", submodules_dict)?;
@@ -144,9 +286,178 @@ fn convert_module_info_to_dict(py: Python, info: &ModuleInfo) -> PyResult<PyObje
     Ok(dict.into())
 }
 
+/// Where a module's names are defined: the node's "file" and the api's "lines"
+/// The JSON formatter folds the lines into the name lists.
+fn set_locations(
+    info: &ModuleInfo,
+    node: &Bound<'_, pyo3::types::PyDict>,
+    api_dict: &Bound<'_, pyo3::types::PyDict>,
+) -> PyResult<()> {
+    if let Some(file) = &info.file {
+        node.set_item("file", file.display().to_string())?;
+    }
+    if !info.lines.is_empty() {
+        api_dict.set_item("lines", &info.lines)?;
+    }
+    Ok(())
+}
+
+/// Where each imported name comes from, with the leading dots of a relative import as "level"
+/// e.g. `from ..main import BaseModel as Model` -> {"Model": {"from": "main", "name": "BaseModel", "level": 2}}
+fn imports_dict<'py>(
+    py: Python<'py>,
+    info: &ModuleInfo,
+) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+    let imports = pyo3::types::PyDict::new(py);
+    for (name, import) in &info.import_map {
+        let entry = pyo3::types::PyDict::new(py);
+        entry.set_item("from", &import.from_module)?;
+        entry.set_item("name", &import.import_name)?;
+        entry.set_item("level", import.level)?;
+        imports.set_item(name, entry)?;
+    }
+    Ok(imports)
+}
+
+/// Public method names per class, e.g. {"Client": {"methods": ["get", "post"]}}
+/// Long classes list the first `MAX_CLASS_METHODS` and count the rest under "more".
+/// With `signatures`, each listed method's one-line signature is kept under "signatures".
+fn class_methods_dict<'py>(
+    py: Python<'py>,
+    info: &ModuleInfo,
+    signatures: bool,
+) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+    let classes = pyo3::types::PyDict::new(py);
+    for class_name in &info.classes {
+        let Some(class_info) = info.class_info.get(class_name) else {
+            continue;
+        };
+        let mut methods: Vec<&str> = Vec::new();
+        for method in &class_info.methods {
+            // Property setters and overloads repeat the name
+            if !method.name.starts_with('_') && !methods.contains(&method.name.as_str()) {
+                methods.push(&method.name);
+            }
+        }
+        let entry = pyo3::types::PyDict::new(py);
+        if methods.len() > MAX_CLASS_METHODS {
+            entry.set_item("more", methods.len() - MAX_CLASS_METHODS)?;
+            methods.truncate(MAX_CLASS_METHODS);
+        }
+        if signatures {
+            let method_signatures = pyo3::types::PyDict::new(py);
+            for method in &methods {
+                let key = format!("{}.{}", class_name, method);
+                if let Some(sig) = info.signatures.get(&key) {
+                    let compact = crate::signature::format_signature_compact(sig, None);
+                    method_signatures.set_item(method, compact)?;
+                }
+            }
+            entry.set_item("signatures", method_signatures)?;
+        }
+        entry.set_item("methods", methods)?;
+        classes.set_item(class_name, entry)?;
+    }
+    Ok(classes)
+}
+
 impl ModuleTreeExplorer {
+    pub fn new(root_module_path: String, max_depth: usize) -> Self {
+        Self {
+            root_module_path,
+            max_depth,
+            verbosity: Verbosity::default(),
+            prefer: StubPreference::from_env(),
+            include_imports: false,
+            hide_empty: false,
+            class_depth: 0,
+            signatures: false,
+            timeout: None,
+            deadline: Mutex::new(None),
+            tree: Mutex::new(None),
+        }
+    }
+
+    /// Stop descending into submodules once exploring takes longer than `timeout`
+    /// Unexplored branches are marked `truncated` instead of failing the whole tree.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Override the `PRETTY_MOD_PREFER` stub preference
+    pub fn with_stub_preference(mut self, prefer: StubPreference) -> Self {
+        self.prefer = prefer;
+        self
+    }
+
+    /// Add each module's import map to the explored tree under `api["imports"]`
+    pub fn with_imports(mut self, include_imports: bool) -> Self {
+        self.include_imports = include_imports;
+        self
+    }
+
+    /// Leave out submodules with no public API (counted under `hidden_empty`)
+    pub fn with_hide_empty(mut self, hide_empty: bool) -> Self {
+        self.hide_empty = hide_empty;
+        self
+    }
+
+    /// Nest each class's public methods under it (1) or list class names only (0)
+    pub fn with_class_depth(mut self, class_depth: usize) -> Self {
+        self.class_depth = class_depth;
+        self
+    }
+
+    /// With `class_depth` 1, show each method with its signature instead of its name only
+    pub fn with_signatures(mut self, signatures: bool) -> Self {
+        self.signatures = signatures;
+        self
+    }
+
+    /// The `api` keys that are only added on request
+    fn set_optional_api(
+        &self,
+        py: Python,
+        info: &ModuleInfo,
+        api_dict: &Bound<'_, pyo3::types::PyDict>,
+    ) -> PyResult<()> {
+        if self.include_imports && !info.import_map.is_empty() {
+            api_dict.set_item("imports", imports_dict(py, info)?)?;
+        }
+        if self.class_depth > 0 && !info.classes.is_empty() {
+            let class_methods = class_methods_dict(py, info, self.signatures)?;
+            api_dict.set_item("class_methods", class_methods)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the time budget for the current exploration is used up
+    fn out_of_time(&self) -> bool {
+        self.deadline
+            .lock()
+            .ok()
+            .and_then(|deadline| *deadline)
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Set how much per-step detail is written to stderr
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// The tree dict for module info parsed elsewhere (e.g., source piped on stdin)
+    pub fn tree_from_module_info(&self, py: Python, info: &ModuleInfo) -> PyResult<PyObject> {
+        convert_module_info_to_dict(py, info, self)
+    }
+
     /// Get Python's sys.path to guide module discovery
-    fn get_sys_path(&self, py: Python) -> PyResult<Vec<PathBuf>> {
+    /// (the target interpreter's path when exploring another environment)
+    pub fn get_sys_path(&self, py: Python) -> PyResult<Vec<PathBuf>> {
+        if let Some(target) = crate::interpreter::target() {
+            return Ok(target.sys_path.clone());
+        }
         let sys = py.import("sys")?;
         let sys_path: Vec<String> = sys.getattr("path")?.extract()?;
         Ok(sys_path.into_iter().map(PathBuf::from).collect())
@@ -160,19 +471,48 @@ impl ModuleTreeExplorer {
     ) -> PyResult<ModuleInfo> {
         // Handle dotted module paths (e.g., "urllib.request")
         let parts: Vec<&str> = module_path.split('.').collect();
+        if let Ok(mut deadline) = self.deadline.lock() {
+            *deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        }
 
         // Find the root module's filesystem path
         let (root_path, start_index) = match self.find_module_path_filesystem(py, &parts) {
             Ok(result) => result,
-            Err(e) => {
-                return Err(e);
+            Err(e) if RUNTIME_ALIASES.contains(&module_path) => {
+                return self
+                    .explore_runtime_module(py, module_path)
+                    .unwrap_or(Err(e));
             }
+            Err(e) => return Err(e),
         };
-        
+
+        // A plain module file can't contain submodules on disk, so the rest of the path
+        // names a module assigned at runtime (e.g., os.path)
+        if start_index + 1 < parts.len() && root_path.is_file() {
+            if let Some(result) = self.explore_runtime_module(py, module_path) {
+                return result;
+            }
+        }
+
+        // A PEP 561 "<name>-stubs" package supplies types for the implementation
+        let stubs = match self.prefer {
+            StubPreference::Implementation => None,
+            _ => stubs_root(&self.get_sys_path(py)?, parts[0]),
+        };
+        if let Some(stubs) = &stubs {
+            self.verbosity
+                .detail(&format!("using stubs from {}", stubs.display()));
+        }
 
         // Build the module tree from the found path
         // Use start_index+1 to skip the part that was already resolved
-        self.build_module_tree_from_parts(&root_path, &parts[start_index + 1..], module_path, 0)
+        self.build_module_tree_from_parts(
+            &root_path,
+            &parts[start_index + 1..],
+            module_path,
+            0,
+            stubs.as_deref(),
+        )
     }
 
     /// Build module tree by walking filesystem (like ruff does)
@@ -181,18 +521,17 @@ impl ModuleTreeExplorer {
         path: &Path,
         module_path: &str,
         depth: usize,
+        stubs: Option<&Path>,
     ) -> PyResult<ModuleInfo> {
         let mut info = if path.is_file() {
             // Parse the .py file directly
-            ModuleInfo::from_python_file(path)?
+            self.parse_module(path, module_path, stubs)?
         } else if path.is_dir() {
             // Check for __init__.py
-            let init_py = path.join("__init__.py");
-            if init_py.exists() {
-                ModuleInfo::from_python_file(&init_py)?
-            } else {
+            match package_init(path) {
+                Some(init_py) => self.parse_module(&init_py, module_path, stubs)?,
                 // Namespace package
-                ModuleInfo::new()
+                None => ModuleInfo::new(),
             }
         } else {
             return Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
@@ -208,19 +547,23 @@ impl ModuleTreeExplorer {
 
             if let Ok(entries) = fs::read_dir(path) {
                 for entry in entries.flatten() {
+                    if self.out_of_time() {
+                        info.truncated = true;
+                        break;
+                    }
                     let entry_path = entry.path();
                     let file_name = entry.file_name();
                     let file_name_str = file_name.to_string_lossy();
 
                     // Skip private modules
-                    if file_name_str.starts_with('_') && file_name_str != "__init__.py" {
+                    if file_name_str.starts_with('_') {
                         continue;
                     }
 
                     // Check if it's a Python module
                     let submodule_name = if entry_path.is_dir() {
                         // Directory is a package if it has __init__.py
-                        if entry_path.join("__init__.py").exists() {
+                        if package_init(&entry_path).is_some() {
                             Some(file_name_str.to_string())
                         } else {
                             // Could be a namespace package, check if it has .py files
@@ -230,9 +573,14 @@ impl ModuleTreeExplorer {
                                 None
                             }
                         }
-                    } else if file_name_str.ends_with(".py") && file_name_str != "__init__.py" {
+                    } else if file_name_str.ends_with(".py") {
                         // Regular .py file
                         Some(file_name_str.trim_end_matches(".py").to_string())
+                    } else if file_name_str.ends_with(".pyi")
+                        && !entry_path.with_extension("py").exists()
+                    {
+                        // Stub for a compiled extension module
+                        Some(file_name_str.trim_end_matches(".pyi").to_string())
                     } else {
                         None
                     };
@@ -248,18 +596,25 @@ impl ModuleTreeExplorer {
 
             // Process submodules
             for (submodule_name, submodule_path) in submodules {
+                if self.out_of_time() {
+                    info.truncated = true;
+                    break;
+                }
                 let full_module_path = format!("{}.{}", module_path, submodule_name);
 
                 match self.build_module_tree_filesystem(
                     &submodule_path,
                     &full_module_path,
                     depth + 1,
+                    stubs,
                 ) {
                     Ok(submodule_info) => {
                         info.submodules.insert(submodule_name, submodule_info);
                     }
-                    Err(_) => {
+                    Err(e) => {
                         // Skip modules that fail to parse
+                        self.verbosity
+                            .detail(&format!("skipped {}: {}", full_module_path, e));
                     }
                 }
             }
@@ -288,14 +643,19 @@ impl ModuleTreeExplorer {
                     return Ok((py_file, i));
                 }
 
+                // Try as a stub for a compiled extension module
+                let stub_file = py_file.with_extension("pyi");
+                if stub_file.exists() {
+                    return Ok((stub_file, i));
+                }
+
                 // Try as a package directory
                 let pkg_dir = current_path.join(part);
                 if pkg_dir.is_dir() {
-                    let init_py = pkg_dir.join("__init__.py");
                     if i == parts.len() - 1 {
                         // Last part - return the directory
                         return Ok((pkg_dir, i));
-                    } else if init_py.exists() || has_python_files(&pkg_dir) {
+                    } else if package_init(&pkg_dir).is_some() || has_python_files(&pkg_dir) {
                         // Intermediate package - continue
                         current_path = pkg_dir;
                     } else {
@@ -321,33 +681,44 @@ impl ModuleTreeExplorer {
         remaining_parts: &[&str],
         full_module_path: &str,
         depth: usize,
+        stubs: Option<&Path>,
     ) -> PyResult<ModuleInfo> {
         if remaining_parts.is_empty() {
             // We've resolved all parts, build from this path
-            self.build_module_tree_filesystem(path, full_module_path, depth)
+            self.build_module_tree_filesystem(path, full_module_path, depth, stubs)
         } else {
             // We have more parts to resolve within this module
+            let parts: Vec<&str> = full_module_path.split('.').collect();
+            let current_module =
+                parts[..parts.len().saturating_sub(remaining_parts.len())].join(".");
             let mut info = if path.is_file() {
-                ModuleInfo::from_python_file(path)?
+                self.parse_module(path, &current_module, stubs)?
             } else {
-                let init_py = path.join("__init__.py");
-                if init_py.exists() {
-                    ModuleInfo::from_python_file(&init_py)?
-                } else {
-                    ModuleInfo::new()
+                match package_init(path) {
+                    Some(init_py) => self.parse_module(&init_py, &current_module, stubs)?,
+                    None => ModuleInfo::new(),
                 }
             };
 
             // Continue resolving the remaining parts
             let next_part = remaining_parts[0];
-            let next_path = path.join(next_part);
-
-            if next_path.exists() || next_path.with_extension("py").exists() {
+            let next_path = [
+                path.join(next_part),
+                path.join(format!("{}.py", next_part)),
+                path.join(format!("{}.pyi", next_part)),
+            ]
+            .into_iter()
+            .find(|candidate| candidate.exists());
+
+            // Resolving the requested path doesn't count toward max_depth; only
+            // the requested module's children do
+            if let Some(next_path) = next_path {
                 let sub_info = self.build_module_tree_from_parts(
                     &next_path,
                     &remaining_parts[1..],
                     full_module_path,
-                    depth + 1,
+                    depth,
+                    stubs,
                 )?;
                 info.submodules.insert(next_part.to_string(), sub_info);
             }
@@ -355,6 +726,188 @@ impl ModuleTreeExplorer {
             Ok(info)
         }
     }
+
+    /// Explore the file the interpreter actually loads a module from
+    /// Covers modules that only exist at runtime, e.g. os.path -> posixpath.py
+    fn explore_runtime_module(
+        &self,
+        py: Python,
+        module_path: &str,
+    ) -> Option<PyResult<ModuleInfo>> {
+        let path = runtime_module_file(py, module_path)?;
+        self.verbosity.detail(&format!(
+            "{} resolves to {} at runtime",
+            module_path,
+            path.display()
+        ));
+        Some(self.build_module_tree_filesystem(&path, module_path, 0, None))
+    }
+
+    /// Parse a module, combined with its file from an installed stubs package
+    /// according to the stub preference
+    fn parse_module(
+        &self,
+        path: &Path,
+        module_path: &str,
+        stubs: Option<&Path>,
+    ) -> PyResult<ModuleInfo> {
+        let stub = stubs.and_then(|root| stub_file(root, module_path));
+        match (self.prefer, stub) {
+            (StubPreference::Stubs, Some(stub)) => ModuleInfo::from_python_file(&stub),
+            (StubPreference::Merge, Some(stub)) => {
+                let mut info = parse_module_source(path)?;
+                match ModuleInfo::from_python_file(&stub) {
+                    Ok(stub_info) => merge_stub(&mut info, stub_info),
+                    Err(e) => {
+                        self.verbosity
+                            .detail(&format!("skipped stub {}: {}", stub.display(), e))
+                    }
+                }
+                Ok(info)
+            }
+            _ => parse_module_source(path),
+        }
+    }
+}
+
+/// The source file a dotted module path resolves to (a package's `__init__.py`,
+/// or the directory of a namespace package)
+pub fn module_source_path(py: Python, module_path: &str) -> Option<PathBuf> {
+    let parts: Vec<&str> = module_path.split('.').collect();
+    let explorer = ModuleTreeExplorer::new(module_path.to_string(), 0);
+    match explorer.find_module_path_filesystem(py, &parts).ok()? {
+        (path, index) if index + 1 == parts.len() => Some(package_init(&path).unwrap_or(path)),
+        _ => None,
+    }
+}
+
+/// Explore several packages, returning the tree label and tree
+/// A single package is shown as-is; several go under a synthetic root named `label`.
+/// `shape` applies the caller's tree options (imports, pruning, class depth) to each explorer.
+pub fn explore_packages(
+    py: Python,
+    label: &str,
+    packages: &[String],
+    max_depth: usize,
+    verbosity: Verbosity,
+    timeout: Option<Duration>,
+    shape: &dyn Fn(ModuleTreeExplorer) -> ModuleTreeExplorer,
+) -> PyResult<(String, PyObject)> {
+    if let [package] = packages {
+        let explorer = shape(ModuleTreeExplorer::new(package.clone(), max_depth))
+            .with_verbosity(verbosity)
+            .with_timeout(timeout);
+        return Ok((package.clone(), explorer.explore(py)?));
+    }
+
+    // Packages share one time budget
+    let started = Instant::now();
+    let submodules = pyo3::types::PyDict::new(py);
+    for package in packages {
+        let remaining = timeout.map(|timeout| timeout.saturating_sub(started.elapsed()));
+        let explorer = shape(ModuleTreeExplorer::new(package.clone(), max_depth))
+            .with_verbosity(verbosity)
+            .with_timeout(remaining);
+        match explorer.explore(py) {
+            Ok(tree) => submodules.set_item(package, tree)?,
+            Err(e) => verbosity.detail(&format!("skipped {}: {}", package, e)),
+        }
+    }
+    // The root has no API of its own, but the same keys as any module
+    let api = pyo3::types::PyDict::new(py);
+    for key in ["all", "functions", "classes", "constants"] {
+        api.set_item(key, pyo3::types::PyList::empty(py))?;
+    }
+    api.set_item("abstract_classes", pyo3::types::PyList::empty(py))?;
+    let tree = pyo3::types::PyDict::new(py);
+    tree.set_item("api", api)?;
+    tree.set_item("submodules", submodules)?;
+    Ok((label.to_string(), tree.into()))
+}
+
+/// A `timeout_seconds` option as a Duration (None means no budget)
+pub fn parse_timeout(timeout_seconds: Option<f64>) -> PyResult<Option<Duration>> {
+    timeout_seconds
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds).map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid timeout_seconds {}: must be a non-negative number",
+                    seconds
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Validate `class_depth`: classes can be expanded one level, into their methods
+pub fn parse_class_depth(class_depth: usize) -> PyResult<usize> {
+    if class_depth > 1 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid class_depth {}: must be 0 or 1",
+            class_depth
+        )));
+    }
+    Ok(class_depth)
+}
+
+/// A module's source file as the import system finds it (`importlib.util.find_spec`)
+/// Only parent packages are imported, not the module itself. Packages resolve to their
+/// directory; compiled modules and other environments are skipped.
+fn runtime_module_file(py: Python, module_path: &str) -> Option<PathBuf> {
+    if crate::interpreter::is_foreign() {
+        return None;
+    }
+    let spec = py
+        .import("importlib.util")
+        .ok()?
+        .call_method1("find_spec", (module_path,))
+        .ok()?;
+    if spec.is_none() {
+        return None;
+    }
+    // Frozen modules (e.g., posixpath since 3.11) keep their source path in loader_state
+    let file: String = if spec.getattr("has_location").ok()?.is_truthy().ok()? {
+        spec.getattr("origin").ok()?.extract().ok()?
+    } else {
+        spec.getattr("loader_state")
+            .ok()?
+            .getattr("filename")
+            .ok()?
+            .extract()
+            .ok()?
+    };
+    let path = PathBuf::from(file);
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    if !matches!(extension, Some("py" | "pyi")) {
+        return None;
+    }
+    if path.file_stem().is_some_and(|stem| stem == "__init__") {
+        return path.parent().map(Path::to_path_buf);
+    }
+    Some(path)
+}
+
+/// A package's `__init__.py`, or `__init__.pyi` when only a stub is present
+fn package_init(dir: &Path) -> Option<PathBuf> {
+    ["__init__.py", "__init__.pyi"]
+        .into_iter()
+        .map(|name| dir.join(name))
+        .find(|init| init.exists())
+}
+
+/// Parse a module, merging in its adjacent `.pyi` stub when the `.py` defines nothing
+/// (e.g., an `__init__.py` that only re-exports a compiled extension)
+fn parse_module_source(path: &Path) -> PyResult<ModuleInfo> {
+    let mut info = ModuleInfo::from_python_file(path)?;
+    let stub = path.with_extension("pyi");
+    if path.extension().is_some_and(|ext| ext == "py")
+        && info.functions.is_empty()
+        && info.classes.is_empty()
+        && stub.exists()
+    {
+        merge_adjacent_stub(&mut info, ModuleInfo::from_python_file(&stub)?);
+    }
+    Ok(info)
 }
 
 /// Check if a directory contains any Python files
@@ -364,7 +917,7 @@ fn has_python_files(path: &Path) -> bool {
             let entry_path = entry.path();
             if entry_path.is_file() {
                 if let Some(ext) = entry_path.extension() {
-                    if ext == "py" {
+                    if ext == "py" || ext == "pyi" {
                         return true;
                     }
                 }
@@ -0,0 +1,284 @@
+use crate::config::{colorize, DisplayConfig};
+use crate::explorer::ModuleTreeExplorer;
+use crate::module_info::{ImportInfo, ModuleInfo};
+use crate::stdlib::is_stdlib_module;
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// How often one module imports from a third-party package
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ModuleUse {
+    pub module: String,
+    pub uses: usize,
+}
+
+/// A third-party top-level package and the modules that import it
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ExternalPackage {
+    pub package: String, // Import name, e.g., "yaml" (not the distribution name)
+    pub uses: usize,     // Imported names across all modules
+    pub modules: Vec<ModuleUse>,
+}
+
+/// Third-party imports of a module tree, most used first
+#[derive(Clone, Debug, Serialize)]
+pub struct ExternalImportReport {
+    pub module: String,
+    pub packages: Vec<ExternalPackage>,
+}
+
+/// Walk a module tree and aggregate the third-party packages its modules import
+pub fn module_external_imports(
+    py: Python,
+    module_path: &str,
+    max_depth: usize,
+    verbosity: Verbosity,
+) -> PyResult<ExternalImportReport> {
+    let explorer =
+        ModuleTreeExplorer::new(module_path.to_string(), max_depth).with_verbosity(verbosity);
+    let tree = explorer.explore_module_pure_filesystem(py, module_path)?;
+    Ok(external_import_report(
+        module_path,
+        &tree,
+        &stdlib_module_names(py),
+    ))
+}
+
+/// `sys.stdlib_module_names` (Python 3.10+) plus the interpreter's builtin modules
+fn stdlib_module_names(py: Python) -> HashSet<String> {
+    let mut names = HashSet::new();
+    if let Ok(sys) = py.import("sys") {
+        for attribute in ["stdlib_module_names", "builtin_module_names"] {
+            if let Ok(found) = sys
+                .getattr(attribute)
+                .and_then(|value| value.extract::<HashSet<String>>())
+            {
+                names.extend(found);
+            }
+        }
+    }
+    names
+}
+
+/// Aggregate the third-party imports of an explored tree
+/// Relative imports, the package's own modules, and the stdlib are not external.
+pub fn external_import_report(
+    module_path: &str,
+    tree: &ModuleInfo,
+    stdlib: &HashSet<String>,
+) -> ExternalImportReport {
+    let own = module_path.split('.').next().unwrap_or(module_path);
+    let mut packages: BTreeMap<String, Vec<ModuleUse>> = BTreeMap::new();
+    collect(tree, module_path, own, stdlib, &mut packages);
+
+    let mut packages: Vec<ExternalPackage> = packages
+        .into_iter()
+        .map(|(package, modules)| ExternalPackage {
+            package,
+            uses: modules.iter().map(|m| m.uses).sum(),
+            modules,
+        })
+        .collect();
+    packages.sort_by(|a, b| b.uses.cmp(&a.uses).then_with(|| a.package.cmp(&b.package)));
+    ExternalImportReport {
+        module: module_path.to_string(),
+        packages,
+    }
+}
+
+fn collect(
+    info: &ModuleInfo,
+    module_path: &str,
+    own: &str,
+    stdlib: &HashSet<String>,
+    packages: &mut BTreeMap<String, Vec<ModuleUse>>,
+) {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for import in info.import_map.values() {
+        if let Some(package) = external_package(import, own, stdlib) {
+            *counts.entry(package).or_default() += 1;
+        }
+    }
+    for (package, uses) in counts {
+        packages
+            .entry(package.to_string())
+            .or_default()
+            .push(ModuleUse {
+                module: module_path.to_string(),
+                uses,
+            });
+    }
+
+    for (name, submodule) in &info.submodules {
+        collect(
+            submodule,
+            &format!("{}.{}", module_path, name),
+            own,
+            stdlib,
+            packages,
+        );
+    }
+}
+
+/// The top-level package of a third-party import, e.g. "httpx" for `from httpx._types import URL`
+fn external_package<'a>(
+    import: &'a ImportInfo,
+    own: &str,
+    stdlib: &HashSet<String>,
+) -> Option<&'a str> {
+    if import.is_relative {
+        return None;
+    }
+    // `import a.b` records "a.b" as the name; `from a.b import c` records the module
+    let module = import.from_module.as_deref().unwrap_or(&import.import_name);
+    let top = module.split('.').next().unwrap_or(module);
+    let internal = top.is_empty()
+        || top == own
+        || top == "__future__"
+        || stdlib.contains(top)
+        || is_stdlib_module(top);
+    (!internal).then_some(top)
+}
+
+/// Format an external import report as a table with the modules using each package
+pub fn format_external_imports_display(report: &ExternalImportReport) -> String {
+    let config = DisplayConfig::get();
+    let mut result = format!(
+        "{} {} external imports\n",
+        colorize(
+            &config.module_icon,
+            &config.color_scheme.module_color,
+            config
+        ),
+        colorize(&report.module, &config.color_scheme.module_color, config)
+    );
+    if report.packages.is_empty() {
+        result.push_str("No third-party imports");
+        return result;
+    }
+
+    let width = report
+        .packages
+        .iter()
+        .map(|p| p.package.len())
+        .max()
+        .unwrap_or(0)
+        .max("package".len());
+    result.push_str(&format!(
+        "\n{:<width$}  {:>4}  {:>7}\n",
+        "package", "uses", "modules"
+    ));
+    for package in &report.packages {
+        result.push_str(&format!(
+            "{:<width$}  {:>4}  {:>7}\n",
+            package.package,
+            package.uses,
+            package.modules.len()
+        ));
+    }
+
+    for package in &report.packages {
+        result.push_str(&format!(
+            "\n{}\n",
+            colorize(&package.package, &config.color_scheme.module_color, config)
+        ));
+        for (i, module) in package.modules.iter().enumerate() {
+            let prefix = if i == package.modules.len() - 1 {
+                &config.tree_last
+            } else {
+                &config.tree_branch
+            };
+            result.push_str(&format!(
+                "{}{} ({})\n",
+                colorize(prefix, &config.color_scheme.tree_color, config),
+                module.module,
+                module.uses
+            ));
+        }
+    }
+    result.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(from_module: Option<&str>, import_name: &str, is_relative: bool) -> ImportInfo {
+        ImportInfo {
+            from_module: from_module.map(str::to_string),
+            import_name: import_name.to_string(),
+            as_name: None,
+            is_relative,
+            level: u32::from(is_relative),
+        }
+    }
+
+    #[test]
+    fn test_external_package() {
+        let stdlib: HashSet<String> = ["dataclasses".to_string()].into();
+        let external = |i: &ImportInfo| external_package(i, "pkg", &stdlib).map(str::to_string);
+        assert_eq!(
+            external(&import(Some("httpx._types"), "URL", false)),
+            Some("httpx".to_string())
+        );
+        assert_eq!(
+            external(&import(None, "yaml.constructor", false)),
+            Some("yaml".to_string())
+        );
+        assert_eq!(external(&import(Some("pkg.core"), "load", false)), None);
+        assert_eq!(external(&import(Some("core"), "load", true)), None);
+        assert_eq!(external(&import(Some("dataclasses"), "field", false)), None);
+        assert_eq!(external(&import(None, "json", false)), None);
+    }
+
+    #[test]
+    fn test_external_import_report() {
+        let mut client = ModuleInfo::new();
+        client
+            .import_map
+            .insert("Client".to_string(), import(Some("httpx"), "Client", false));
+        let mut root = ModuleInfo::new();
+        root.import_map
+            .insert("URL".to_string(), import(Some("httpx"), "URL", false));
+        root.import_map.insert(
+            "Timeout".to_string(),
+            import(Some("httpx"), "Timeout", false),
+        );
+        root.import_map
+            .insert("yaml".to_string(), import(None, "yaml", false));
+        root.import_map
+            .insert("client".to_string(), import(None, "client", true));
+        root.submodules.insert("client".to_string(), client);
+
+        let report = external_import_report("pkg", &root, &HashSet::new());
+        assert_eq!(
+            report.packages,
+            vec![
+                ExternalPackage {
+                    package: "httpx".to_string(),
+                    uses: 3,
+                    modules: vec![
+                        ModuleUse {
+                            module: "pkg".to_string(),
+                            uses: 2
+                        },
+                        ModuleUse {
+                            module: "pkg.client".to_string(),
+                            uses: 1
+                        },
+                    ],
+                },
+                ExternalPackage {
+                    package: "yaml".to_string(),
+                    uses: 1,
+                    modules: vec![ModuleUse {
+                        module: "pkg".to_string(),
+                        uses: 1
+                    }],
+                },
+            ]
+        );
+    }
+}
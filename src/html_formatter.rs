@@ -0,0 +1,226 @@
+use crate::class_explorer::{ClassView, MethodEntry};
+use crate::config::DisplayConfig;
+use crate::module_info::FunctionSignature;
+use crate::module_summary::ModuleSummary;
+use crate::output_format::{pyobject_to_json_value, OutputFormatter};
+use crate::signature::split_parameters;
+use pyo3::prelude::*;
+use serde_json::Value;
+
+/// HTML formatter for notebooks (`_repr_html_`)
+pub struct HtmlFormatter;
+
+impl OutputFormatter for HtmlFormatter {
+    fn format_tree(&self, py: Python, tree: &PyObject, module_name: &str) -> PyResult<String> {
+        let tree = pyobject_to_json_value(py, tree)?;
+        Ok(format!(
+            "<div class=\"pretty-mod\">{}</div>",
+            format_tree_html(&tree, module_name, true)
+        ))
+    }
+
+    fn format_signature(&self, signature: &FunctionSignature) -> String {
+        format!(
+            "<div class=\"pretty-mod\">{}</div>",
+            format_signature_html(signature)
+        )
+    }
+
+    fn format_signature_not_available(&self, object_name: &str) -> String {
+        format!(
+            "<div class=\"pretty-mod\"><code>{}</code> <i>(signature not available)</i></div>",
+            escape(object_name)
+        )
+    }
+
+    fn format_ambiguous_signature(&self, object_name: &str, candidates: &[String]) -> String {
+        let items: String = candidates
+            .iter()
+            .map(|candidate| format!("<li><code>{}</code></li>", escape(candidate)))
+            .collect();
+        format!(
+            "<div class=\"pretty-mod\"><code>{}</code> <i>(ambiguous: defined in {} modules)</i>\
+             <ul>{}</ul></div>",
+            escape(object_name),
+            candidates.len(),
+            items
+        )
+    }
+
+    fn format_class(&self, class: &ClassView) -> String {
+        let groups = [
+            ("constructors", &class.constructors),
+            ("methods", &class.methods),
+            ("properties", &class.properties),
+            ("class/static methods", &class.class_methods),
+            ("inherited", &class.inherited),
+        ];
+        let items: String = groups
+            .iter()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(label, entries)| format!("<li>{}<ul>{}</ul></li>", label, method_items(entries)))
+            .collect();
+        format!(
+            "<div class=\"pretty-mod\"><b>{}</b> <small>{}</small><ul>{}</ul></div>",
+            escape(&class.name),
+            escape(&class.module),
+            items
+        )
+    }
+
+    fn format_source(&self, source: &str) -> String {
+        format!(
+            "<div class=\"pretty-mod\"><pre><code class=\"language-python\">{}</code></pre></div>",
+            escape(source)
+        )
+    }
+
+    fn format_module_summary(&self, summary: &ModuleSummary) -> String {
+        let description = summary
+            .summary
+            .as_deref()
+            .map(|line| format!(" <small>{}</small>", escape(line)))
+            .unwrap_or_default();
+        format!(
+            "<div class=\"pretty-mod\"><b>{}</b>{}<ul><li>{} functions, {} classes, {} constants, \
+             {} submodules</li><li>{}</li></ul></div>",
+            escape(&summary.name),
+            description,
+            summary.functions,
+            summary.classes,
+            summary.constants,
+            summary.submodules,
+            escape(&summary.exports.join(", "))
+        )
+    }
+}
+
+fn method_items(entries: &[MethodEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| format!("<li>{}</li>", format_signature_html(&entry.signature)))
+        .collect()
+}
+
+/// A module and its submodules as nested `<details>` (the root starts expanded)
+fn format_tree_html(tree: &Value, module_name: &str, open: bool) -> String {
+    let config = DisplayConfig::get();
+    let mut items = String::new();
+
+    let api = &tree["api"];
+    let groups = [
+        (&config.exports_icon, "__all__", &api["all"]),
+        (&config.exports_icon, "re-exports", &api["reexports"]),
+        (&config.function_icon, "functions", &api["functions"]),
+        (&config.class_icon, "classes", &api["classes"]),
+        (&config.constant_icon, "constants", &api["constants"]),
+    ];
+    for (icon, label, names) in groups {
+        let names: Vec<&str> = names
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        if !names.is_empty() {
+            items.push_str(&format!(
+                "<li>{} {}: <code>{}</code></li>",
+                escape(icon),
+                label,
+                escape(&names.join(", "))
+            ));
+        }
+    }
+    if tree["truncated"].as_bool() == Some(true) {
+        items.push_str("<li><i>… truncated (time budget exhausted)</i></li>");
+    }
+
+    if let Some(submodules) = tree["submodules"].as_object() {
+        for (name, submodule) in submodules {
+            items.push_str(&format!(
+                "<li>{}</li>",
+                format_tree_html(submodule, name, false)
+            ));
+        }
+    }
+
+    format!(
+        "<details{}><summary>{} <b>{}</b></summary><ul>{}</ul></details>",
+        if open { " open" } else { "" },
+        escape(&config.module_icon),
+        escape(module_name),
+        items
+    )
+}
+
+/// A signature on one line, e.g. `<code><b>loads</b>(s, *, cls=None) -&gt; Any</code>`
+pub fn format_signature_html(signature: &FunctionSignature) -> String {
+    let parameters = split_parameters(&signature.parameters).join(", ");
+    let returns = signature
+        .return_type
+        .as_ref()
+        .map(|return_type| format!(" -&gt; {}", escape(return_type)))
+        .unwrap_or_default();
+    format!(
+        "<code><b>{}</b>({}){}</code>",
+        escape(&signature.name),
+        escape(&parameters),
+        returns
+    )
+}
+
+/// Escape text for HTML element content and attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_signature_html_escapes() {
+        let signature = FunctionSignature {
+            name: "get".to_string(),
+            parameters: "key: str, default: list[int] | None=None".to_string(),
+            return_type: Some("dict[str, \"Item\"]".to_string()),
+            source: None,
+            kwargs: None,
+            param_docs: None,
+        };
+        assert_eq!(
+            format_signature_html(&signature),
+            "<code><b>get</b>(key: str, default: list[int] | None=None) -&gt; \
+             dict[str, &quot;Item&quot;]</code>"
+        );
+        assert_eq!(escape("a<b> & 'c'"), "a&lt;b&gt; &amp; &#39;c&#39;");
+    }
+
+    #[test]
+    fn test_format_tree_html() {
+        let tree = serde_json::json!({
+            "api": {"all": [], "functions": ["loads"], "classes": [], "constants": []},
+            "submodules": {
+                "decoder": {"api": {"classes": ["JSONDecoder"]}, "submodules": {}}
+            }
+        });
+        let html = format_tree_html(&tree, "json", true);
+        assert!(html.starts_with("<details open><summary>"));
+        assert!(html.contains("<b>json</b>"));
+        assert!(html.contains("functions: <code>loads</code>"));
+        assert!(html.contains("<details><summary>"));
+        assert!(html.contains("classes: <code>JSONDecoder</code>"));
+        assert!(!html.contains("__all__"));
+    }
+}
@@ -0,0 +1,214 @@
+use reqwest::header::{
+    HeaderMap, ACCEPT, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How long a cached response is used without revalidating (seconds)
+const DEFAULT_MAX_AGE: u64 = 600;
+
+/// A metadata response, fresh from the network or replayed from the cache
+#[derive(Debug)]
+pub struct MetadataResponse {
+    pub status: StatusCode,
+    pub body: String,
+    pub content_type: Option<String>,
+    pub url: String, // final URL after redirects, for resolving relative links
+}
+
+/// What is stored on disk for one URL
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: u64,
+    content_type: Option<String>,
+    body: String,
+}
+
+/// Directory for cached metadata: `PRETTY_MOD_CACHE_DIR`, else the platform cache directory
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("PRETTY_MOD_CACHE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let base = if cfg!(windows) {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Caches"))
+    } else {
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+    base.map(|base| base.join("pretty-mod"))
+}
+
+/// GET an index metadata URL, reusing a cached copy while fresh and revalidating
+/// it with ETag/Last-Modified afterwards (so unchanged metadata costs one 304)
+/// `PRETTY_MOD_NO_CACHE` bypasses the cache; `PRETTY_MOD_CACHE_MAX_AGE` sets freshness in seconds.
+pub fn get_metadata(url: &str, accept: Option<&str>) -> reqwest::Result<MetadataResponse> {
+    let cache_path = if std::env::var_os("PRETTY_MOD_NO_CACHE").is_some() {
+        None
+    } else {
+        cache_dir().map(|dir| dir.join("metadata").join(cache_key(url, accept)))
+    };
+    let cached: Option<CacheEntry> = cache_path
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok());
+
+    let now = unix_now();
+    if let Some(entry) = &cached {
+        if now.saturating_sub(entry.fetched_at) < max_age() {
+            return Ok(entry.replay());
+        }
+    }
+
+    let mut request = crate::http_client::get(url);
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept);
+    }
+    if let Some(entry) = &cached {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let response = request.send()?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        if let (Some(mut entry), Some(path)) = (cached, &cache_path) {
+            entry.fetched_at = now;
+            store(path, &entry);
+            return Ok(entry.replay());
+        }
+    }
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let final_url = response.url().to_string();
+    let body = response.text()?;
+    let content_type = header(&headers, CONTENT_TYPE.as_str());
+
+    if status.is_success() {
+        if let Some(path) = &cache_path {
+            store(
+                path,
+                &CacheEntry {
+                    url: final_url.clone(),
+                    etag: header(&headers, ETAG.as_str()),
+                    last_modified: header(&headers, LAST_MODIFIED.as_str()),
+                    fetched_at: now,
+                    content_type: content_type.clone(),
+                    body: body.clone(),
+                },
+            );
+        }
+    }
+
+    Ok(MetadataResponse {
+        status,
+        body,
+        content_type,
+        url: final_url,
+    })
+}
+
+impl CacheEntry {
+    fn replay(&self) -> MetadataResponse {
+        MetadataResponse {
+            status: StatusCode::OK,
+            body: self.body.clone(),
+            content_type: self.content_type.clone(),
+            url: self.url.clone(),
+        }
+    }
+}
+
+/// Cache file name for a URL (and the media types asked for)
+fn cache_key(url: &str, accept: Option<&str>) -> String {
+    let digest = Sha256::digest(format!("{}\n{}", url, accept.unwrap_or("")).as_bytes());
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}.json", hex)
+}
+
+fn max_age() -> u64 {
+    std::env::var("PRETTY_MOD_CACHE_MAX_AGE")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_AGE)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Best-effort write: a read-only or missing cache directory just means no caching
+fn store(path: &Path, entry: &CacheEntry) {
+    let Ok(text) = serde_json::to_string(entry) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    // Write then rename so concurrent runs never read a partial entry
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+    if fs::write(&tmp, text).is_ok() {
+        let _ = fs::rename(&tmp, path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_depends_on_url_and_accept() {
+        let json = cache_key("https://pypi.org/pypi/toml/json", None);
+        assert_eq!(json, cache_key("https://pypi.org/pypi/toml/json", None));
+        assert_ne!(json, cache_key("https://pypi.org/pypi/tomli/json", None));
+        assert_ne!(
+            json,
+            cache_key("https://pypi.org/pypi/toml/json", Some("text/html"))
+        );
+        assert!(json.ends_with(".json"));
+    }
+
+    #[test]
+    fn test_cache_entry_round_trip() {
+        let entry = CacheEntry {
+            url: "https://pypi.org/simple/toml/".to_string(),
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+            fetched_at: 1,
+            content_type: Some("text/html".to_string()),
+            body: "<a href=\"toml-0.10.2.tar.gz\">toml-0.10.2.tar.gz</a>".to_string(),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metadata").join("entry.json");
+        store(&path, &entry);
+        let loaded: CacheEntry =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let replayed = loaded.replay();
+        assert_eq!(replayed.status, StatusCode::OK);
+        assert_eq!(replayed.body, entry.body);
+        assert_eq!(replayed.url, entry.url);
+    }
+}
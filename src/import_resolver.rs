@@ -1,14 +1,8 @@
 use crate::module_info::FunctionSignature;
 use pyo3::prelude::*;
-use std::env;
-
-macro_rules! debug_log {
-    ($($arg:tt)*) => {
-        if env::var("PRETTY_MOD_DEBUG").is_ok() {
-            eprintln!("[DEBUG] {}", format!($($arg)*));
-        }
-    };
-}
+use crate::logging::debug_log;
+use crate::verbosity::Verbosity;
+use std::path::PathBuf;
 
 /// Resolves symbols through import chains using existing infrastructure
 pub struct ImportChainResolver;
@@ -23,9 +17,24 @@ impl ImportChainResolver {
         &self, 
         py: Python,
         module_path: &str, 
-        symbol_name: &str
+        symbol_name: &str,
+        verbosity: Verbosity,
     ) -> Option<FunctionSignature> {
+        self.resolve_symbol_definition(py, module_path, symbol_name, verbosity)
+            .map(|(sig, _)| sig)
+    }
+
+    /// Like `resolve_symbol_signature`, with the file the chain ended in
+    /// (None for smart signatures of known patterns)
+    pub fn resolve_symbol_definition(
+        &self,
+        py: Python,
+        module_path: &str,
+        symbol_name: &str,
+        verbosity: Verbosity,
+    ) -> Option<(FunctionSignature, Option<PathBuf>)> {
         debug_log!("Resolving {}:{}", module_path, symbol_name);
+        verbosity.detail(&format!("resolving {}:{}", module_path, symbol_name));
         
         // First, try to get the module's __init__.py info
         let explorer = crate::explorer::ModuleTreeExplorer::new(module_path.to_string(), 2);
@@ -36,7 +45,7 @@ impl ImportChainResolver {
             // Check if symbol is directly available
             if let Some(sig) = module_info.signatures.get(symbol_name) {
                 debug_log!("Found {} directly in module signatures", symbol_name);
-                return Some(sig.clone());
+                return Some((sig.clone(), module_info.file.clone()));
             }
             
             // Check if the symbol is imported from somewhere else
@@ -81,6 +90,10 @@ impl ImportChainResolver {
                 
                 // Try to get the signature from the target module
                 debug_log!("Resolved target module: {}", target_module);
+                verbosity.detail(&format!(
+                    "chain hop: {}:{} -> {}:{}",
+                    module_path, symbol_name, target_module, import_info.import_name
+                ));
                 
                 if !target_module.is_empty() {
                     let target_explorer = crate::explorer::ModuleTreeExplorer::new(target_module.clone(), 2);
@@ -94,7 +107,7 @@ impl ImportChainResolver {
                         // Look for the imported symbol in the target module
                         if let Some(sig) = target_info.signatures.get(&import_info.import_name) {
                             debug_log!("Found signature for {}", import_info.import_name);
-                            return Some(sig.clone());
+                            return Some((sig.clone(), target_info.file.clone()));
                         }
                         
                         // Check if it's a class and look for __init__ or __call__
@@ -102,13 +115,13 @@ impl ImportChainResolver {
                             // Try __init__ first
                             let init_name = format!("{}.__init__", import_info.import_name);
                             if let Some(sig) = target_info.signatures.get(&init_name) {
-                                return Some(sig.clone());
+                                return Some((sig.clone(), target_info.file.clone()));
                             }
                             
                             // Try __call__ method (for callable classes)
                             let call_name = format!("{}.__call__", import_info.import_name);
                             if let Some(sig) = target_info.signatures.get(&call_name) {
-                                return Some(sig.clone());
+                                return Some((sig.clone(), target_info.file.clone()));
                             }
                         }
 
@@ -125,14 +138,14 @@ impl ImportChainResolver {
                             let call_name = format!("{}.__call__", decorator_class);
                             if let Some(sig) = target_info.signatures.get(&call_name) {
                                 debug_log!("Found decorator __call__ signature");
-                                return Some(sig.clone());
+                                return Some((sig.clone(), target_info.file.clone()));
                             }
                             
                             // Try __init__ as fallback
                             let init_name = format!("{}.__init__", decorator_class);
                             if let Some(sig) = target_info.signatures.get(&init_name) {
                                 debug_log!("Found decorator __init__ signature");
-                                return Some(sig.clone());
+                                return Some((sig.clone(), target_info.file.clone()));
                             }
                             
                             // Create smart signature since decorator class exists
@@ -143,11 +156,15 @@ impl ImportChainResolver {
                                 _ => "func=None, *args, **kwargs".to_string(),
                             };
                             
-                            return Some(crate::module_info::FunctionSignature {
+                            let sig = crate::module_info::FunctionSignature {
                                 name: import_info.import_name.clone(),
                                 parameters: smart_parameters,
                                 return_type: Some("Decorated function or decorator".to_string()),
-                            });
+                                source: None,
+                                kwargs: None,
+                                param_docs: None,
+                            };
+                            return Some((sig, target_info.file.clone()));
                         }
                         
                         // Check if the symbol is itself imported from elsewhere in the target module
@@ -179,9 +196,19 @@ impl ImportChainResolver {
                             };
                             
                             debug_log!("Following import chain to {}", next_module);
+                            verbosity.detail(&format!(
+                                "chain hop: {}:{} -> {}:{}",
+                                target_module, import_info.import_name,
+                                next_module, target_import_info.import_name
+                            ));
                             
                             // Recursively resolve in the next module
-                            return self.resolve_symbol_signature(py, &next_module, &target_import_info.import_name);
+                            return self.resolve_symbol_definition(
+                                py,
+                                &next_module,
+                                &target_import_info.import_name,
+                                verbosity,
+                            );
                         }
                     }
                 }
@@ -197,7 +224,7 @@ impl ImportChainResolver {
                         let sub_explorer = crate::explorer::ModuleTreeExplorer::new(submodule_path.clone(), 2);
                         if let Ok(sub_info) = sub_explorer.explore_module_pure_filesystem(py, &submodule_path) {
                             if let Some(sig) = sub_info.signatures.get(symbol_name) {
-                                return Some(sig.clone());
+                                return Some((sig.clone(), sub_info.file.clone()));
                             }
                         }
                     }
@@ -207,6 +234,7 @@ impl ImportChainResolver {
         
         // If no import chain found, try smart signatures for known patterns
         self.try_smart_signatures(module_path, symbol_name)
+            .map(|sig| (sig, None))
     }
 
     /// Generate smart signatures for known decorator patterns when AST parsing fails
@@ -220,6 +248,9 @@ impl ImportChainResolver {
                 name: "flow".to_string(),
                 parameters: "func=None, *, name=None, description=None, version=None, flow_run_name=None, task_runner=None, timeout_seconds=None, validate_parameters=True, persist_result=None, result_storage=None, result_serializer=None, cache_policy=None, cache_expiration=None, cache_key_fn=None, on_completion=None, on_failure=None, on_cancellation=None, on_crashed=None, on_running=None, retries=None, retry_delay_seconds=None, retry_jitter_factor=None, log_prints=None".to_string(),
                 return_type: Some("Decorated function or decorator".to_string()),
+                source: None,
+                kwargs: None,
+                param_docs: None,
             });
         }
 
@@ -230,6 +261,9 @@ impl ImportChainResolver {
                 name: "task".to_string(),
                 parameters: "func=None, *, name=None, description=None, tags=None, version=None, cache_policy=None, cache_expiration=None, cache_key_fn=None, task_run_name=None, retries=None, retry_delay_seconds=None, retry_jitter_factor=None, persist_result=None, result_storage=None, result_serializer=None, timeout_seconds=None, log_prints=None, refresh_cache=None, on_completion=None, on_failure=None".to_string(),
                 return_type: Some("Decorated function or decorator".to_string()),
+                source: None,
+                kwargs: None,
+                param_docs: None,
             });
         }
 
@@ -262,6 +296,9 @@ mod tests {
                 name: "my_func".to_string(),
                 parameters: "x: int, y: str".to_string(),
                 return_type: Some("bool".to_string()),
+                source: None,
+                kwargs: None,
+                param_docs: None,
             },
         );
         
@@ -278,6 +315,7 @@ mod tests {
             import_name: "FlowDecorator".to_string(),
             as_name: Some("flow".to_string()),
             is_relative: true,
+            level: 1,
         };
         
         assert_eq!(import_info.from_module, Some(".flows".to_string()));
@@ -294,6 +332,7 @@ mod tests {
             import_name: "BaseModel".to_string(),
             as_name: None,
             is_relative: false,
+            level: 0,
         };
         
         assert_eq!(import_info.from_module, Some("pydantic".to_string()));
@@ -315,6 +354,7 @@ mod tests {
                 import_name: "FlowDecorator".to_string(),
                 as_name: Some("flow".to_string()),
                 is_relative: true,
+                level: 1,
             },
         );
         
@@ -325,6 +365,7 @@ mod tests {
                 import_name: "BaseModel".to_string(),
                 as_name: None,
                 is_relative: false,
+                level: 0,
             },
         );
         
@@ -0,0 +1,148 @@
+use pyo3::prelude::*;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Arc;
+
+/// Prints the target interpreter's version and module search path as JSON
+const QUERY_SCRIPT: &str = "import json, sys; print(json.dumps({\
+'version': '%d.%d.%d' % sys.version_info[:3], \
+'path': [p for p in sys.path if p]}))";
+
+/// Another Python interpreter whose environment is explored instead of the current one
+#[derive(Debug, Clone)]
+pub struct TargetInterpreter {
+    pub executable: String,
+    pub version: String,
+    pub sys_path: Vec<PathBuf>,
+}
+
+thread_local! {
+    /// The interpreter selected with `--python`, while a command runs on this thread
+    /// Calls running concurrently on other threads keep exploring their own environment.
+    static TARGET: RefCell<Option<Arc<TargetInterpreter>>> = const { RefCell::new(None) };
+}
+
+impl TargetInterpreter {
+    /// Run the interpreter once to read its `sys.path` and version
+    pub fn query(executable: &str) -> PyResult<Self> {
+        let output = Command::new(executable)
+            .args(["-c", QUERY_SCRIPT])
+            .output()
+            .map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyFileNotFoundError, _>(format!(
+                    "Cannot run Python interpreter '{}': {}",
+                    executable, e
+                ))
+            })?;
+        if !output.status.success() {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                "Python interpreter '{}' failed: {}",
+                executable,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Self::from_query_output(executable, &String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Parse the JSON printed by `QUERY_SCRIPT`
+    fn from_query_output(executable: &str, stdout: &str) -> PyResult<Self> {
+        let invalid = || {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                "Unexpected output from Python interpreter '{}'",
+                executable
+            ))
+        };
+        let json: serde_json::Value = serde_json::from_str(stdout.trim()).map_err(|_| invalid())?;
+        let version = json["version"].as_str().ok_or_else(invalid)?.to_string();
+        let sys_path = json["path"]
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .filter_map(|p| p.as_str())
+            .map(PathBuf::from)
+            .collect();
+        Ok(Self {
+            executable: executable.to_string(),
+            version,
+            sys_path,
+        })
+    }
+}
+
+/// Restores this thread's previous target when dropped
+/// Not `Send`: it must be dropped on the thread that selected the interpreter.
+pub struct TargetGuard {
+    previous: Option<Arc<TargetInterpreter>>,
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for TargetGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        TARGET.with(|target| *target.borrow_mut() = previous);
+    }
+}
+
+/// Explore `executable`'s environment on this thread until the returned guard is dropped
+/// Returns None (and changes nothing) when no interpreter was given.
+pub fn activate(executable: Option<&str>) -> PyResult<Option<TargetGuard>> {
+    let Some(executable) = executable else {
+        return Ok(None);
+    };
+    Ok(Some(enter(TargetInterpreter::query(executable)?)))
+}
+
+fn enter(interpreter: TargetInterpreter) -> TargetGuard {
+    let previous = TARGET.with(|target| target.borrow_mut().replace(Arc::new(interpreter)));
+    TargetGuard {
+        previous,
+        _thread: PhantomData,
+    }
+}
+
+/// The selected interpreter, if this thread is exploring another environment
+pub fn target() -> Option<Arc<TargetInterpreter>> {
+    TARGET.with(|target| target.borrow().clone())
+}
+
+/// Whether this thread is exploring another interpreter's environment
+/// Runtime imports (inspection, downloads, installed metadata) would read the wrong environment.
+pub fn is_foreign() -> bool {
+    TARGET.with(|target| target.borrow().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_query_output() {
+        let stdout = r#"{"version": "3.11.9", "path": ["/usr/lib/python311.zip", "/venv/lib/python3.11/site-packages"]}"#;
+        let interpreter = TargetInterpreter::from_query_output("/venv/bin/python", stdout).unwrap();
+        assert_eq!(interpreter.version, "3.11.9");
+        assert_eq!(
+            interpreter.sys_path,
+            vec![
+                PathBuf::from("/usr/lib/python311.zip"),
+                PathBuf::from("/venv/lib/python3.11/site-packages"),
+            ]
+        );
+        assert!(TargetInterpreter::from_query_output("python", "not json").is_err());
+    }
+
+    #[test]
+    fn test_target_is_scoped_to_the_thread() {
+        let interpreter = TargetInterpreter {
+            executable: "/venv/bin/python".to_string(),
+            version: "3.11.9".to_string(),
+            sys_path: vec![PathBuf::from("/venv/lib/python3.11/site-packages")],
+        };
+        let guard = enter(interpreter);
+        assert!(is_foreign());
+        assert!(!std::thread::spawn(is_foreign).join().unwrap());
+        drop(guard);
+        assert!(!is_foreign());
+    }
+}
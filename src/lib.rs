@@ -1,80 +1,199 @@
+mod all_exports;
+mod api_diff;
+mod browse;
+mod check;
+mod class_explorer;
 mod config;
+mod coverage;
+mod daemon;
+mod deps;
+mod dist_metadata;
+mod docstring;
 mod explorer;
+mod external_imports;
+mod html_formatter;
+mod http_cache;
+mod http_client;
 mod import_resolver;
+mod interpreter;
+mod logging;
 mod module_info;
+mod module_summary;
 mod output_format;
 mod package_downloader;
+mod project;
+mod requirement;
+mod schema;
 mod semantic;
 mod signature;
+mod signature_object;
+mod simple_index;
+mod source_snippet;
 mod stdlib;
+mod stub_drift;
+mod stubs;
 mod tree_formatter;
+mod typed_dict;
 mod utils;
+mod verbosity;
 
+use crate::dist_metadata::{DistMetadata, Scripts};
 use crate::explorer::ModuleTreeExplorer;
-use crate::output_format::create_formatter;
-use crate::utils::{extract_base_package, try_download_and_import, import_object_impl};
+use crate::output_format::{
+    create_formatter, create_formatter_with_width, JsonFormatter, OutputFormatter,
+};
+use crate::package_downloader::PackageDownloader;
+use crate::utils::import_object_impl;
+use crate::verbosity::Verbosity;
 use pyo3::prelude::*;
 
 /// Display a module tree
 #[pyfunction]
-#[pyo3(signature = (root_module_path, max_depth = 2, quiet = false, format = "pretty"))]
-fn display_tree(py: Python, root_module_path: &str, max_depth: usize, quiet: bool, format: &str) -> PyResult<()> {
-    let formatter = create_formatter(format);
-    // Check for invalid single colon (but allow double colon)
-    if root_module_path.contains(':') && !root_module_path.contains("::") {
+#[pyo3(signature = (root_module_path, max_depth = 2, quiet = false, format = "pretty", verbose = false, python = None, timeout_seconds = None, include_imports = false, hide_empty = false, class_depth = 0, signatures = false, json_out = None))]
+#[allow(clippy::too_many_arguments)]
+fn display_tree(
+    py: Python,
+    root_module_path: &str,
+    max_depth: usize,
+    quiet: bool,
+    format: &str,
+    verbose: bool,
+    python: Option<&str>,
+    timeout_seconds: Option<f64>,
+    include_imports: bool,
+    hide_empty: bool,
+    class_depth: usize,
+    signatures: bool,
+    json_out: Option<&str>,
+) -> PyResult<()> {
+    let formatter = create_formatter(format)?;
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let timeout = explorer::parse_timeout(timeout_seconds)?;
+    let class_depth = explorer::parse_class_depth(class_depth)?;
+    // Options that shape the tree, shared by every exploration attempt below
+    let shape = |explorer: ModuleTreeExplorer| {
+        explorer
+            .with_verbosity(verbosity)
+            .with_imports(include_imports)
+            .with_hide_empty(hide_empty)
+            .with_class_depth(class_depth)
+            .with_signatures(signatures)
+    };
+
+    // "." (or any directory with a pyproject.toml) explores the project's own packages
+    if project::is_project_dir(root_module_path) {
+        let (label, tree) = project::explore_project(
+            py,
+            std::path::Path::new(root_module_path),
+            max_depth,
+            verbosity,
+            timeout,
+            &shape,
+        )?;
+        let dist = DistSource::Unknown;
+        return emit_tree(py, formatter.as_ref(), &tree, &label, dist, json_out);
+    }
+
+    // "-" parses a single module piped on stdin: no sys.path resolution, no downloads
+    if root_module_path == utils::STDIN_MODULE {
+        let explorer = shape(ModuleTreeExplorer::new(utils::STDIN_NAME.to_string(), 0));
+        let tree = explorer.tree_from_module_info(py, &utils::module_from_stdin(py)?)?;
+        let (label, dist) = (utils::STDIN_NAME, DistSource::Unknown);
+        return emit_tree(py, formatter.as_ref(), &tree, label, dist, json_out);
+    }
+
+    // Resolve modules against another interpreter's sys.path
+    let _target = activate_interpreter(python, verbosity)?;
+
+    // Check for invalid single colon (but allow double colon and direct reference URLs)
+    let without_url = match root_module_path.split_once('@') {
+        Some((before, after)) if requirement::is_url(after.trim()) => before,
+        _ => root_module_path,
+    };
+    if without_url.contains(':') && !without_url.contains("::") {
         return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
             format!("Invalid module path '{}': use 'pretty-mod sig' for exploring specific objects", root_module_path)
         ));
     }
     
-    // Parse the full specification
-    let (package_override, module_path, version) = utils::parse_full_spec(root_module_path);
-    
-    // Remove any PEP 508 version specifiers from module path
-    let module_name = module_path
-        .split(&['[', '>', '<', '=', '!'][..])
-        .next()
-        .unwrap_or(module_path)
-        .trim();
+    // Parse the full specification (extras, specifiers, and markers are stripped)
+    utils::check_pins(root_module_path)?;
+    let (package_override, module_name, version) = utils::parse_full_spec(root_module_path);
     
     // Try to explore the module directly first
-    let explorer = ModuleTreeExplorer::new(module_name.to_string(), max_depth);
+    let explorer =
+        shape(ModuleTreeExplorer::new(module_name.to_string(), max_depth)).with_timeout(timeout);
     match explorer.explore(py) {
         Ok(tree) => {
             // Display tree using the formatter
-            let tree_str = formatter.format_tree(py, &tree, module_name)?;
-            println!("{}", tree_str);
-            Ok(())
+            let dist = DistSource::Installed(module_name);
+            emit_tree(py, formatter.as_ref(), &tree, module_name, dist, json_out)
         }
         Err(e) => {
             // Check if it's a module not found error
             let err_str = e.to_string();
             if err_str.contains("No module named") || err_str.contains("ModuleNotFoundError") {
-                // Determine which package to download
-                let download_package = if let Some(pkg) = package_override {
-                    // Use the explicit package name
-                    pkg
-                } else {
-                    // Extract the base package name from module
-                    extract_base_package(module_name)
-                };
-                
-                // Build download spec with version if present
-                let download_spec = if let Some(v) = version {
-                    format!("{}@{}", download_package, v)
-                } else {
-                    download_package.to_string()
-                };
-                
-                // Try downloading and importing the package
-                match try_download_and_import(py, &download_spec, quiet, || {
+                // The name may be an installed distribution with a different import name
+                if package_override.is_none() {
+                    // Its RECORD lists the packages it installs, even inside namespaces
+                    let packages = dist_metadata::local_dist_packages(py, module_name);
+                    if !packages.is_empty() {
+                        verbosity.detail(&format!(
+                            "{} installs {}",
+                            module_name,
+                            packages.join(", ")
+                        ));
+                        let explored = explorer::explore_packages(
+                            py,
+                            module_name,
+                            &packages,
+                            max_depth,
+                            verbosity,
+                            timeout,
+                            &shape,
+                        );
+                        if let Ok((label, tree)) = explored {
+                            let dist = DistSource::Named(module_name);
+                            let formatter = formatter.as_ref();
+                            return emit_tree(py, formatter, &tree, &label, dist, json_out);
+                        }
+                    }
+                    for candidate in dist_metadata::local_import_candidates(py, module_name) {
+                        let explorer = shape(ModuleTreeExplorer::new(candidate.clone(), max_depth))
+                            .with_timeout(timeout);
+                        if let Ok(tree) = explorer.explore(py) {
+                            verbosity.detail(&format!(
+                                "{} is installed as '{}'",
+                                module_name, candidate
+                            ));
+                            let dist = DistSource::Installed(&candidate);
+                            return emit_tree(py, formatter.as_ref(), &tree, &candidate, dist, json_out);
+                        }
+                    }
+                }
+
+                // Try downloading and importing the package (namespace packages
+                // may come from a dashed distribution name, e.g. google-cloud-storage)
+                match utils::try_download_module(py, module_name, package_override, version, false, verbosity, |downloader| {
                     // Try exploration again with the full module path
-                    let explorer = ModuleTreeExplorer::new(module_name.to_string(), max_depth);
+                    let explorer = shape(ModuleTreeExplorer::new(module_name.to_string(), max_depth))
+                        .with_timeout(timeout);
                     match explorer.explore(py) {
                         Ok(tree) => {
-                            let tree_str = formatter.format_tree(py, &tree, module_name)?;
-                            println!("{}", tree_str);
-                            Ok(())
+                            let dist = DistSource::Downloaded(downloader);
+                            emit_tree(py, formatter.as_ref(), &tree, module_name, dist, json_out)
+                        }
+                        // A distribution name: explore the packages its wheel installs
+                        Err(e)
+                            if e.to_string().contains("No module named")
+                                && !downloader.packages().is_empty() =>
+                        {
+                            let packages = downloader.packages();
+                            let (label, tree) = explorer::explore_packages(
+                                py, module_name, packages, max_depth, verbosity, timeout, &shape,
+                            )?;
+                            let dist = DistSource::Downloaded(downloader);
+                            emit_tree(py, formatter.as_ref(), &tree, &label, dist, json_out)
                         }
                         Err(e) => Err(e)
                     }
@@ -82,6 +201,10 @@ fn display_tree(py: Python, root_module_path: &str, max_depth: usize, quiet: boo
                     Ok(()) => Ok(()),
                     Err(e) => {
                         let err_str = e.to_string();
+                        if err_str.contains("No distribution provides") {
+                            println!("Cannot explore {}: {}", module_name, e.value(py));
+                            return Ok(());
+                        }
                         if err_str.contains("No module named") {
                             let missing = err_str
                                 .split("No module named")
@@ -110,34 +233,606 @@ fn display_tree(py: Python, root_module_path: &str, max_depth: usize, quiet: boo
     }
 }
 
+/// Select another interpreter's environment for the rest of a command
+fn activate_interpreter(
+    python: Option<&str>,
+    verbosity: Verbosity,
+) -> PyResult<Option<interpreter::TargetGuard>> {
+    let guard = interpreter::activate(python)?;
+    if let Some(target) = interpreter::target() {
+        verbosity.detail(&format!(
+            "using {} (Python {}, {} path entries)",
+            target.executable,
+            target.version,
+            target.sys_path.len()
+        ));
+    }
+    Ok(guard)
+}
+
+/// Where an explored tree's distribution details come from
+enum DistSource<'a> {
+    /// Not part of a distribution (a local project, stdin)
+    Unknown,
+    /// The installed distribution providing this module
+    Installed(&'a str),
+    /// An installed distribution, by name
+    Named(&'a str),
+    Downloaded(&'a PackageDownloader),
+}
+
+impl DistSource<'_> {
+    fn metadata(&self, py: Python) -> Option<DistMetadata> {
+        match self {
+            DistSource::Unknown => None,
+            DistSource::Installed(module_name) => DistMetadata::from_installed(py, module_name),
+            DistSource::Named(dist_name) => DistMetadata::from_installed_dist(py, dist_name),
+            DistSource::Downloaded(downloader) => downloader.metadata().cloned(),
+        }
+    }
+
+    fn scripts(&self, py: Python) -> Scripts {
+        match self {
+            DistSource::Unknown => Scripts::new(),
+            DistSource::Installed(module_name) => dist_metadata::installed_scripts(py, module_name),
+            DistSource::Named(dist_name) => dist_metadata::installed_dist_scripts(py, dist_name),
+            DistSource::Downloaded(downloader) => downloader.scripts().clone(),
+        }
+    }
+}
+
+/// Print a tree in the chosen format and, with `json_out`, also write it as JSON
+/// The write happens after printing, so a failed write never loses the displayed tree.
+fn emit_tree(
+    py: Python,
+    formatter: &dyn OutputFormatter,
+    tree: &PyObject,
+    module_name: &str,
+    dist: DistSource,
+    json_out: Option<&str>,
+) -> PyResult<()> {
+    // Scripts belong to the distribution, so only its top-level package lists them
+    if !module_name.contains('.') {
+        let scripts = dist.scripts(py);
+        if !scripts.is_empty() {
+            tree.bind(py).set_item("scripts", scripts)?;
+        }
+    }
+    let metadata = if formatter.include_dist_metadata() || json_out.is_some() {
+        dist.metadata(py)
+    } else {
+        None
+    };
+    if formatter.include_dist_metadata() {
+        attach_dist_metadata(py, tree, metadata.clone())?;
+    }
+    println!("{}", formatter.format_tree(py, tree, module_name)?);
+
+    if let Some(path) = json_out {
+        attach_dist_metadata(py, tree, metadata)?;
+        let json = JsonFormatter.format_tree(py, tree, module_name)?;
+        output_format::write_json_file(path, &json)?;
+    }
+    Ok(())
+}
+
+/// Write the JSON form of a result to `json_out`, passing the formatted output through
+/// A failed write raises OSError carrying the formatted output as its `output` attribute.
+fn write_json_out(
+    py: Python,
+    output: String,
+    json_out: Option<&str>,
+    json: impl FnOnce() -> String,
+) -> PyResult<String> {
+    if let Some(path) = json_out {
+        if let Err(e) = output_format::write_json_file(path, &json()) {
+            e.value(py).setattr("output", &output)?;
+            return Err(e);
+        }
+    }
+    Ok(output)
+}
+
+/// Attach distribution metadata to an explored tree (omitted when unknown)
+fn attach_dist_metadata(py: Python, tree: &PyObject, metadata: Option<DistMetadata>) -> PyResult<()> {
+    if let Some(metadata) = metadata {
+        tree.bind(py).set_item("dist", metadata)?;
+    }
+    Ok(())
+}
+
 /// Display a function signature
 #[pyfunction]
-#[pyo3(signature = (import_path, quiet = false, format = "pretty"))]
-fn display_signature(py: Python, import_path: &str, quiet: bool, format: &str) -> PyResult<String> {
-    use crate::signature::try_ast_signature;
-    let formatter = create_formatter(format);
+#[pyo3(signature = (import_path, quiet = false, format = "pretty", verbose = false, width = None, python = None, json_out = None, entry_point = false, param_docs = false, show_source = false, source_lines = source_snippet::DEFAULT_SOURCE_LINES))]
+#[allow(clippy::too_many_arguments)]
+fn display_signature(
+    py: Python,
+    import_path: &str,
+    quiet: bool,
+    format: &str,
+    verbose: bool,
+    width: Option<usize>,
+    python: Option<&str>,
+    json_out: Option<&str>,
+    entry_point: bool,
+    param_docs: bool,
+    show_source: bool,
+    source_lines: usize,
+) -> PyResult<String> {
+    use crate::signature::{defined_in_footer, try_ast_signature};
+    let formatter = create_formatter_with_width(format, width)?;
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let _target = activate_interpreter(python, verbosity)?;
+
+    // "distribution:script" names a console script; show the function it runs
+    let target;
+    let import_path = if entry_point {
+        target = dist_metadata::resolve_entry_point(py, import_path, verbosity)?;
+        target.as_str()
+    } else {
+        import_path
+    };
+    utils::check_pins(import_path)?;
+
+    // "-:name" looks the object up in a module piped on stdin
+    let stdin_object = import_path
+        .strip_prefix(utils::STDIN_MODULE)
+        .and_then(|rest| rest.strip_prefix(':'));
+    if let Some(object_name) = stdin_object {
+        let info = utils::module_from_stdin(py)?;
+        return match info.signatures.get(object_name) {
+            Some(sig) => {
+                let sig = &with_param_docs(sig, param_docs);
+                write_json_out(py, formatter.format_signature(sig), json_out, || {
+                    JsonFormatter.format_signature(sig)
+                })
+            }
+            None => write_json_out(
+                py,
+                formatter.format_signature_not_available(object_name),
+                json_out,
+                || JsonFormatter.format_signature_not_available(object_name),
+            ),
+        };
+    }
+
+    // A path naming a module has no signature; summarize the module instead
+    if let Some(summary) = module_summary::summarize_module(py, import_path) {
+        let output = formatter.format_module_summary(&summary);
+        return write_json_out(py, output, json_out, || {
+            JsonFormatter.format_module_summary(&summary)
+        });
+    }
     
+    let object_name = utils::parse_object_spec(import_path)
+        .map(|(_, _, object_name, _)| object_name)
+        .unwrap_or(import_path);
+
     // First try to get signature from AST
-    if let Some(result) = try_ast_signature(py, import_path, quiet) {
+    if let Some(result) = try_ast_signature(py, import_path, verbosity) {
+        let candidates = &result.candidates;
         if let Some(ref sig) = result.signature {
-            return Ok(formatter.format_signature(sig));
+            let sig = &with_param_docs(sig, param_docs);
+            let source_code = show_source
+                .then(|| source_snippet::source_snippet(py, import_path, source_lines, verbosity))
+                .flatten();
+            let source_code = source_code.as_deref();
+            let mut output = if format.eq_ignore_ascii_case("json") {
+                JsonFormatter.format_signature_with_extras(sig, candidates, source_code)
+            } else {
+                formatter.format_signature(sig)
+            };
+            // Escape sequences only belong in the pretty terminal output
+            if format.eq_ignore_ascii_case("pretty") {
+                let footer = result.file.as_deref().and_then(defined_in_footer);
+                if let Some(footer) = footer {
+                    output.push('\n');
+                    output.push_str(&footer);
+                }
+                if !candidates.is_empty() {
+                    output.push('\n');
+                    output.push_str(&crate::signature::candidates_footer(candidates));
+                }
+            }
+            if let (false, Some(source_code)) = (format.eq_ignore_ascii_case("json"), source_code) {
+                output.push('\n');
+                output.push_str(&formatter.format_source(source_code));
+            }
+            return write_json_out(py, output, json_out, || {
+                JsonFormatter.format_signature_with_extras(sig, candidates, source_code)
+            });
+        }
+        // Several modules define it and the package re-exports none of them
+        if !candidates.is_empty() {
+            let output = formatter.format_ambiguous_signature(object_name, candidates);
+            return write_json_out(py, output, json_out, || {
+                JsonFormatter.format_ambiguous_signature(object_name, candidates)
+            });
         }
     }
-    
+
     // If AST parsing didn't find it, return a simple message
-    let object_name = if import_path.contains(':') {
-        import_path.split(':').last().unwrap_or(import_path)
-    } else {
-        import_path.split('.').last().unwrap_or(import_path)
+    let output = formatter.format_signature_not_available(object_name);
+    write_json_out(py, output, json_out, || {
+        JsonFormatter.format_signature_not_available(object_name)
+    })
+}
+
+/// Keep the docstring parameter descriptions only when they were asked for
+fn with_param_docs(
+    sig: &module_info::FunctionSignature,
+    param_docs: bool,
+) -> module_info::FunctionSignature {
+    let mut sig = sig.clone();
+    if !param_docs {
+        sig.param_docs = None;
+    }
+    sig
+}
+
+/// Explore a class: its methods grouped by kind, optionally with inherited members
+#[pyfunction]
+#[pyo3(signature = (import_path, include_inherited = false, quiet = false, verbose = false, dunders = "hide"))]
+fn explore_class(
+    py: Python,
+    import_path: &str,
+    include_inherited: bool,
+    quiet: bool,
+    verbose: bool,
+    dunders: &str,
+) -> PyResult<class_explorer::ClassView> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let dunders = class_explorer::Dunders::parse(dunders)?;
+    class_explorer::explore_class(py, import_path, include_inherited, dunders, verbosity)
+}
+
+/// Display a class's methods
+#[pyfunction]
+#[pyo3(signature = (import_path, include_inherited = false, quiet = false, format = "pretty", verbose = false, dunders = "hide"))]
+fn display_methods(
+    py: Python,
+    import_path: &str,
+    include_inherited: bool,
+    quiet: bool,
+    format: &str,
+    verbose: bool,
+    dunders: &str,
+) -> PyResult<String> {
+    let formatter = create_formatter(format)?;
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let dunders = class_explorer::Dunders::parse(dunders)?;
+    let class =
+        class_explorer::explore_class(py, import_path, include_inherited, dunders, verbosity)?;
+    Ok(formatter.format_class(&class))
+}
+
+/// Resolve a signature into a structured object (None when not found)
+#[pyfunction]
+#[pyo3(signature = (import_path, quiet = false, verbose = false))]
+fn get_signature(
+    py: Python,
+    import_path: &str,
+    quiet: bool,
+    verbose: bool,
+) -> Option<signature_object::SignatureObject> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    signature::try_ast_signature(py, import_path, verbosity)
+        .and_then(|result| result.signature)
+        .map(signature_object::SignatureObject::new)
+}
+
+/// Resolve the signature of a live function, class or method (None when not found)
+#[pyfunction]
+#[pyo3(signature = (obj, quiet = false, verbose = false))]
+fn sig_of(
+    py: Python,
+    obj: &Bound<'_, PyAny>,
+    quiet: bool,
+    verbose: bool,
+) -> PyResult<Option<signature_object::SignatureObject>> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    Ok(signature::live_object_signature(py, obj, verbosity)?
+        .map(signature_object::SignatureObject::new))
+}
+
+/// Check a signature against expected parameters (or a full compact signature)
+#[pyfunction]
+#[pyo3(signature = (import_path, params = None, return_type = None, expected = None, strict = false, exact = false, quiet = false))]
+#[allow(clippy::too_many_arguments)]
+fn check_signature(
+    py: Python,
+    import_path: &str,
+    params: Option<Vec<String>>,
+    return_type: Option<String>,
+    expected: Option<&str>,
+    strict: bool,
+    exact: bool,
+    quiet: bool,
+) -> PyResult<check::CheckReport> {
+    let expectation = match (expected, params) {
+        (Some(expected), _) => check::ExpectedSignature::from_compact(expected)?,
+        (None, Some(params)) => check::ExpectedSignature::from_params(&params, return_type, exact),
+        (None, None) => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "check_signature needs 'params' or 'expected'",
+            ))
+        }
     };
-    
-    Ok(formatter.format_signature_not_available(object_name))
+
+    let verbosity = Verbosity::from_flags(quiet, false);
+    let report = match signature::try_ast_signature(py, import_path, verbosity)
+        .and_then(|result| result.signature)
+    {
+        Some(sig) => {
+            let actual = signature_object::SignatureObject::new(sig);
+            check::compare_signature(import_path, &actual, &expectation)
+        }
+        None => check::CheckReport {
+            target: import_path.to_string(),
+            missing: expectation.parameters.iter().map(|p| p.to_text()).collect(),
+            mismatched: vec!["signature not available".to_string()],
+            ..Default::default()
+        },
+    };
+    report.enforce(strict)
+}
+
+/// Check a module's exports (`__all__`, or its public names) against an expected list
+#[pyfunction]
+#[pyo3(signature = (module_path, expected, strict = false, exact = false))]
+fn check_exports(
+    py: Python,
+    module_path: &str,
+    expected: Vec<String>,
+    strict: bool,
+    exact: bool,
+) -> PyResult<check::CheckReport> {
+    let explorer = ModuleTreeExplorer::new(module_path.to_string(), 0);
+    let info = explorer.explore_module_pure_filesystem(py, module_path)?;
+    let actual = info.all_exports.clone().unwrap_or_else(|| {
+        info.functions
+            .iter()
+            .chain(&info.classes)
+            .chain(&info.constants)
+            .chain(&info.reexports)
+            .cloned()
+            .collect()
+    });
+    check::compare_exports(module_path, &actual, &expected, exact).enforce(strict)
+}
+
+/// Compare a package's `.pyi` stubs with its implementation, module by module
+#[pyfunction]
+#[pyo3(signature = (module_path, max_depth = 2, strict = false, quiet = false))]
+fn check_stubs(
+    py: Python,
+    module_path: &str,
+    max_depth: usize,
+    strict: bool,
+    quiet: bool,
+) -> PyResult<Vec<stub_drift::StubDrift>> {
+    let verbosity = Verbosity::from_flags(quiet, false);
+    let reports = stub_drift::check_stub_drift(py, module_path, max_depth, verbosity)?;
+    let drifted: Vec<&str> = reports
+        .iter()
+        .filter(|report| !report.ok)
+        .map(|report| report.module.as_str())
+        .collect();
+    if strict && !drifted.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyAssertionError, _>(format!(
+            "Stubs drifted from the implementation in: {}",
+            drifted.join(", ")
+        )));
+    }
+    Ok(reports)
+}
+
+/// Compare a local project's public API with a published release (e.g., "mypkg@latest")
+#[pyfunction]
+#[pyo3(signature = (local_path, release, max_depth = 2, quiet = false, verbose = false))]
+fn diff_api(
+    py: Python,
+    local_path: &str,
+    release: &str,
+    max_depth: usize,
+    quiet: bool,
+    verbose: bool,
+) -> PyResult<api_diff::ApiDiff> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    api_diff::diff_local_release(
+        py,
+        std::path::Path::new(local_path),
+        release,
+        max_depth,
+        verbosity,
+    )
+}
+
+/// The JSON Schema of a machine-readable output ("tree", "signature", "diff", ...)
+#[pyfunction]
+fn get_schema(py: Python, kind: &str) -> PyResult<PyObject> {
+    let document = schema::schema(kind)?.to_string();
+    Ok(py.import("json")?.call_method1("loads", (document,))?.unbind())
 }
 
-/// Import an object from a module path (public API, no auto-download)
+/// Answer tree/sig/search requests on a unix socket from warm caches, until shut down
 #[pyfunction]
-pub fn import_object(py: Python, import_path: &str) -> PyResult<PyObject> {
-    import_object_impl(py, import_path)
+#[pyo3(signature = (socket = None, quiet = false, verbose = false))]
+fn serve(py: Python, socket: Option<&str>, quiet: bool, verbose: bool) -> PyResult<()> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    daemon::serve(py, &daemon::socket_path(socket), verbosity)
+}
+
+/// Send a request ({"op": "tree" | "sig" | "search" | "ping" | "shutdown", ...}) to the daemon
+/// With `spawn`, a daemon is started in the background when none is listening.
+#[pyfunction]
+#[pyo3(signature = (request, socket = None, spawn = true))]
+fn query_daemon(
+    py: Python,
+    request: &Bound<'_, PyAny>,
+    socket: Option<&str>,
+    spawn: bool,
+) -> PyResult<PyObject> {
+    daemon::query(py, &daemon::socket_path(socket), request, spawn)
+}
+
+/// Display a per-module stub drift report
+#[pyfunction]
+#[pyo3(signature = (module_path, max_depth = 2, quiet = false, format = "pretty", verbose = false))]
+fn display_stub_drift(
+    py: Python,
+    module_path: &str,
+    max_depth: usize,
+    quiet: bool,
+    format: &str,
+    verbose: bool,
+) -> PyResult<String> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let reports = stub_drift::check_stub_drift(py, module_path, max_depth, verbosity)?;
+    if format.eq_ignore_ascii_case("json") {
+        return serde_json::to_string_pretty(&reports)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()));
+    }
+    Ok(stub_drift::format_drift_display(module_path, &reports))
+}
+
+/// Display how completely a module tree's public functions are annotated
+#[pyfunction]
+#[pyo3(signature = (module_path, max_depth = 2, quiet = false, format = "pretty", verbose = false))]
+fn display_coverage(
+    py: Python,
+    module_path: &str,
+    max_depth: usize,
+    quiet: bool,
+    format: &str,
+    verbose: bool,
+) -> PyResult<String> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let report = coverage::module_coverage(py, module_path, max_depth, verbosity)?;
+    if format.eq_ignore_ascii_case("json") {
+        return serde_json::to_string_pretty(&report)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()));
+    }
+    Ok(coverage::format_coverage_display(&report))
+}
+
+/// Display the third-party packages a module tree imports and which modules use them
+#[pyfunction]
+#[pyo3(signature = (module_path, max_depth = 2, quiet = false, format = "pretty", verbose = false))]
+fn display_external_imports(
+    py: Python,
+    module_path: &str,
+    max_depth: usize,
+    quiet: bool,
+    format: &str,
+    verbose: bool,
+) -> PyResult<String> {
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let report = external_imports::module_external_imports(py, module_path, max_depth, verbosity)?;
+    if format.eq_ignore_ascii_case("json") {
+        return serde_json::to_string_pretty(&report)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()));
+    }
+    Ok(external_imports::format_external_imports_display(&report))
+}
+
+/// Display the API of every dependency listed in a pyproject.toml or requirements file
+#[pyfunction]
+#[pyo3(signature = (path, max_depth = 1, quiet = false, format = "pretty", verbose = false))]
+fn display_deps(
+    py: Python,
+    path: &str,
+    max_depth: usize,
+    quiet: bool,
+    format: &str,
+    verbose: bool,
+) -> PyResult<String> {
+    let formatter = create_formatter(format)?;
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    let requirements = deps::read_dependencies(std::path::Path::new(path))?;
+    deps::display_dependencies(
+        py,
+        &requirements,
+        max_depth,
+        formatter.as_ref(),
+        format == "json",
+        verbosity,
+    )
+}
+
+/// Browse a module tree interactively: expand modules, read signatures, filter with `/`
+/// Prints the static tree instead when stdout is not a terminal.
+#[pyfunction]
+#[pyo3(signature = (root_module_path, quiet = false, verbose = false))]
+fn browse(py: Python, root_module_path: &str, quiet: bool, verbose: bool) -> PyResult<()> {
+    use std::io::IsTerminal;
+    if !std::io::stdout().is_terminal() {
+        return display_tree(py, root_module_path, 2, quiet, "pretty", verbose, None, None, false, false, 0, false, None);
+    }
+
+    let verbosity = Verbosity::from_flags(quiet, verbose);
+    utils::check_pins(root_module_path)?;
+    let (package_override, module_name, version) = utils::parse_full_spec(root_module_path);
+    match browse::browse(py, module_name, verbosity) {
+        Err(e)
+            if e.is_instance_of::<pyo3::exceptions::PyModuleNotFoundError>(py)
+                && !stdlib::is_stdlib_module(module_name) =>
+        {
+            utils::try_download_module(
+                py,
+                module_name,
+                package_override,
+                version,
+                false,
+                verbosity,
+                |_| browse::browse(py, module_name, verbosity),
+            )
+        }
+        result => result,
+    }
+}
+
+/// Import an object from a module path (public API, downloads only when asked)
+/// A version pin must match the installed distribution; with `download`, a module
+/// that isn't installed is fetched from PyPI at the pinned version.
+#[pyfunction]
+#[pyo3(signature = (import_path, download = false, quiet = false))]
+pub fn import_object(
+    py: Python,
+    import_path: &str,
+    download: bool,
+    quiet: bool,
+) -> PyResult<PyObject> {
+    let (package_override, path) = match import_path.split_once("::") {
+        Some((package, path)) => (Some(package), path),
+        None => (None, import_path),
+    };
+    utils::check_pins(path)?;
+    let (path, version) = utils::strip_object_version(path);
+    let module_name = path.split(':').next().unwrap_or(&path);
+
+    if let Some(pin) = version {
+        utils::check_version_pin(py, module_name, pin)?;
+    }
+    match import_object_impl(py, &path) {
+        Err(e)
+            if download
+                && e.is_instance_of::<pyo3::exceptions::PyModuleNotFoundError>(py)
+                && !stdlib::is_stdlib_module(module_name) =>
+        {
+            let verbosity = Verbosity::from_flags(quiet, false);
+            utils::try_download_module(
+                py,
+                module_name,
+                package_override,
+                version,
+                false,
+                verbosity,
+                |_| import_object_impl(py, &path),
+            )
+        }
+        result => result,
+    }
 }
 
 
@@ -148,5 +843,26 @@ fn pretty_mod(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(display_tree, m)?)?;
     m.add_function(wrap_pyfunction!(display_signature, m)?)?;
     m.add_function(wrap_pyfunction!(import_object, m)?)?;
+    m.add_function(wrap_pyfunction!(explore_class, m)?)?;
+    m.add_function(wrap_pyfunction!(display_methods, m)?)?;
+    m.add_class::<signature_object::SignatureObject>()?;
+    m.add_class::<signature_object::Parameter>()?;
+    m.add_class::<check::CheckReport>()?;
+    m.add_function(wrap_pyfunction!(get_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(sig_of, m)?)?;
+    m.add_function(wrap_pyfunction!(check_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(check_exports, m)?)?;
+    m.add_class::<stub_drift::StubDrift>()?;
+    m.add_function(wrap_pyfunction!(check_stubs, m)?)?;
+    m.add_function(wrap_pyfunction!(display_stub_drift, m)?)?;
+    m.add_function(wrap_pyfunction!(display_deps, m)?)?;
+    m.add_function(wrap_pyfunction!(display_coverage, m)?)?;
+    m.add_function(wrap_pyfunction!(display_external_imports, m)?)?;
+    m.add_function(wrap_pyfunction!(browse, m)?)?;
+    m.add_class::<api_diff::ApiDiff>()?;
+    m.add_function(wrap_pyfunction!(diff_api, m)?)?;
+    m.add_function(wrap_pyfunction!(get_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(serve, m)?)?;
+    m.add_function(wrap_pyfunction!(query_daemon, m)?)?;
     Ok(())
 }
@@ -0,0 +1,76 @@
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use std::env;
+
+/// Python logging levels
+pub const DEBUG: u8 = 10;
+pub const INFO: u8 = 20;
+
+/// Cached handle to `logging.getLogger("pretty_mod")`
+static LOGGER: GILOnceCell<PyObject> = GILOnceCell::new();
+
+/// Get the pretty_mod logger, configuring it on first use
+fn logger(py: Python<'_>) -> Option<&PyObject> {
+    LOGGER
+        .get_or_try_init(py, || -> PyResult<PyObject> {
+            let logging = py.import("logging")?;
+            let logger = logging.call_method1("getLogger", ("pretty_mod",))?;
+
+            // PRETTY_MOD_DEBUG=1 is a convenience: enable DEBUG and print to stderr
+            // unless the application already attached its own handlers
+            if env::var("PRETTY_MOD_DEBUG").is_ok() {
+                logger.call_method1("setLevel", (DEBUG,))?;
+                if !logger.call_method0("hasHandlers")?.extract::<bool>()? {
+                    let handler = logging.call_method0("StreamHandler")?;
+                    let formatter =
+                        logging.call_method1("Formatter", ("[%(levelname)s] %(message)s",))?;
+                    handler.call_method1("setFormatter", (formatter,))?;
+                    logger.call_method1("addHandler", (handler,))?;
+                }
+            }
+
+            Ok(logger.unbind())
+        })
+        .ok()
+}
+
+/// Check whether the pretty_mod logger would emit a record at this level
+pub fn is_enabled(level: u8) -> bool {
+    Python::with_gil(|py| {
+        logger(py)
+            .and_then(|logger| logger.call_method1(py, "isEnabledFor", (level,)).ok())
+            .and_then(|enabled| enabled.extract::<bool>(py).ok())
+            .unwrap_or(false)
+    })
+}
+
+/// Emit a record on the pretty_mod logger
+pub fn log(level: u8, message: &str) {
+    Python::with_gil(|py| {
+        if let Some(logger) = logger(py) {
+            // Logging must never break exploration
+            let _ = logger.call_method1(py, "log", (level, message));
+        }
+    });
+}
+
+/// Log a DEBUG message; the message is only formatted when DEBUG is enabled
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        if $crate::logging::is_enabled($crate::logging::DEBUG) {
+            $crate::logging::log($crate::logging::DEBUG, &format!($($arg)*));
+        }
+    };
+}
+
+/// Log an INFO message; the message is only formatted when INFO is enabled
+macro_rules! info_log {
+    ($($arg:tt)*) => {
+        if $crate::logging::is_enabled($crate::logging::INFO) {
+            $crate::logging::log($crate::logging::INFO, &format!($($arg)*));
+        }
+    };
+}
+
+pub(crate) use debug_log;
+pub(crate) use info_log;
@@ -1,11 +1,12 @@
-use crate::{semantic, signature};
+use crate::all_exports::{self, StaticAll};
+use crate::{docstring, semantic, signature, typed_dict};
 use pyo3::prelude::*;
 use ruff_python_ast::{Expr, ExprList, ExprName, Mod, Stmt, StmtAssign};
 use ruff_python_parser::{parse, Mode};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Function signature information
 #[derive(Serialize, Deserialize, Clone, Debug, IntoPyObject)]
@@ -13,6 +14,31 @@ pub struct FunctionSignature {
     pub name: String,
     pub parameters: String,
     pub return_type: Option<String>,
+    /// Where the signature came from when not the AST (e.g., "docstring")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// `**kwargs: Unpack[SomeTypedDict]` expanded into the accepted keywords
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kwargs: Option<UnpackedKwargs>,
+    /// One-line parameter descriptions from a numpy- or Google-style docstring
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub param_docs: Option<BTreeMap<String, String>>,
+}
+
+/// The keywords accepted through `**kwargs: Unpack[TypedDict]`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, IntoPyObject)]
+pub struct UnpackedKwargs {
+    pub parameter: String,  // e.g., "kwargs"
+    pub typed_dict: String, // e.g., "RequestOptions"
+    pub fields: Vec<TypedDictField>,
+}
+
+/// A TypedDict key, e.g., `timeout: NotRequired[float]`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, IntoPyObject)]
+pub struct TypedDictField {
+    pub name: String,
+    pub annotation: String, // With Required/NotRequired unwrapped
+    pub required: bool,
 }
 
 /// Import information tracking where symbols come from
@@ -22,6 +48,57 @@ pub struct ImportInfo {
     pub import_name: String,          // e.g., "BaseModel"
     pub as_name: Option<String>,      // e.g., "Model" for "import BaseModel as Model"
     pub is_relative: bool,            // true for "from .main import"
+    #[serde(default)]
+    pub level: u32,                   // leading dots, e.g. 2 for "from ..main import"
+}
+
+impl ImportInfo {
+    /// The module named by the `from` clause, with relative imports resolved from `module_path`
+    /// None for `import x` and for relative imports beyond the top-level package.
+    pub fn source_module(&self, module_path: &str, is_package: bool) -> Option<String> {
+        if self.level == 0 {
+            return self.from_module.clone();
+        }
+        let base = relative_import_base(module_path, is_package, self.level)?;
+        match &self.from_module {
+            Some(from_module) => Some(format!("{}.{}", base, from_module)),
+            None => Some(base),
+        }
+    }
+}
+
+/// The package a relative import with `level` leading dots resolves against
+/// One dot is the package itself for an `__init__`, and the parent package otherwise.
+pub fn relative_import_base(module_path: &str, is_package: bool, level: u32) -> Option<String> {
+    let mut parts: Vec<&str> = module_path.split('.').collect();
+    let pops = (level as usize).checked_sub(1)? + usize::from(!is_package);
+    if pops >= parts.len() {
+        return None;
+    }
+    parts.truncate(parts.len() - pops);
+    Some(parts.join("."))
+}
+
+/// Class structure information used by the class explorer
+#[derive(Serialize, Deserialize, Clone, Debug, Default, IntoPyObject)]
+pub struct ClassInfo {
+    pub bases: Vec<String>,       // e.g., ["SessionRedirectMixin"] (as written in source)
+    pub methods: Vec<MethodInfo>, // In definition order; signatures live under "Class.method"
+    pub is_abstract: bool,        // ABC/ABCMeta base or any @abstractmethod
+}
+
+/// A method defined directly in a class body
+#[derive(Serialize, Deserialize, Clone, Debug, Default, IntoPyObject)]
+pub struct MethodInfo {
+    pub name: String,
+    pub decorators: Vec<String>, // e.g., ["property"], ["classmethod"], ["name.setter"]
+}
+
+/// Whether a function, class, or "Class.method" name is public
+/// Dunder methods such as `__init__` count as public.
+pub fn is_public_name(name: &str) -> bool {
+    name.split('.')
+        .all(|part| !part.starts_with('_') || part.starts_with("__"))
 }
 
 /// Rust representation of module information
@@ -31,10 +108,26 @@ pub struct ModuleInfo {
     pub classes: Vec<String>,
     pub constants: Vec<String>,
     pub imports: Vec<String>,
-    pub submodules: HashMap<String, ModuleInfo>,
+    pub submodules: BTreeMap<String, ModuleInfo>,
     pub all_exports: Option<Vec<String>>,
-    pub signatures: HashMap<String, FunctionSignature>,
-    pub import_map: HashMap<String, ImportInfo>,  // Maps symbol name to where it's imported from
+    pub signatures: BTreeMap<String, FunctionSignature>,
+    pub import_map: BTreeMap<String, ImportInfo>,  // Maps symbol name to where it's imported from
+    pub class_info: BTreeMap<String, ClassInfo>,   // Module-level classes by name
+    /// Names re-exported with `from .x import y as y` / `import x as x` (when there is no `__all__`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reexports: Vec<String>,
+    /// Submodules were left unexplored because the time budget ran out
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// `__all__` is assigned but can only be known at runtime
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dynamic_all: bool,
+    /// The line defining each public module-level function, class and constant
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lines: BTreeMap<String, usize>,
+    /// The file the module was parsed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
 }
 
 impl ModuleInfo {
@@ -44,29 +137,108 @@ impl ModuleInfo {
             classes: Vec::new(),
             constants: Vec::new(),
             imports: Vec::new(),
-            submodules: HashMap::new(),
+            submodules: BTreeMap::new(),
             all_exports: None,
-            signatures: HashMap::new(),
-            import_map: HashMap::new(),
+            signatures: BTreeMap::new(),
+            import_map: BTreeMap::new(),
+            class_info: BTreeMap::new(),
+            reexports: Vec::new(),
+            truncated: false,
+            dynamic_all: false,
+            lines: BTreeMap::new(),
+            file: None,
         }
     }
 
+    /// The module was parsed from a package's `__init__`
+    pub fn is_package(&self) -> bool {
+        self.file
+            .as_deref()
+            .and_then(Path::file_stem)
+            .is_some_and(|stem| stem == "__init__")
+    }
+
+    /// Listed classes that are abstract (in `classes` order)
+    pub fn abstract_classes(&self) -> Vec<String> {
+        self.classes
+            .iter()
+            .filter(|name| self.class_info.get(*name).is_some_and(|c| c.is_abstract))
+            .cloned()
+            .collect()
+    }
+
+    /// Count this module and all of its explored submodules
+    pub fn count_modules(&self) -> usize {
+        1 + self
+            .submodules
+            .values()
+            .map(|sub| sub.count_modules())
+            .sum::<usize>()
+    }
+
+    /// Sum a per-module count over this module and all of its submodules
+    pub fn count_items(&self, count: fn(&ModuleInfo) -> usize) -> usize {
+        count(self)
+            + self
+                .submodules
+                .values()
+                .map(|sub| sub.count_items(count))
+                .sum::<usize>()
+    }
+
+    /// Whether the module lists anything public (exports, functions, classes, constants)
+    /// A runtime-computed `__all__` counts, since its contents are unknown.
+    pub fn has_public_api(&self) -> bool {
+        self.all_exports.as_ref().is_some_and(|all| !all.is_empty())
+            || !self.reexports.is_empty()
+            || !self.functions.is_empty()
+            || !self.classes.is_empty()
+            || !self.constants.is_empty()
+            || self.dynamic_all
+    }
+
+    /// Drop submodules with no public API and no descendants that have one
+    /// Truncated modules are kept since their contents are unknown. Returns how many were dropped.
+    pub fn prune_empty(&mut self) -> usize {
+        let mut hidden = self
+            .submodules
+            .values_mut()
+            .map(|sub| sub.prune_empty())
+            .sum::<usize>();
+        let before = self.submodules.len();
+        self.submodules
+            .retain(|_, sub| sub.has_public_api() || sub.truncated || !sub.submodules.is_empty());
+        hidden += before - self.submodules.len();
+        hidden
+    }
+
     /// Parse a Python file and extract module information
     pub fn from_python_file(file_path: &Path) -> PyResult<Self> {
-        let mut info = ModuleInfo::new();
+        crate::daemon::cached_parse(file_path, || {
+            let source = fs::read_to_string(file_path).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                    "Failed to read {}: {}",
+                    file_path.display(),
+                    e
+                ))
+            })?;
+            Self::parse_source(&source, &file_path.display().to_string(), Some(file_path))
+        })
+    }
 
-        let source = fs::read_to_string(file_path).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                "Failed to read {}: {}",
-                file_path.display(),
-                e
-            ))
-        })?;
+    /// Parse module source that has no file (e.g., piped on stdin); `name` labels errors
+    /// Imports from other modules can't be followed since there is no package around it.
+    pub fn from_source(source: &str, name: &str) -> PyResult<Self> {
+        Self::parse_source(source, name, None)
+    }
+
+    fn parse_source(source: &str, name: &str, file_path: Option<&Path>) -> PyResult<Self> {
+        let mut info = ModuleInfo::new();
 
-        let parsed = parse(&source, Mode::Module.into()).map_err(|e| {
+        let parsed = parse(source, Mode::Module.into()).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PySyntaxError, _>(format!(
                 "Failed to parse {}: {:?}",
-                file_path.display(),
+                name,
                 e
             ))
         })?;
@@ -79,7 +251,7 @@ impl ModuleInfo {
 
         // Try enhanced semantic analysis first
         let mut analyzer = semantic::SemanticAnalyzer::new();
-        if analyzer.analyze_file(file_path).is_ok() {
+        if analyzer.analyze_source(source).is_ok() {
             // Extract signatures using semantic analysis (includes methods!)
             if analyzer.extract_module_info(&mut info).is_ok() {
                 // Semantic analysis succeeded - we now have method signatures too
@@ -119,6 +291,9 @@ impl ModuleInfo {
                                 name: name_str,
                                 parameters,
                                 return_type,
+                                source: None,
+                                kwargs: typed_dict::unpacked_kwargs(&func_def.parameters),
+                                param_docs: docstring::body_param_docs(&func_def.body),
                             },
                         );
                     }
@@ -141,6 +316,17 @@ impl ModuleInfo {
                                             name: class_name.clone(),
                                             parameters,
                                             return_type: None, // Constructors don't have explicit return types
+                                            source: None,
+                                            kwargs: typed_dict::unpacked_kwargs(
+                                                &func_def.parameters,
+                                            ),
+                                            // numpy style documents the constructor on the class
+                                            param_docs: docstring::body_param_docs(
+                                                &func_def.body,
+                                            )
+                                            .or_else(|| {
+                                                docstring::body_param_docs(&class_def.body)
+                                            }),
                                         },
                                     );
                                     break;
@@ -191,6 +377,7 @@ impl ModuleInfo {
                                 import_name,
                                 as_name,
                                 is_relative: false,
+                                level: 0,
                             },
                         );
                     }
@@ -212,6 +399,7 @@ impl ModuleInfo {
                                 import_name,
                                 as_name,
                                 is_relative,
+                                level: import_from.level,
                             },
                         );
                     }
@@ -231,6 +419,22 @@ impl ModuleInfo {
         // Process all statements in the module
         process_statements(&module.body, &mut info, &mut raw_functions, &mut raw_classes, &mut raw_constants);
 
+        info.lines = symbol_lines(&module.body, source);
+        info.file = file_path.map(Path::to_path_buf);
+
+        // An empty path resolves no imports, so sourced modules only see their own body
+        let file_path = file_path.unwrap_or(Path::new(""));
+
+        // `__all__` assembled from other modules' `__all__` lists
+        match all_exports::static_all(&module.body, file_path) {
+            StaticAll::Names(names) if !names.is_empty() => info.all_exports = Some(names),
+            StaticAll::Dynamic if info.all_exports.is_none() => info.dynamic_all = true,
+            _ => {}
+        }
+
+        // Expand **kwargs: Unpack[TypedDict] now that the module's definitions are known
+        typed_dict::expand_unpacked_kwargs(&mut info, &module.body, file_path);
+
         // Apply __all__ filter if present
         if let Some(ref all_exports) = info.all_exports {
             let export_set: HashSet<&str> = all_exports.iter().map(|s| s.as_str()).collect();
@@ -250,8 +454,76 @@ impl ModuleInfo {
             info.functions = raw_functions;
             info.classes = raw_classes;
             info.constants = raw_constants;
+
+            // PEP 484: a redundant alias marks an intentional re-export
+            info.reexports = info
+                .import_map
+                .iter()
+                .filter(|(name, import)| {
+                    !name.starts_with('_') && import.as_name.as_ref() == Some(&import.import_name)
+                })
+                .map(|(name, _)| name.clone())
+                .collect();
         }
 
         Ok(info)
     }
 }
+
+/// The 1-based line of each public function, class and constant defined in `stmts`
+/// Names are located where they are written, after any decorators; the first definition wins.
+fn symbol_lines(stmts: &[Stmt], source: &str) -> BTreeMap<String, usize> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let mut lines = BTreeMap::new();
+    collect_symbol_lines(stmts, &line_starts, &mut lines);
+    lines
+}
+
+fn collect_symbol_lines(
+    stmts: &[Stmt],
+    line_starts: &[usize],
+    lines: &mut BTreeMap<String, usize>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::FunctionDef(func_def) => {
+                let offset = usize::from(func_def.name.range.start());
+                add_symbol_line(lines, line_starts, func_def.name.as_str(), offset);
+            }
+            Stmt::ClassDef(class_def) => {
+                let offset = usize::from(class_def.name.range.start());
+                add_symbol_line(lines, line_starts, class_def.name.as_str(), offset);
+            }
+            Stmt::Assign(StmtAssign { targets, .. }) => {
+                for target in targets {
+                    if let Expr::Name(ExprName { id, range, .. }) = target {
+                        let offset = usize::from(range.start());
+                        add_symbol_line(lines, line_starts, id.as_str(), offset);
+                    }
+                }
+            }
+            // As in `process_statement`, e.g. `if TYPE_CHECKING:`
+            Stmt::If(if_stmt) => {
+                collect_symbol_lines(&if_stmt.body, line_starts, lines);
+                for clause in &if_stmt.elif_else_clauses {
+                    collect_symbol_lines(&clause.body, line_starts, lines);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn add_symbol_line(
+    lines: &mut BTreeMap<String, usize>,
+    line_starts: &[usize],
+    name: &str,
+    offset: usize,
+) {
+    if !name.starts_with('_') && !lines.contains_key(name) {
+        let line = line_starts.partition_point(|&start| start <= offset);
+        lines.insert(name.to_string(), line);
+    }
+}
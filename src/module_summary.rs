@@ -0,0 +1,220 @@
+use crate::config::{colorize, DisplayConfig};
+use crate::explorer::{module_source_path, ModuleTreeExplorer};
+use crate::module_info::{is_public_name, ModuleInfo};
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::fs;
+
+/// How many exports a summary lists before "..."
+const TOP_EXPORTS: usize = 8;
+
+/// What `sig` shows for a path that names a module rather than a function or class
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ModuleSummary {
+    /// Always "module", so tools can tell this apart from a signature
+    pub kind: &'static str,
+    pub name: String,
+    /// The first line of the module's docstring
+    pub summary: Option<String>,
+    pub functions: usize,
+    pub classes: usize,
+    pub constants: usize,
+    pub submodules: usize,
+    /// `__all__`, or else the public functions and classes (at most `TOP_EXPORTS`)
+    pub exports: Vec<String>,
+    /// Exports left out of `exports`
+    pub more_exports: usize,
+}
+
+/// Whether module `parent` binds `name` to something other than its submodule `name`
+/// A definition, or an import from elsewhere (e.g. `from .name import name`), which is
+/// how names in `__all__` or re-exported usually get there; `from . import name` is the
+/// submodule itself.
+fn binds_attribute(info: &ModuleInfo, parent: &str, name: &str) -> bool {
+    let defined = info.functions.iter().any(|function| function == name)
+        || info.classes.iter().any(|class| class == name)
+        || info.signatures.contains_key(name);
+    let imported = info.import_map.get(name).is_some_and(|import| {
+        let from_module = import.from_module.as_deref();
+        from_module.is_some_and(|from_module| import.is_relative || from_module != parent)
+    });
+    defined || imported
+}
+
+/// Summarize the module a `sig` path names, e.g. "prefect:flows" or "prefect.flows"
+/// None when the path is not a module, or its parent binds that name to something else.
+pub fn summarize_module(py: Python, import_path: &str) -> Option<ModuleSummary> {
+    let spec = crate::utils::parse_object_spec(import_path);
+    let module_path = match spec {
+        Some((_, parent, object_name, _)) => format!("{}.{}", parent, object_name),
+        None if !import_path.contains(['@', ':']) => import_path.to_string(),
+        None => return None,
+    };
+    let source_path = module_source_path(py, &module_path)?;
+
+    // A package attribute wins over a submodule of the same name
+    if let Some((_, parent, object_name, _)) = spec {
+        let defined = ModuleTreeExplorer::new(parent.to_string(), 1)
+            .explore_module_pure_filesystem(py, parent)
+            .is_ok_and(|info| binds_attribute(&info, parent, object_name));
+        if defined {
+            return None;
+        }
+    }
+    let info = ModuleTreeExplorer::new(module_path.clone(), 1)
+        .explore_module_pure_filesystem(py, &module_path)
+        .ok()?;
+
+    let mut exports: Vec<String> = match &info.all_exports {
+        Some(all) => all.clone(),
+        None => info
+            .functions
+            .iter()
+            .chain(&info.classes)
+            .filter(|name| is_public_name(name))
+            .cloned()
+            .collect(),
+    };
+    let more_exports = exports.len().saturating_sub(TOP_EXPORTS);
+    exports.truncate(TOP_EXPORTS);
+    let summary = source_path
+        .is_file()
+        .then(|| fs::read_to_string(&source_path).ok())
+        .flatten()
+        .and_then(|source| crate::docstring::module_summary_line(&source));
+
+    Some(ModuleSummary {
+        kind: "module",
+        name: module_path,
+        summary,
+        functions: info.functions.len(),
+        classes: info.classes.len(),
+        constants: info.constants.len(),
+        submodules: info.submodules.len(),
+        exports,
+        more_exports,
+    })
+}
+
+/// "3 functions", "1 class", ...
+fn count(n: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", n, if n == 1 { singular } else { plural })
+}
+
+/// The counts line, e.g. "3 functions, 1 class, 0 constants, 2 submodules"
+fn counts(summary: &ModuleSummary) -> String {
+    [
+        count(summary.functions, "function", "functions"),
+        count(summary.classes, "class", "classes"),
+        count(summary.constants, "constant", "constants"),
+        count(summary.submodules, "submodule", "submodules"),
+    ]
+    .join(", ")
+}
+
+/// The exports line, e.g. "flow, Flow, serve, ... (+4)"
+fn exports_line(summary: &ModuleSummary) -> String {
+    let mut line = summary.exports.join(", ");
+    if summary.more_exports > 0 {
+        line.push_str(&format!(", ... (+{})", summary.more_exports));
+    }
+    line
+}
+
+/// A module summary in the tree style, ending with a pointer to `pretty-mod tree`
+pub fn format_summary_display(summary: &ModuleSummary) -> String {
+    let config = DisplayConfig::get();
+    let tree = |prefix: &str| colorize(prefix, &config.color_scheme.tree_color, config);
+    let mut result = format!(
+        "{} {}",
+        colorize(
+            &config.module_icon,
+            &config.color_scheme.module_color,
+            config
+        ),
+        colorize(&summary.name, &config.color_scheme.module_color, config)
+    );
+    if let Some(line) = &summary.summary {
+        result.push_str(&format!(" - {}", line));
+    }
+
+    let mut lines = vec![counts(summary)];
+    if !summary.exports.is_empty() {
+        lines.push(format!(
+            "{} {}",
+            colorize(
+                &config.exports_icon,
+                &config.color_scheme.exports_color,
+                config
+            ),
+            exports_line(summary)
+        ));
+    }
+    lines.push(format!(
+        "{} is a module; try `pretty-mod tree {}`",
+        summary.name, summary.name
+    ));
+    for (i, line) in lines.iter().enumerate() {
+        let prefix = if i + 1 == lines.len() {
+            &config.tree_last
+        } else {
+            &config.tree_branch
+        };
+        result.push_str(&format!("\n{}{}", tree(prefix), line));
+    }
+    result
+}
+
+/// A module summary on one line
+pub fn format_summary_compact(summary: &ModuleSummary) -> String {
+    format!("{} (module: {})", summary.name, counts(summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(exports: &[&str], more_exports: usize) -> ModuleSummary {
+        ModuleSummary {
+            kind: "module",
+            name: "prefect.flows".to_string(),
+            summary: Some("Flows.".to_string()),
+            functions: 3,
+            classes: 1,
+            constants: 0,
+            submodules: 2,
+            exports: exports.iter().map(|name| name.to_string()).collect(),
+            more_exports,
+        }
+    }
+
+    #[test]
+    fn test_counts_and_exports() {
+        let summary = summary(&["flow", "Flow"], 3);
+        assert_eq!(
+            counts(&summary),
+            "3 functions, 1 class, 0 constants, 2 submodules"
+        );
+        assert_eq!(exports_line(&summary), "flow, Flow, ... (+3)");
+    }
+
+    #[test]
+    fn test_binds_attribute() {
+        let source = "from . import flows\nfrom .worker import worker\nfrom pkg import engine\n\
+                      from other.tasks import tasks\nclass Runner: ...\n";
+        let info = ModuleInfo::from_source(source, "pkg").unwrap();
+        assert!(binds_attribute(&info, "pkg", "worker"));
+        assert!(binds_attribute(&info, "pkg", "tasks"));
+        assert!(binds_attribute(&info, "pkg", "Runner"));
+        assert!(!binds_attribute(&info, "pkg", "flows"));
+        assert!(!binds_attribute(&info, "pkg", "engine"));
+        assert!(!binds_attribute(&info, "pkg", "missing"));
+    }
+
+    #[test]
+    fn test_json_has_module_kind() {
+        let value = serde_json::to_value(summary(&[], 0)).unwrap();
+        assert_eq!(value["kind"], "module");
+        assert_eq!(value["name"], "prefect.flows");
+    }
+}
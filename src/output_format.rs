@@ -1,6 +1,9 @@
+use crate::class_explorer::ClassView;
 use crate::module_info::FunctionSignature;
+use crate::module_summary::ModuleSummary;
 use pyo3::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 /// Trait for different output format visitors
 pub trait OutputFormatter {
@@ -12,10 +15,32 @@ pub trait OutputFormatter {
 
     /// Format a signature not available message
     fn format_signature_not_available(&self, object_name: &str) -> String;
+
+    /// Format the `module:name` candidates of a name several modules define
+    fn format_ambiguous_signature(&self, object_name: &str, candidates: &[String]) -> String;
+
+    /// Format a class with its grouped methods
+    fn format_class(&self, class: &ClassView) -> String;
+
+    /// Format the summary shown when a signature path names a module
+    fn format_module_summary(&self, summary: &ModuleSummary) -> String;
+
+    /// Format a definition's source, shown below its signature
+    fn format_source(&self, source: &str) -> String {
+        format!("```python\n{}\n```", source)
+    }
+
+    /// Whether trees should carry distribution metadata under a "dist" key
+    fn include_dist_metadata(&self) -> bool {
+        false
+    }
 }
 
 /// Pretty print formatter (current default behavior)
-pub struct PrettyPrintFormatter;
+pub struct PrettyPrintFormatter {
+    /// Wrap width for long parameters (None disables wrapping)
+    pub width: Option<usize>,
+}
 
 impl OutputFormatter for PrettyPrintFormatter {
     fn format_tree(&self, py: Python, tree: &PyObject, module_name: &str) -> PyResult<String> {
@@ -25,7 +50,7 @@ impl OutputFormatter for PrettyPrintFormatter {
 
     fn format_signature(&self, signature: &FunctionSignature) -> String {
         // Use existing signature formatter
-        crate::signature::format_signature_display(signature)
+        crate::signature::format_signature_display_with_width(signature, self.width)
     }
 
     fn format_signature_not_available(&self, object_name: &str) -> String {
@@ -40,6 +65,18 @@ impl OutputFormatter for PrettyPrintFormatter {
             crate::config::colorize(object_name, &config.color_scheme.signature_color, config)
         )
     }
+
+    fn format_ambiguous_signature(&self, object_name: &str, candidates: &[String]) -> String {
+        crate::signature::format_candidates_display(object_name, candidates)
+    }
+
+    fn format_class(&self, class: &ClassView) -> String {
+        crate::class_explorer::format_class_display(class)
+    }
+
+    fn format_module_summary(&self, summary: &ModuleSummary) -> String {
+        crate::module_summary::format_summary_display(summary)
+    }
 }
 
 /// JSON formatter for machine-readable output
@@ -47,15 +84,20 @@ pub struct JsonFormatter;
 
 impl OutputFormatter for JsonFormatter {
     fn format_tree(&self, py: Python, tree: &PyObject, module_name: &str) -> PyResult<String> {
-        // Convert PyObject tree to a serializable structure
-        let mut result = HashMap::new();
+        // Convert PyObject tree to a serializable structure (ordered keys keep output stable)
+        let mut result = BTreeMap::new();
         result.insert(
             "module".to_string(),
             serde_json::Value::String(module_name.to_string()),
         );
 
         // Convert the tree structure to JSON
-        if let Ok(tree_value) = pyobject_to_json_value(py, tree) {
+        if let Ok(mut tree_value) = pyobject_to_json_value(py, tree) {
+            locate_names(&mut tree_value);
+            // Distribution metadata describes the whole result, not the root module
+            if let Some(dist) = tree_value.as_object_mut().and_then(|t| t.remove("dist")) {
+                result.insert("dist".to_string(), dist);
+            }
             result.insert("tree".to_string(), tree_value);
         }
 
@@ -76,12 +118,148 @@ impl OutputFormatter for JsonFormatter {
         });
         serde_json::to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
     }
+
+    fn format_ambiguous_signature(&self, object_name: &str, candidates: &[String]) -> String {
+        let result = serde_json::json!({
+            "name": object_name,
+            "available": false,
+            "reason": "ambiguous: defined in several modules",
+            "candidates": candidates,
+        });
+        serde_json::to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    fn format_class(&self, class: &ClassView) -> String {
+        serde_json::to_string_pretty(class).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    fn format_module_summary(&self, summary: &ModuleSummary) -> String {
+        serde_json::to_string_pretty(summary).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    fn include_dist_metadata(&self) -> bool {
+        true
+    }
+}
+
+impl JsonFormatter {
+    /// A signature with every definition of its name, when several modules define it,
+    /// and the definition's source (under "source_code": "source" says where it was read)
+    pub fn format_signature_with_extras(
+        &self,
+        signature: &FunctionSignature,
+        candidates: &[String],
+        source_code: Option<&str>,
+    ) -> String {
+        let mut result = serde_json::to_value(signature).unwrap_or_default();
+        if let Some(result) = result.as_object_mut() {
+            if !candidates.is_empty() {
+                result.insert("candidates".to_string(), candidates.into());
+            }
+            if let Some(source_code) = source_code {
+                result.insert("source_code".to_string(), source_code.into());
+            }
+        }
+        serde_json::to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+/// Compact formatter: one-line signatures, optionally wrapped at a max width
+pub struct CompactFormatter {
+    pub max_width: Option<usize>,
+}
+
+impl OutputFormatter for CompactFormatter {
+    fn format_tree(&self, py: Python, tree: &PyObject, module_name: &str) -> PyResult<String> {
+        // Trees have no one-line form; reuse the pretty tree
+        crate::tree_formatter::format_tree_display(py, tree, module_name)
+    }
+
+    fn format_signature(&self, signature: &FunctionSignature) -> String {
+        crate::signature::format_signature_compact(signature, self.max_width)
+    }
+
+    fn format_signature_not_available(&self, object_name: &str) -> String {
+        format!("{} (signature not available)", object_name)
+    }
+
+    fn format_ambiguous_signature(&self, object_name: &str, candidates: &[String]) -> String {
+        format!("{} (ambiguous: {})", object_name, candidates.join(", "))
+    }
+
+    fn format_class(&self, class: &ClassView) -> String {
+        crate::class_explorer::format_class_display(class)
+    }
+
+    fn format_module_summary(&self, summary: &ModuleSummary) -> String {
+        crate::module_summary::format_summary_compact(summary)
+    }
+}
+
+/// Turn each module's function, class and constant names into `{"name", "line"}` objects
+/// using the api's "lines" (which is dropped); names without a known line get no "line".
+/// Classes expanded with `class_depth` also carry their "methods" (and "more") from the api's
+/// "class_methods", which is dropped too.
+fn locate_names(node: &mut serde_json::Value) {
+    if let Some(api) = node.get_mut("api").and_then(|api| api.as_object_mut()) {
+        let lines = api.remove("lines").unwrap_or_default();
+        let mut class_methods = api.remove("class_methods").unwrap_or_default();
+        for key in ["functions", "classes", "constants"] {
+            let Some(serde_json::Value::Array(names)) = api.get_mut(key) else {
+                continue;
+            };
+            for name in names.iter_mut() {
+                let mut entry = serde_json::Map::new();
+                if let Some(line) = name.as_str().and_then(|name| lines.get(name)) {
+                    entry.insert("line".to_string(), line.clone());
+                }
+                if key == "classes" {
+                    let methods = name.as_str().and_then(|name| class_methods.get_mut(name));
+                    if let Some(methods) = methods {
+                        nest_methods(&mut entry, methods.take());
+                    }
+                }
+                entry.insert("name".to_string(), name.take());
+                *name = serde_json::Value::Object(entry);
+            }
+        }
+    }
+    if let Some(submodules) = node.get_mut("submodules").and_then(|s| s.as_object_mut()) {
+        for submodule in submodules.values_mut() {
+            locate_names(submodule);
+        }
+    }
+}
+
+/// Add a class's `class_methods` entry to its JSON object: "methods" as `{"name"}` objects,
+/// each with its "signature" when the tree was explored with signatures, and "more"
+fn nest_methods(class: &mut serde_json::Map<String, serde_json::Value>, entry: serde_json::Value) {
+    let signatures = entry.get("signatures");
+    let methods: Vec<serde_json::Value> = entry["methods"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|method| {
+            let mut object = serde_json::Map::new();
+            let signature = method.as_str().and_then(|method| signatures?.get(method));
+            if let Some(signature) = signature {
+                object.insert("signature".to_string(), signature.clone());
+            }
+            object.insert("name".to_string(), method.clone());
+            serde_json::Value::Object(object)
+        })
+        .collect();
+    class.insert("methods".to_string(), methods.into());
+    if let Some(more) = entry.get("more") {
+        class.insert("more".to_string(), more.clone());
+    }
 }
 
 /// Convert PyObject to serde_json::Value
-fn pyobject_to_json_value(py: Python, obj: &PyObject) -> PyResult<serde_json::Value> {
+pub fn pyobject_to_json_value(py: Python, obj: &PyObject) -> PyResult<serde_json::Value> {
     // Try to extract as different Python types
     if let Ok(dict) = obj.extract::<HashMap<String, PyObject>>(py) {
+        // serde_json::Map is sorted by key, so dict iteration order doesn't leak into output
         let mut map = serde_json::Map::new();
         for (key, value) in dict {
             if let Ok(json_value) = pyobject_to_json_value(py, &value) {
@@ -112,10 +290,125 @@ fn pyobject_to_json_value(py: Python, obj: &PyObject) -> PyResult<serde_json::Va
     }
 }
 
+/// Formats accepted by `create_formatter`
+pub const FORMATS: &[&str] = &["pretty", "json", "compact", "html"];
+
 /// Factory function to create formatter based on format string
-pub fn create_formatter(format: &str) -> Box<dyn OutputFormatter> {
-    match format.to_lowercase().as_str() {
+pub fn create_formatter(format: &str) -> PyResult<Box<dyn OutputFormatter>> {
+    create_formatter_with_width(format, None)
+}
+
+/// Create a formatter with an explicit wrap width
+/// The pretty format falls back to the terminal width; compact only wraps when given one
+pub fn create_formatter_with_width(
+    format: &str,
+    width: Option<usize>,
+) -> PyResult<Box<dyn OutputFormatter>> {
+    Ok(match format.to_lowercase().as_str() {
+        "pretty" => Box::new(PrettyPrintFormatter {
+            width: width.or_else(crate::config::terminal_width),
+        }),
         "json" => Box::new(JsonFormatter),
-        _ => Box::new(PrettyPrintFormatter),
+        "html" => Box::new(crate::html_formatter::HtmlFormatter),
+        "compact" => Box::new(CompactFormatter { max_width: width }),
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown format '{}': expected one of {}",
+                format,
+                FORMATS.join(", ")
+            )))
+        }
+    })
+}
+
+/// Write JSON output to a file atomically (a temp file next to it, then rename)
+/// so a consumer never reads a partial result.
+pub fn write_json_file(path: &str, json: &str) -> PyResult<()> {
+    let target = Path::new(path);
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = target.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let written =
+        std::fs::write(&tmp, format!("{}\n", json)).and_then(|()| std::fs::rename(&tmp, target));
+    written.map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        PyErr::new::<pyo3::exceptions::PyOSError, _>(format!(
+            "Failed to write JSON output to '{}': {}",
+            path, e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_json_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tree.json");
+        let path = path.to_str().unwrap();
+        write_json_file(path, "{\"a\": 1}").unwrap();
+        write_json_file(path, "{\"a\": 2}").unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "{\"a\": 2}\n");
+        // Only the target is left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let missing = dir.path().join("missing").join("tree.json");
+        assert!(write_json_file(missing.to_str().unwrap(), "{}").is_err());
+    }
+
+    #[test]
+    fn test_locate_names() {
+        let mut tree = serde_json::json!({
+            "api": {"functions": ["load", "dump"], "classes": [], "lines": {"load": 3}},
+            "submodules": {"codec": {"api": {"constants": ["LIMIT"], "lines": {"LIMIT": 1}}}},
+        });
+        locate_names(&mut tree);
+        assert_eq!(
+            tree["api"]["functions"],
+            serde_json::json!([{"name": "load", "line": 3}, {"name": "dump"}])
+        );
+        assert!(tree["api"].get("lines").is_none());
+        assert_eq!(
+            tree["submodules"]["codec"]["api"]["constants"][0]["line"],
+            1
+        );
+    }
+
+    #[test]
+    fn test_locate_names_nests_class_methods() {
+        let mut tree = serde_json::json!({
+            "api": {
+                "classes": ["Client", "Plain"],
+                "lines": {"Client": 4},
+                "class_methods": {
+                    "Client": {
+                        "methods": ["get", "close"],
+                        "more": 2,
+                        "signatures": {"get": "get(self, url: str) -> Response"},
+                    },
+                },
+            },
+        });
+        locate_names(&mut tree);
+        assert_eq!(
+            tree["api"]["classes"],
+            serde_json::json!([
+                {
+                    "name": "Client",
+                    "line": 4,
+                    "methods": [
+                        {"name": "get", "signature": "get(self, url: str) -> Response"},
+                        {"name": "close"},
+                    ],
+                    "more": 2,
+                },
+                {"name": "Plain"},
+            ])
+        );
+        assert!(tree["api"].get("class_methods").is_none());
     }
 }
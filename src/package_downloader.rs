@@ -1,28 +1,111 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::{Read, Seek};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use pyo3::prelude::*;
+use sha2::{Digest, Sha256};
 use tempfile::TempDir;
 
+use crate::dist_metadata::{extracted_scripts, packages_from_files, DistMetadata, Scripts};
+use crate::http_cache::get_metadata;
+use crate::logging::info_log;
+use crate::requirement::{parse_requirement, select_version};
+use crate::simple_index::{
+    configured_index_url, fragment_sha256, parse_html_page, parse_json_page, project_url,
+    ReleaseFile, ACCEPT as SIMPLE_ACCEPT, PYPI_SIMPLE_URL,
+};
+use crate::verbosity::Verbosity;
+
+/// How distributions are fetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadBackend {
+    /// PyPI's JSON API or a simple index, over HTTP
+    Builtin,
+    /// `uv pip install` of a wheel, with uv's cache, indexes, and credentials
+    Uv,
+}
+
+impl DownloadBackend {
+    /// PRETTY_MOD_DOWNLOADER=uv opts into uv; anything else uses the built-in downloader
+    fn from_env() -> Self {
+        match std::env::var("PRETTY_MOD_DOWNLOADER") {
+            Ok(val) if val.trim().eq_ignore_ascii_case("uv") => Self::Uv,
+            _ => Self::Builtin,
+        }
+    }
+}
+
 /// Downloads and extracts a Python package from PyPI
 #[derive(Debug)]
 pub struct PackageDownloader {
     package_name: String,
     version_spec: Option<String>,
+    specifier: Option<String>,
+    url: Option<String>,
+    metadata: Option<DistMetadata>,
+    scripts: Scripts,
+    packages: Vec<String>, // Importable packages the wheel installs, from its RECORD
+    verbosity: Verbosity,
+    backend: DownloadBackend,
+    sdist_fallback: bool,
+    sources_of: Option<String>, // Top-level module whose wheel has only sources extracted
+    expected_module: Option<String>, // Module a guessed distribution must contain
+    package_path: Option<PathBuf>,
     temp_dir: Option<TempDir>,
 }
 
 impl PackageDownloader {
+    /// Create a downloader from "package", "package@1.2.3", or a PEP 508
+    /// requirement such as "package[extra]>=1.0; python_version >= '3.9'"
     pub fn new(package_name: String) -> Self {
         // Parse version spec if present
         let (name, version) = crate::utils::parse_package_spec(&package_name);
+        let requirement = parse_requirement(name);
         Self {
-            package_name: name.to_string(),
+            package_name: requirement.name.to_string(),
             version_spec: version.map(|v| v.to_string()),
+            specifier: requirement.specifier.map(|s| s.to_string()),
+            url: requirement.url.map(|u| u.to_string()),
+            metadata: None,
+            scripts: Scripts::new(),
+            packages: Vec::new(),
+            verbosity: Verbosity::default(),
+            backend: DownloadBackend::from_env(),
+            // PRETTY_MOD_NO_SDIST skips the extra download for bandwidth-sensitive use
+            sdist_fallback: std::env::var("PRETTY_MOD_NO_SDIST").is_err(),
+            sources_of: None,
+            expected_module: None,
+            package_path: None,
             temp_dir: None,
         }
     }
 
+    /// Set how much progress detail is written to stderr
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Extract only Python sources (and the dist-info) from wheels containing
+    /// `module_path`'s top-level package, skipping bundled data; every top-level package's
+    /// sources are kept, as the module may import private ones (e.g., `_pkg_impl`).
+    /// Unrecognized layouts extract fully.
+    pub fn with_sources_only(mut self, module_path: &str) -> Self {
+        let top_level = module_path.split('.').next().unwrap_or(module_path);
+        self.sources_of = Some(top_level.to_string());
+        self
+    }
+
+    /// Only extract a wheel whose file list contains `module_path`, for distribution names
+    /// guessed from a module; anything else (sdists included) is rejected unextracted
+    pub fn with_expected_module(mut self, module_path: &str) -> Self {
+        self.expected_module = Some(module_path.to_string());
+        self
+    }
+
     /// Download and extract the package, returning the path to the extracted package
     pub fn download_and_extract(&mut self) -> PyResult<PathBuf> {
         // Create a temporary directory
@@ -33,115 +116,449 @@ impl PackageDownloader {
             ))
         })?;
 
-        // Query PyPI's simple API
-        let package_info = self.fetch_package_info()?;
+        // uv fetches and installs a wheel with its own cache, indexes, and credentials
+        let installed = if self.backend == DownloadBackend::Uv {
+            self.install_with_uv(temp_dir.path())
+                .map_err(|e| {
+                    self.verbosity.detail(&format!(
+                        "uv could not fetch {} ({}); falling back to the built-in downloader",
+                        self.package_name, e
+                    ))
+                })
+                .ok()
+        } else {
+            None
+        };
+        let (extracted_path, filename, sdist) = match installed {
+            Some(installed) => {
+                self.check_install(&installed)?;
+                (installed, self.requirement(), None)
+            }
+            None => {
+                self.verbosity.detail("download backend: built-in");
+                // Query PyPI's simple API
+                let package_info = self.fetch_package_info()?;
+
+                // Download the wheel or source distribution
+                info_log!(
+                    "Downloading {} from {}",
+                    self.package_name,
+                    package_info.url
+                );
+                self.verbosity
+                    .detail(&format!("download URL: {}", package_info.url));
+                let downloaded_path = self.download_package(&package_info, temp_dir.path())?;
+                if let Some(module_path) = &self.expected_module {
+                    if !wheel_provides(&downloaded_path, module_path) {
+                        return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(
+                            format!(
+                                "No module named '{}' in {}",
+                                module_path, package_info.filename
+                            ),
+                        ));
+                    }
+                }
 
-        // Download the wheel or source distribution
-        let downloaded_path = self.download_package(&package_info, temp_dir.path())?;
+                // Extract the package
+                let extracted_path =
+                    self.extract_package(&downloaded_path, temp_dir.path(), "extracted")?;
+                (extracted_path, package_info.filename, package_info.sdist)
+            }
+        };
 
-        // Extract the package
-        let extracted_path = self.extract_package(&downloaded_path, temp_dir.path())?;
+        self.metadata = DistMetadata::from_extracted(&extracted_path);
+        self.scripts = extracted_scripts(&extracted_path);
+        self.packages = extracted_packages(&extracted_path);
 
         // Find the actual package directory
         let package_path = self.find_package_root(&extracted_path)?;
+        self.verbosity.detail(&format!(
+            "extracted {} to {}",
+            filename,
+            package_path.display()
+        ));
+
+        // Wheels of compiled extensions often carry no Python sources; the sdist may
+        if let Some(sdist) = &sdist {
+            if self.sdist_fallback && lacks_python_sources(&package_path) {
+                self.merge_sdist_sources(sdist, temp_dir.path(), &package_path);
+            }
+        }
+        info_log!(
+            "Downloaded {} ({}) to {}",
+            self.package_name,
+            filename,
+            package_path.display()
+        );
 
         // Store temp_dir to keep it alive
         self.temp_dir = Some(temp_dir);
+        self.package_path = Some(package_path.clone());
 
         Ok(package_path)
     }
 
-    /// Query PyPI's JSON API for package info
-    fn fetch_package_info(&self) -> PyResult<PackageInfo> {
-        let clean_name = self.normalize_package_name(&self.package_name);
-        let url = format!("https://pypi.org/pypi/{}/json", clean_name);
-
-        let response = reqwest::blocking::get(&url).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                "Failed to fetch package info: {}",
-                e
-            ))
+    /// Install the distribution, without dependencies, into `dest_dir/installed` with uv
+    /// Only wheels are considered, so no sdist build backend runs, and uv picks one built
+    /// for this interpreter. A `--target` install has the layout of an extracted wheel.
+    fn install_with_uv(&self, dest_dir: &Path) -> PyResult<PathBuf> {
+        let target = dest_dir.join("installed");
+        let requirement = self.requirement();
+        self.verbosity
+            .detail(&format!("download backend: uv ({})", requirement));
+        let python = Python::with_gil(|py| -> PyResult<String> {
+            py.import("sys")?.getattr("executable")?.extract()
         })?;
-
-        if !response.status().is_success() {
-            return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(
-                format!("Package '{}' not found on PyPI", self.package_name),
+        let output = std::process::Command::new("uv")
+            .args(["pip", "install", "--no-deps", "--quiet"])
+            .args(["--only-binary", ":all:", "--python", python.as_str()])
+            .arg("--target")
+            .arg(&target)
+            .arg(&requirement)
+            .output()
+            .map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("cannot run uv: {}", e))
+            })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.trim().lines().last().unwrap_or("uv failed");
+            return Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(
+                reason.to_string(),
             ));
         }
+        Ok(target)
+    }
 
-        let json: serde_json::Value = response.json().map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to parse JSON: {}", e))
-        })?;
+    /// Apply the wheel extraction's rules to a uv install: a guessed distribution must
+    /// contain the expected module, and with `sources_of` only sources are kept
+    fn check_install(&self, installed: &Path) -> PyResult<()> {
+        let files = relative_files(installed);
+        if let Some(module_path) = &self.expected_module {
+            if !names_provide(files.iter().map(String::as_str), module_path) {
+                return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(
+                    format!(
+                        "No module named '{}' in {}",
+                        module_path,
+                        self.requirement()
+                    ),
+                ));
+            }
+        }
+        let sources_only = self
+            .sources_of
+            .as_deref()
+            .is_some_and(|top_level| files.iter().any(|name| in_top_level(name, top_level)));
+        if sources_only {
+            for name in files.iter().filter(|name| !is_source_entry(name)) {
+                let _ = fs::remove_file(installed.join(name));
+            }
+        }
+        Ok(())
+    }
+
+    /// The requirement this downloader fetches, e.g. "toml==0.10.2" or "uvicorn>=0.30"
+    fn requirement(&self) -> String {
+        match (&self.url, &self.version_spec, &self.specifier) {
+            (Some(url), _, _) => format!("{} @ {}", self.package_name, url),
+            (_, Some(version), _) if version != "latest" => {
+                format!("{}=={}", self.package_name, version)
+            }
+            (_, _, Some(specifier)) => format!("{}{}", self.package_name, specifier),
+            _ => self.package_name.clone(),
+        }
+    }
+
+    /// Download the sdist and copy its `.py`/`.pyi` files into the extracted wheel
+    /// Failures are reported and ignored: the wheel alone is still usable.
+    fn merge_sdist_sources(&self, sdist: &PackageInfo, dest_dir: &Path, package_path: &Path) {
+        self.verbosity.detail(&format!(
+            "{} has only compiled modules; fetching sources from {}",
+            self.package_name, sdist.filename
+        ));
+        let merged = self
+            .download_package(sdist, dest_dir)
+            .and_then(|archive| self.extract_package(&archive, dest_dir, "sdist"))
+            .and_then(|sdist_root| {
+                let name = self.normalize_package_name(&self.package_name);
+                find_source_package(&sdist_root, &name).ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyFileNotFoundError, _>(format!(
+                        "no '{}' package in {}",
+                        name, sdist.filename
+                    ))
+                })
+            })
+            .and_then(|source_package| merge_sources(&source_package, package_path));
+        match merged {
+            Ok(count) => self
+                .verbosity
+                .detail(&format!("merged {} source files from {}", count, sdist.filename)),
+            Err(e) => self
+                .verbosity
+                .detail(&format!("could not use {}: {}", sdist.filename, e)),
+        }
+    }
+
+    /// Distribution metadata read from the downloaded artifact, if any
+    pub fn metadata(&self) -> Option<&DistMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Console and GUI scripts declared by the downloaded wheel
+    pub fn scripts(&self) -> &Scripts {
+        &self.scripts
+    }
+
+    /// Importable packages the downloaded wheel installs (empty for sdists)
+    /// e.g., ["azure.identity"] for azure-identity
+    pub fn packages(&self) -> &[String] {
+        &self.packages
+    }
+
+    /// The distribution name, without version or extras
+    pub fn package_name(&self) -> &str {
+        &self.package_name
+    }
+
+    /// Where the package was extracted, once `download_and_extract` has succeeded
+    pub fn package_path(&self) -> Option<&Path> {
+        self.package_path.as_deref()
+    }
+
+    /// Resolve the requested version to a downloadable file
+    fn fetch_package_info(&self) -> PyResult<PackageInfo> {
+        // Direct references skip the index entirely
+        if let Some(url) = &self.url {
+            let filename = url
+                .split('#')
+                .next()
+                .unwrap_or(url)
+                .rsplit('/')
+                .next()
+                .unwrap_or("package.whl")
+                .to_string();
+            return Ok(PackageInfo {
+                url: url.clone(),
+                filename,
+                sha256: fragment_sha256(url),
+                sdist: None,
+            });
+        }
+
+        let index = self.fetch_release_index()?;
 
         // Determine which version to download
         let target_version = match &self.version_spec {
-            Some(spec) if spec == "latest" => {
-                // Use the latest version
-                json["info"]["version"].as_str().ok_or_else(|| {
-                    PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing version info")
-                })?
-            }
+            Some(spec) if spec == "latest" => index.latest()?,
             Some(spec) => {
                 // Check if the specific version exists
-                if json["releases"][spec].is_null() {
+                if !index.releases.contains_key(spec) {
                     return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
                         format!("Version '{}' not found for package '{}'", spec, self.package_name),
                     ));
                 }
-                spec
+                spec.clone()
             }
-            None => {
+            None => match &self.specifier {
+                Some(specifier) => {
+                    // Pick the newest release satisfying the specifier set
+                    let candidates = index
+                        .releases
+                        .iter()
+                        .filter(|(_, files)| !files.is_empty())
+                        .map(|(version, _)| version.as_str());
+                    select_version(candidates, specifier)
+                        .ok_or_else(|| {
+                            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                                "No version of '{}' satisfies '{}'",
+                                self.package_name, specifier
+                            ))
+                        })?
+                        .to_string()
+                }
                 // Default to latest version
-                json["info"]["version"].as_str().ok_or_else(|| {
-                    PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing version info")
-                })?
-            }
+                None => index.latest()?,
+            },
         };
 
+        self.verbosity.detail(&format!(
+            "resolved {} to version {}",
+            self.package_name, target_version
+        ));
+
         // Find a wheel or source distribution for the target version
-        let releases = json["releases"][target_version].as_array().ok_or_else(|| {
+        let releases = index.releases.get(&target_version).ok_or_else(|| {
             PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing release info")
         })?;
 
-        // Prefer wheels over source distributions
-        let wheel_url = releases
+        // Source distribution for this version, used directly or to fill in a compiled wheel
+        let sdist = releases
             .iter()
-            .find(|r| r["filename"].as_str().unwrap_or("").ends_with(".whl"))
-            .and_then(|r| r["url"].as_str());
+            .find(|r| r.filename.ends_with(".tar.gz") || r.filename.ends_with(".zip"))
+            .map(PackageInfo::from_release_file);
 
-        let (url, filename) = if let Some(wheel_url) = wheel_url {
-            let filename = wheel_url.split('/').last().unwrap_or("package.whl");
-            (wheel_url.to_string(), filename.to_string())
-        } else {
-            // Fall back to source distribution
-            let sdist = releases
-                .iter()
-                .find(|r| {
-                    let filename = r["filename"].as_str().unwrap_or("");
-                    filename.ends_with(".tar.gz") || filename.ends_with(".zip")
-                })
-                .ok_or_else(|| {
-                    PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                        "No suitable distribution found",
-                    )
-                })?;
+        // Prefer wheels over source distributions
+        if let Some(wheel) = releases.iter().find(|r| r.filename.ends_with(".whl")) {
+            return Ok(PackageInfo {
+                sdist: sdist.map(Box::new),
+                ..PackageInfo::from_release_file(wheel)
+            });
+        }
 
-            let url = sdist["url"]
-                .as_str()
-                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing URL"))?;
-            let filename = sdist["filename"].as_str().ok_or_else(|| {
-                PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing filename")
-            })?;
+        // Fall back to source distribution
+        sdist.ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("No suitable distribution found")
+        })
+    }
+
+    /// List the package's releases from a configured simple index, or PyPI's JSON API
+    /// falling back to PyPI's simple index when the JSON API is unavailable
+    fn fetch_release_index(&self) -> PyResult<ReleaseIndex> {
+        if let Some(index_url) = configured_index_url() {
+            return self.fetch_simple_index(&index_url);
+        }
+        match self.fetch_json_index()? {
+            Some(index) => Ok(index),
+            None => self.fetch_simple_index(PYPI_SIMPLE_URL),
+        }
+    }
+
+    /// Query PyPI's JSON API (None when the API itself is unavailable)
+    fn fetch_json_index(&self) -> PyResult<Option<ReleaseIndex>> {
+        let clean_name = self.normalize_package_name(&self.package_name);
+        let url = format!("https://pypi.org/pypi/{}/json", clean_name);
+
+        let unavailable = |reason: String| {
+            self.verbosity.detail(&format!(
+                "JSON API unavailable ({}); trying the simple index",
+                reason
+            ));
+            Ok(None)
+        };
+
+        let response = match get_metadata(&url, None) {
+            Ok(response) => response,
+            Err(e) => return unavailable(e.to_string()),
+        };
+
+        if response.status == reqwest::StatusCode::NOT_FOUND {
+            return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(
+                format!("Package '{}' not found on PyPI", self.package_name),
+            ));
+        }
+        if !response.status.is_success() {
+            return unavailable(format!("HTTP {}", response.status));
+        }
+
+        let json: serde_json::Value = match serde_json::from_str(&response.body) {
+            Ok(json) => json,
+            Err(e) => return unavailable(e.to_string()),
+        };
+
+        let releases = json["releases"]
+            .as_object()
+            .map(|releases| {
+                releases
+                    .iter()
+                    .map(|(version, files)| {
+                        let files = files
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|file| {
+                                Some(ReleaseFile {
+                                    filename: file["filename"].as_str()?.to_string(),
+                                    url: file["url"].as_str()?.to_string(),
+                                    sha256: file["digests"]["sha256"].as_str().map(str::to_string),
+                                })
+                            })
+                            .collect();
+                        (version.clone(), files)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Some(ReleaseIndex {
+            latest: json["info"]["version"].as_str().map(str::to_string),
+            releases,
+        }))
+    }
+
+    /// Query a PEP 503/691 simple index for the package's files
+    fn fetch_simple_index(&self, index_url: &str) -> PyResult<ReleaseIndex> {
+        let url = project_url(index_url, &self.package_name);
+        self.verbosity.detail(&format!("querying simple index {}", url));
+
+        let response = get_metadata(&url, Some(SIMPLE_ACCEPT)).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to fetch package info: {}",
+                e
+            ))
+        })?;
+
+        if response.status == reqwest::StatusCode::NOT_FOUND {
+            return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(
+                format!("Package '{}' not found on {}", self.package_name, index_url),
+            ));
+        }
+        if !response.status.is_success() {
+            return Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                "Failed to fetch package info: HTTP {}",
+                response.status
+            )));
+        }
 
-            (url.to_string(), filename.to_string())
+        let is_json = response
+            .content_type
+            .as_deref()
+            .is_some_and(|value| value.contains("json"));
+        let releases = if is_json {
+            parse_json_page(&response.body, &response.url).ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid simple index response")
+            })?
+        } else {
+            parse_html_page(&response.body, &response.url)
         };
 
-        Ok(PackageInfo { url, filename })
+        Ok(ReleaseIndex {
+            latest: None,
+            releases,
+        })
     }
 
     /// Download the package file
     fn download_package(&self, info: &PackageInfo, dest_dir: &Path) -> PyResult<PathBuf> {
-        let response = reqwest::blocking::get(&info.url).map_err(|e| {
+        let filename = archive_file_name(&info.filename).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid archive filename '{}'",
+                info.filename
+            ))
+        })?;
+
+        // Local direct references are copied rather than fetched
+        if let Some(local_path) = info.url.strip_prefix("file://") {
+            let local_path = local_path.split('#').next().unwrap_or(local_path);
+            let dest_path = dest_dir.join(filename);
+            fs::copy(local_path, &dest_path).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                    "Failed to copy {}: {}",
+                    local_path, e
+                ))
+            })?;
+            if info.sha256.is_some() {
+                let content = fs::read(&dest_path).map_err(|e| {
+                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+                        "Failed to read {}: {}",
+                        dest_path.display(),
+                        e
+                    ))
+                })?;
+                verify_sha256(info, &content)?;
+            }
+            return Ok(dest_path);
+        }
+
+        let response = crate::http_client::get(&info.url).send().map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
                 "Failed to download package: {}",
                 e
@@ -158,8 +575,9 @@ impl PackageDownloader {
         let content = response.bytes().map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to read response: {}", e))
         })?;
+        verify_sha256(info, &content)?;
 
-        let dest_path = dest_dir.join(&info.filename);
+        let dest_path = dest_dir.join(filename);
         fs::write(&dest_path, content).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to write file: {}", e))
         })?;
@@ -168,8 +586,13 @@ impl PackageDownloader {
     }
 
     /// Extract the downloaded package
-    fn extract_package(&self, archive_path: &Path, dest_dir: &Path) -> PyResult<PathBuf> {
-        let extract_dir = dest_dir.join("extracted");
+    fn extract_package(
+        &self,
+        archive_path: &Path,
+        dest_dir: &Path,
+        subdir: &str,
+    ) -> PyResult<PathBuf> {
+        let extract_dir = dest_dir.join(subdir);
         fs::create_dir_all(&extract_dir).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
                 "Failed to create extract dir: {}",
@@ -177,58 +600,12 @@ impl PackageDownloader {
             ))
         })?;
 
-        if archive_path.extension().map(|s| s.to_str()) == Some(Some("whl")) {
+        let skipped = if archive_path.extension().map(|s| s.to_str()) == Some(Some("whl")) {
             // Extract wheel using zip
             let file = fs::File::open(archive_path).map_err(|e| {
                 PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to open wheel: {}", e))
             })?;
-
-            let mut archive = zip::ZipArchive::new(file).map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to read wheel: {}", e))
-            })?;
-
-            for i in 0..archive.len() {
-                let mut file = archive.by_index(i).map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                        "Failed to read zip entry: {}",
-                        e
-                    ))
-                })?;
-
-                let path = extract_dir.join(file.name());
-
-                if file.is_dir() {
-                    fs::create_dir_all(&path).map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to create dir: {}",
-                            e
-                        ))
-                    })?;
-                } else {
-                    if let Some(parent) = path.parent() {
-                        fs::create_dir_all(parent).map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                                "Failed to create parent dir: {}",
-                                e
-                            ))
-                        })?;
-                    }
-
-                    let mut outfile = fs::File::create(&path).map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to create file: {}",
-                            e
-                        ))
-                    })?;
-
-                    std::io::copy(&mut file, &mut outfile).map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                            "Failed to extract file: {}",
-                            e
-                        ))
-                    })?;
-                }
-            }
+            unpack_zip(file, &extract_dir, self.sources_of.as_deref())?
         } else if archive_path.to_str().unwrap_or("").ends_with(".tar.gz") {
             // Extract tar.gz
             let file = fs::File::open(archive_path).map_err(|e| {
@@ -237,22 +614,29 @@ impl PackageDownloader {
                     e
                 ))
             })?;
-
-            let gz = flate2::read::GzDecoder::new(file);
-            let mut archive = tar::Archive::new(gz);
-
-            archive.unpack(&extract_dir).map_err(|e| {
+            unpack_tar(flate2::read::GzDecoder::new(file), &extract_dir)?
+        } else if archive_path.extension().map(|s| s.to_str()) == Some(Some("zip")) {
+            // Extract a zip source distribution in full
+            let file = fs::File::open(archive_path).map_err(|e| {
                 PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
-                    "Failed to extract archive: {}",
+                    "Failed to open archive: {}",
                     e
                 ))
             })?;
+            unpack_zip(file, &extract_dir, None)?
         } else {
             return Err(PyErr::new::<pyo3::exceptions::PyNotImplementedError, _>(
                 "Unsupported archive format",
             ));
-        }
+        };
 
+        if !skipped.is_empty() {
+            self.verbosity.detail(&format!(
+                "skipped unsafe entries in {}: {}",
+                archive_path.display(),
+                skipped.join(", ")
+            ));
+        }
         Ok(extract_dir)
     }
 
@@ -260,10 +644,22 @@ impl PackageDownloader {
     fn find_package_root(&self, extract_dir: &Path) -> PyResult<PathBuf> {
         let normalized_name = self.normalize_package_name(&self.package_name);
 
-        // First, check if the package is directly in the extract directory (common for wheels)
-        let direct_path = extract_dir.join(&normalized_name);
-        if direct_path.exists() && direct_path.is_dir() {
-            return Ok(direct_path);
+        // First, check if the package is directly in an install root (common for wheels)
+        let install_roots = wheel_install_roots(extract_dir);
+        for root in &install_roots {
+            let direct_path = root.join(&normalized_name);
+            if direct_path.exists() && direct_path.is_dir() {
+                return Ok(direct_path);
+            }
+        }
+
+        // Import names can differ from the distribution name; RECORD/top_level.txt list them
+        let top_levels = extracted_top_levels(extract_dir);
+        if let Some(data_root) = install_roots[1..]
+            .iter()
+            .find(|root| top_levels.iter().any(|name| provides_top_level(root, name)))
+        {
+            return Ok(data_root.clone());
         }
 
         // For source distributions, look for setup.py or pyproject.toml
@@ -299,21 +695,588 @@ impl PackageDownloader {
 
     /// Normalize package name (replace - with _, lowercase)
     fn normalize_package_name(&self, name: &str) -> String {
-        // Extract base name from version specifiers
-        let base_name = name
-            .split(&['>', '<', '=', '!', '['][..])
-            .next()
-            .unwrap_or(name)
-            .trim();
+        // Extract base name from extras and version specifiers
+        let base_name = parse_requirement(name).name;
 
         base_name.replace('-', "_").to_lowercase()
     }
 }
 
+/// Concurrent downloads unless `PRETTY_MOD_MAX_DOWNLOADS` says otherwise
+const DEFAULT_MAX_DOWNLOADS: usize = 4;
+
+/// How many packages `download_all` fetches at once
+pub fn max_concurrent_downloads() -> usize {
+    std::env::var("PRETTY_MOD_MAX_DOWNLOADS")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|&limit| limit > 0)
+        .unwrap_or(DEFAULT_MAX_DOWNLOADS)
+}
+
+/// Download several packages on a small pool of threads, at most `max_concurrency` at a time
+/// Results come back in input order, one per spec, so a failure affects only its own package.
+/// Progress goes through Python's stderr: call this with the GIL released (`py.allow_threads`).
+pub fn download_all(
+    specs: &[String],
+    verbosity: Verbosity,
+    max_concurrency: usize,
+) -> Vec<PyResult<PackageDownloader>> {
+    let total = specs.len();
+    let next = AtomicUsize::new(0);
+    let finished = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<PyResult<PackageDownloader>>>> =
+        Mutex::new((0..total).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..max_concurrency.clamp(1, total.max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(spec) = specs.get(index) else {
+                        break;
+                    };
+                    let mut downloader =
+                        PackageDownloader::new(spec.clone()).with_verbosity(verbosity);
+                    let result = downloader.download_and_extract().map(|_| downloader);
+                    let done = finished.fetch_add(1, Ordering::SeqCst) + 1;
+                    verbosity.detail(&format!(
+                        "[{}/{}] {} {}",
+                        done,
+                        total,
+                        if result.is_ok() { "downloaded" } else { "failed" },
+                        spec
+                    ));
+                    if let Ok(mut results) = results.lock() {
+                        results[index] = Some(result);
+                    }
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or_else(|| {
+                Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                    "download did not complete",
+                ))
+            })
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 struct PackageInfo {
     url: String,
     filename: String,
+    sha256: Option<String>,          // expected digest, when the index provides one
+    sdist: Option<Box<PackageInfo>>, // same-version sdist when a wheel was chosen
+}
+
+impl PackageInfo {
+    fn from_release_file(file: &ReleaseFile) -> Self {
+        Self {
+            url: file.url.clone(),
+            filename: file.filename.clone(),
+            sha256: file.sha256.clone(),
+            sdist: None,
+        }
+    }
+}
+
+/// Files per version, from the JSON API or a simple index
+struct ReleaseIndex {
+    latest: Option<String>, // the index's notion of latest, if it has one
+    releases: BTreeMap<String, Vec<ReleaseFile>>,
+}
+
+impl ReleaseIndex {
+    /// The latest version: as reported by the index, else the newest final release
+    fn latest(&self) -> PyResult<String> {
+        self.latest
+            .clone()
+            .or_else(|| {
+                let candidates = self
+                    .releases
+                    .iter()
+                    .filter(|(_, files)| !files.is_empty())
+                    .map(|(version, _)| version.as_str());
+                select_version(candidates, "").map(str::to_string)
+            })
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing version info"))
+    }
+}
+
+/// An archive entry's path relative to the extraction root, or None when it is
+/// absolute or climbs out with `..`
+fn safe_entry_path(name: &Path) -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+/// The final component of an index-supplied filename, so the download stays in its
+/// directory; None for names like "" or ".."
+fn archive_file_name(filename: &str) -> Option<&str> {
+    Path::new(filename).file_name()?.to_str()
+}
+
+/// The paths (relative to the root) a symlink at `entry` pointing at `target` walks
+/// through, ending where it leads; None when it leaves the root
+fn link_walk(entry: &Path, target: &Path) -> Option<Vec<PathBuf>> {
+    let mut current = entry.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut walk = Vec::new();
+    for component in target.components() {
+        match component {
+            Component::Normal(part) => current.push(part),
+            Component::CurDir => continue,
+            Component::ParentDir if current.pop() => {}
+            _ => return None,
+        }
+        walk.push(current.clone());
+    }
+    Some(walk)
+}
+
+/// A symlink extracted so far, with the paths its target walks through
+type ExtractedLink = (PathBuf, Vec<PathBuf>);
+
+/// Whether a symlink at `entry` pointing at `target` stays inside the root
+/// Checked lexically, so a link may neither pass through an extracted link nor sit where
+/// an extracted link passes through: chained, each could look inside while the chain
+/// leaves the root.
+fn link_stays_inside(entry: &Path, target: &Path, links: &[ExtractedLink]) -> bool {
+    let Some(walk) = link_walk(entry, target) else {
+        return false;
+    };
+    !links
+        .iter()
+        .any(|(link, link_walk)| passes_through(&walk, link) || passes_through(link_walk, entry))
+}
+
+/// Whether a hard link to `target` stays inside, not reaching it through an extracted link
+fn hard_link_stays_inside(target: &Path, links: &[ExtractedLink]) -> bool {
+    safe_entry_path(target).is_some_and(|target| {
+        !links
+            .iter()
+            .any(|(link, _)| target.ancestors().skip(1).any(|dir| dir == link))
+    })
+}
+
+/// Whether a link's walk passes through `path` before reaching its end
+fn passes_through(walk: &[PathBuf], path: &Path) -> bool {
+    walk.split_last()
+        .is_some_and(|(_, through)| through.iter().any(|step| step == path))
+}
+
+/// Schemes of a wheel's `<name>.data` directory that install into site-packages
+const DATA_LIB_SCHEMES: [&str; 2] = ["purelib", "platlib"];
+
+/// A wheel entry's path once installed, e.g. "pkg-1.0.data/purelib/pkg/x.py" -> "pkg/x.py"
+fn installed_entry_path(name: &str) -> &str {
+    let Some((first, rest)) = name.split_once('/') else {
+        return name;
+    };
+    if !first.ends_with(".data") {
+        return name;
+    }
+    DATA_LIB_SCHEMES
+        .iter()
+        .find_map(|scheme| rest.strip_prefix(scheme)?.strip_prefix('/'))
+        .unwrap_or(name)
+}
+
+/// Directories of an extracted wheel that install into site-packages: the root, then
+/// any `<name>.data/purelib` and `<name>.data/platlib`
+fn wheel_install_roots(extract_dir: &Path) -> Vec<PathBuf> {
+    let mut roots = vec![extract_dir.to_path_buf()];
+    let Ok(entries) = fs::read_dir(extract_dir) else {
+        return roots;
+    };
+    let mut data_dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.extension().is_some_and(|ext| ext == "data"))
+        .collect();
+    data_dirs.sort();
+    for data_dir in data_dirs {
+        roots.extend(
+            DATA_LIB_SCHEMES
+                .iter()
+                .map(|scheme| data_dir.join(scheme))
+                .filter(|root| root.is_dir()),
+        );
+    }
+    roots
+}
+
+/// The `.dist-info` directory of an extracted wheel
+fn extracted_dist_info(extract_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(extract_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.is_dir() && path.extension().is_some_and(|ext| ext == "dist-info"))
+}
+
+/// Importable packages an extracted wheel installs, from its RECORD
+fn extracted_packages(extract_dir: &Path) -> Vec<String> {
+    let Some(record) = extracted_dist_info(extract_dir)
+        .and_then(|dist_info| fs::read_to_string(dist_info.join("RECORD")).ok())
+    else {
+        return Vec::new();
+    };
+    packages_from_files(
+        record
+            .lines()
+            .map(|line| installed_entry_path(line.split(',').next().unwrap_or_default())),
+    )
+}
+
+/// Top-level import names of an extracted wheel, from top_level.txt or else RECORD
+fn extracted_top_levels(extract_dir: &Path) -> Vec<String> {
+    let Some(dist_info) = extracted_dist_info(extract_dir) else {
+        return Vec::new();
+    };
+    if let Ok(text) = fs::read_to_string(dist_info.join("top_level.txt")) {
+        let names: Vec<String> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        if !names.is_empty() {
+            return names;
+        }
+    }
+    let Ok(record) = fs::read_to_string(dist_info.join("RECORD")) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = Vec::new();
+    for line in record.lines() {
+        let entry = installed_entry_path(line.split(',').next().unwrap_or_default());
+        let first = entry.split('/').next().unwrap_or(entry);
+        let is_module = entry.contains('/')
+            || matches!(
+                Path::new(first).extension().and_then(|ext| ext.to_str()),
+                Some("py" | "pyi" | "so" | "pyd")
+            );
+        if first.ends_with(".dist-info") || first.ends_with(".data") || !is_module {
+            continue;
+        }
+        // "six.py" -> "six"; "_cffi_backend.cpython-312-x86_64-linux-gnu.so" -> "_cffi_backend"
+        let name = first.split('.').next().unwrap_or(first);
+        if !name.is_empty() && !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Whether an install root holds the top-level package or module `name`
+fn provides_top_level(root: &Path, name: &str) -> bool {
+    root.join(name).is_dir()
+        || root.join(format!("{}.py", name)).is_file()
+        || root.join(format!("{}.pyi", name)).is_file()
+}
+
+/// Whether a wheel entry belongs to the top-level module `top_level`
+/// e.g., "yaml/__init__.py", "six.py", or "_cffi_backend.cpython-312-x86_64-linux-gnu.so"
+/// Entries under `<name>.data/purelib` or `platlib` are matched by their installed path.
+fn in_top_level(name: &str, top_level: &str) -> bool {
+    let name = installed_entry_path(name);
+    let first = name.split('/').next().unwrap_or(name);
+    first.split('.').next() == Some(top_level)
+}
+
+/// Whether a wheel entry is needed to read the wheel's sources: Python files, stubs,
+/// and extension modules installed into site-packages, plus the dist-info metadata
+fn is_source_entry(name: &str) -> bool {
+    let first = name.split('/').next().unwrap_or(name);
+    if first.ends_with(".dist-info") {
+        return true;
+    }
+    // Other `<name>.data` schemes (scripts, headers, data) aren't importable
+    let installed = installed_entry_path(name);
+    let first = installed.split('/').next().unwrap_or(installed);
+    !first.ends_with(".data")
+        && (name.ends_with('/')
+            || name.ends_with("py.typed")
+            || matches!(
+                Path::new(name).extension().and_then(|ext| ext.to_str()),
+                Some("py" | "pyi" | "so" | "pyd")
+            ))
+}
+
+/// Whether a downloaded wheel's file list contains the module `module_path`, as a
+/// source file, stub, extension module, or package directory
+fn wheel_provides(archive_path: &Path, module_path: &str) -> bool {
+    if !archive_path.extension().is_some_and(|ext| ext == "whl") {
+        return false;
+    }
+    let Some(archive) = fs::File::open(archive_path)
+        .ok()
+        .and_then(|file| zip::ZipArchive::new(file).ok())
+    else {
+        return false;
+    };
+    names_provide(archive.file_names(), module_path)
+}
+
+/// Whether wheel entry names (or paths in an install) include the module `module_path`
+fn names_provide<'a>(names: impl Iterator<Item = &'a str>, module_path: &str) -> bool {
+    let path = module_path.replace('.', "/");
+    let package_prefix = format!("{}/", path);
+    let module_prefix = format!("{}.", path);
+    names.map(installed_entry_path).any(|name| {
+        name.starts_with(&package_prefix)
+            || name.strip_prefix(&module_prefix).is_some_and(|rest| {
+                let extension = rest.rsplit('.').next().unwrap_or(rest);
+                matches!(extension, "py" | "pyi" | "so" | "pyd")
+            })
+    })
+}
+
+/// Extract a wheel, returning the names of entries skipped as unsafe
+/// Absolute and `..` paths are skipped, as are symlinks (wheels have no use for them).
+/// With `sources_of`, only sources are extracted (see `is_source_entry`), unless the
+/// wheel doesn't contain that top-level module where expected.
+fn unpack_zip<R: Read + Seek>(
+    reader: R,
+    extract_dir: &Path,
+    sources_of: Option<&str>,
+) -> PyResult<Vec<String>> {
+    let io_error = |context: &str, e: &dyn std::fmt::Display| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}: {}", context, e))
+    };
+    let mut archive =
+        zip::ZipArchive::new(reader).map_err(|e| io_error("Failed to read zip archive", &e))?;
+
+    let sources_of = sources_of.filter(|top_level| {
+        archive
+            .file_names()
+            .any(|name| in_top_level(name, top_level))
+    });
+
+    let mut skipped = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| io_error("Failed to read zip entry", &e))?;
+        if sources_of.is_some() && !is_source_entry(file.name()) {
+            continue;
+        }
+        let is_symlink = file
+            .unix_mode()
+            .is_some_and(|mode| mode & 0o170000 == 0o120000);
+        let Some(relative) = safe_entry_path(Path::new(file.name())).filter(|_| !is_symlink) else {
+            skipped.push(file.name().to_string());
+            continue;
+        };
+        let path = extract_dir.join(relative);
+
+        if file.is_dir() {
+            fs::create_dir_all(&path).map_err(|e| io_error("Failed to create dir", &e))?;
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| io_error("Failed to create parent dir", &e))?;
+            }
+            let mut outfile =
+                fs::File::create(&path).map_err(|e| io_error("Failed to create file", &e))?;
+            std::io::copy(&mut file, &mut outfile)
+                .map_err(|e| io_error("Failed to extract file", &e))?;
+        }
+    }
+    Ok(skipped)
+}
+
+/// Extract a tarball, returning the names of entries skipped as unsafe
+/// Absolute and `..` paths are skipped, as are links pointing outside the root, alone
+/// or chained through other links; `unpack_in` additionally refuses to write through a
+/// symlinked directory.
+fn unpack_tar<R: Read>(reader: R, extract_dir: &Path) -> PyResult<Vec<String>> {
+    let io_error = |e: std::io::Error| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to extract archive: {}", e))
+    };
+    let mut archive = tar::Archive::new(reader);
+
+    let mut skipped = Vec::new();
+    let mut links: Vec<ExtractedLink> = Vec::new();
+    for entry in archive.entries().map_err(io_error)? {
+        let mut entry = entry.map_err(io_error)?;
+        let name = entry.path().map_err(io_error)?.into_owned();
+        let entry_type = entry.header().entry_type();
+        let relative = safe_entry_path(&name);
+        let target = entry.link_name().map_err(io_error)?;
+        let safe = match (&relative, &target) {
+            (None, _) => false,
+            (Some(relative), Some(target)) if entry_type.is_symlink() => {
+                link_stays_inside(relative, target, &links)
+            }
+            (Some(_), Some(target)) if entry_type.is_hard_link() => {
+                hard_link_stays_inside(target, &links)
+            }
+            (Some(_), _) => true,
+        };
+        if !safe || !entry.unpack_in(extract_dir).map_err(io_error)? {
+            skipped.push(name.display().to_string());
+            continue;
+        }
+        if let (Some(relative), Some(target)) = (relative, target) {
+            if entry_type.is_symlink() {
+                let walk = link_walk(&relative, &target).unwrap_or_default();
+                links.push((relative, walk));
+            }
+        }
+    }
+    Ok(skipped)
+}
+
+/// Check a downloaded file against the digest published by the index
+fn verify_sha256(info: &PackageInfo, content: &[u8]) -> PyResult<()> {
+    let Some(expected) = &info.sha256 else {
+        return Ok(());
+    };
+    let actual: String = Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(PyErr::new::<pyo3::exceptions::PyIOError, _>(format!(
+            "Hash mismatch for {}: expected sha256 {}, got {}",
+            info.filename, expected, actual
+        )));
+    }
+    Ok(())
+}
+
+/// The files under `dir` as `/`-separated relative paths, like archive entry names
+/// Symlinks are listed but not followed.
+fn relative_files(dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let Ok(entries) = fs::read_dir(dir.join(&relative)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = relative.join(entry.file_name());
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                pending.push(path);
+            } else {
+                let components: Vec<_> = path.iter().map(|part| part.to_string_lossy()).collect();
+                files.push(components.join("/"));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Whether a package has extension modules without stubs and no Python definitions
+/// e.g., an orjson wheel: a `.so` next to an `__init__.py` that only re-exports it
+fn lacks_python_sources(package_path: &Path) -> bool {
+    let mut unstubbed_extensions = 0;
+    let mut has_definitions = false;
+    let mut pending = vec![package_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.ends_with(".pyi") {
+                return false;
+            }
+            if file_name.ends_with(".so") || file_name.ends_with(".pyd") {
+                // "_core.cpython-312-x86_64-linux-gnu.so" pairs with "_core.pyi"
+                let stem = file_name.split('.').next().unwrap_or("");
+                if !dir.join(format!("{}.pyi", stem)).exists() {
+                    unstubbed_extensions += 1;
+                }
+            } else if file_name.ends_with(".py") {
+                let source = fs::read_to_string(&path).unwrap_or_default();
+                has_definitions |= source.lines().any(|line| {
+                    let line = line.trim_start();
+                    line.starts_with("def ")
+                        || line.starts_with("async def ")
+                        || line.starts_with("class ")
+                });
+            }
+        }
+    }
+    unstubbed_extensions > 0 && !has_definitions
+}
+
+/// Find the importable package inside an extracted sdist (e.g., "name-1.0/python/name")
+/// Searches breadth-first a few levels deep so the shallowest match wins.
+fn find_source_package(sdist_root: &Path, name: &str) -> Option<PathBuf> {
+    let mut level = vec![sdist_root.to_path_buf()];
+    for _ in 0..4 {
+        let mut next_level = Vec::new();
+        for dir in level {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut subdirs: Vec<PathBuf> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect();
+            subdirs.sort();
+            for path in subdirs {
+                let is_package = path.join("__init__.py").exists()
+                    || path.join("__init__.pyi").exists();
+                if is_package && path.file_name().is_some_and(|n| n == name) {
+                    return Some(path);
+                }
+                next_level.push(path);
+            }
+        }
+        level = next_level;
+    }
+    None
+}
+
+/// Copy `.py`/`.pyi` files from `source` into `dest`, keeping files already in `dest`
+/// Returns the number of files copied.
+fn merge_sources(source: &Path, dest: &Path) -> PyResult<usize> {
+    let io_err = |e: std::io::Error| {
+        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to merge sources: {}", e))
+    };
+    let mut copied = 0;
+    for entry in fs::read_dir(source).map_err(io_err)?.flatten() {
+        let path = entry.path();
+        let target = dest.join(entry.file_name());
+        if path.is_dir() {
+            copied += merge_sources(&path, &target)?;
+        } else if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("py" | "pyi")
+        ) && !target.exists()
+        {
+            fs::create_dir_all(dest).map_err(io_err)?;
+            fs::copy(&path, &target).map_err(io_err)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
 }
 
 /// Temporary directory path holder
@@ -338,3 +1301,453 @@ impl DownloadedPackage {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_new_accepts_pip_style_pins() {
+        let downloader = PackageDownloader::new("requests[socks]==2.31.0".to_string());
+        assert_eq!(downloader.package_name, "requests");
+        assert_eq!(downloader.version_spec.as_deref(), Some("2.31.0"));
+        assert_eq!(downloader.specifier, None);
+    }
+
+    #[test]
+    fn test_requirement_for_uv() {
+        let requirement = |spec: &str| PackageDownloader::new(spec.to_string()).requirement();
+        assert_eq!(requirement("toml"), "toml");
+        assert_eq!(requirement("toml@0.10.2"), "toml==0.10.2");
+        assert_eq!(requirement("toml@latest"), "toml");
+        assert_eq!(requirement("uvicorn[standard]>=0.30"), "uvicorn>=0.30");
+        assert_eq!(
+            requirement("pkg @ https://example.com/pkg-1.0-py3-none-any.whl"),
+            "pkg @ https://example.com/pkg-1.0-py3-none-any.whl"
+        );
+    }
+
+    #[test]
+    fn test_check_install() {
+        let dir = tempfile::tempdir().unwrap();
+        let installed = dir.path().join("installed");
+        write(&installed.join("heavy/__init__.py"), "");
+        write(&installed.join("heavy/data/table.csv"), "a,b\n");
+        write(&installed.join("heavy-1.0.dist-info/RECORD"), "");
+        write(&installed.join("bin/heavy"), "#!/usr/bin/env python\n");
+        assert_eq!(
+            relative_files(&installed),
+            vec![
+                "bin/heavy",
+                "heavy-1.0.dist-info/RECORD",
+                "heavy/__init__.py",
+                "heavy/data/table.csv"
+            ]
+        );
+
+        let downloader = PackageDownloader::new("heavy".to_string()).with_expected_module("light");
+        assert!(downloader.check_install(&installed).is_err());
+
+        let downloader = PackageDownloader::new("heavy".to_string())
+            .with_expected_module("heavy")
+            .with_sources_only("heavy");
+        downloader.check_install(&installed).unwrap();
+        assert!(installed.join("heavy/__init__.py").is_file());
+        assert!(installed.join("heavy-1.0.dist-info/RECORD").is_file());
+        assert!(!installed.join("heavy/data/table.csv").exists());
+        assert!(!installed.join("bin/heavy").exists());
+    }
+
+    #[test]
+    fn test_lacks_python_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("fastjson");
+        write(&package.join("__init__.py"), "from .fastjson import *\n");
+        write(&package.join("fastjson.cpython-312-x86_64-linux-gnu.so"), "");
+        assert!(lacks_python_sources(&package));
+
+        // An adjacent stub documents the extension
+        write(&package.join("fastjson.pyi"), "def loads(s: str) -> object: ...\n");
+        assert!(!lacks_python_sources(&package));
+    }
+
+    #[test]
+    fn test_pure_python_with_speedups_is_complete() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("yamlish");
+        write(&package.join("__init__.py"), "def load(stream):\n    pass\n");
+        write(&package.join("_speedups.cpython-312-x86_64-linux-gnu.so"), "");
+        assert!(!lacks_python_sources(&package));
+    }
+
+    #[test]
+    fn test_merge_sdist_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let sdist_root = dir.path().join("sdist");
+        let source = sdist_root.join("fastjson-1.0").join("pysrc").join("fastjson");
+        write(&source.join("__init__.pyi"), "def loads(s: str) -> object: ...\n");
+        write(&source.join("__init__.py"), "raise ImportError\n");
+        write(&source.join("sub").join("util.py"), "def helper(): ...\n");
+
+        let wheel = dir.path().join("extracted").join("fastjson");
+        write(&wheel.join("__init__.py"), "from .fastjson import *\n");
+
+        let found = find_source_package(&sdist_root, "fastjson").unwrap();
+        assert_eq!(found, source);
+        assert_eq!(merge_sources(&found, &wheel).unwrap(), 2);
+        // Files shipped in the wheel win
+        assert_eq!(
+            fs::read_to_string(wheel.join("__init__.py")).unwrap(),
+            "from .fastjson import *\n"
+        );
+        assert!(wheel.join("__init__.pyi").exists());
+        assert!(wheel.join("sub").join("util.py").exists());
+    }
+
+    #[test]
+    fn test_safe_entry_path() {
+        assert_eq!(
+            safe_entry_path(Path::new("./pkg/__init__.py")),
+            Some(PathBuf::from("pkg/__init__.py"))
+        );
+        assert_eq!(safe_entry_path(Path::new("../evil.py")), None);
+        assert_eq!(safe_entry_path(Path::new("pkg/../../evil.py")), None);
+        assert_eq!(safe_entry_path(Path::new("/etc/evil.py")), None);
+        assert!(link_stays_inside(
+            Path::new("pkg/link"),
+            Path::new("../other/file.py")
+        ));
+        assert!(!link_stays_inside(
+            Path::new("pkg/link"),
+            Path::new("../../outside")
+        ));
+        assert!(!link_stays_inside(
+            Path::new("link"),
+            Path::new("/etc/passwd")
+        ));
+    }
+
+    #[test]
+    fn test_archive_file_name() {
+        assert_eq!(
+            archive_file_name("toml-0.10.2.tar.gz"),
+            Some("toml-0.10.2.tar.gz")
+        );
+        assert_eq!(
+            archive_file_name("../../toml-0.10.2.tar.gz"),
+            Some("toml-0.10.2.tar.gz")
+        );
+        assert_eq!(archive_file_name("/tmp/evil.whl"), Some("evil.whl"));
+        assert_eq!(archive_file_name(".."), None);
+        assert_eq!(archive_file_name("files/.."), None);
+        assert_eq!(archive_file_name(""), None);
+    }
+
+    #[test]
+    fn test_unpack_zip_skips_traversal() {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for name in ["pkg/__init__.py", "../evil.py", "/abs.py"] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(b"x = 1\n").unwrap();
+        }
+        writer
+            .add_symlink("pkg/link.py", "../../outside.py", options)
+            .unwrap();
+        let archive = writer.finish().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("extracted");
+        let skipped = unpack_zip(archive, &root, None).unwrap();
+        assert_eq!(skipped, vec!["../evil.py", "/abs.py", "pkg/link.py"]);
+        assert!(root.join("pkg").join("__init__.py").is_file());
+        assert!(!dir.path().join("evil.py").exists());
+        assert!(!root.join("pkg").join("link.py").exists());
+    }
+
+    fn wheel(names: &[&str]) -> std::io::Cursor<Vec<u8>> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for name in names {
+            writer.start_file(*name, options).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_wheel_provides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("google_cloud_storage-2.0-py3-none-any.whl");
+        let names = [
+            "google/cloud/storage/__init__.py",
+            "google/cloud/_speedups.cpython-312-x86_64-linux-gnu.so",
+            "odd-1.0.data/purelib/google/cloud/extra.py",
+            "google_cloud_storage-2.0.dist-info/RECORD",
+        ];
+        fs::write(&path, wheel(&names).into_inner()).unwrap();
+        assert!(wheel_provides(&path, "google.cloud.storage"));
+        assert!(wheel_provides(&path, "google.cloud._speedups"));
+        assert!(wheel_provides(&path, "google.cloud.extra"));
+        assert!(wheel_provides(&path, "google.cloud"));
+        assert!(!wheel_provides(&path, "google.cloud.bigquery"));
+        assert!(!wheel_provides(&path, "google.cloud.stor"));
+
+        let sdist = dir.path().join("google-cloud-storage-2.0.tar.gz");
+        fs::write(&sdist, b"").unwrap();
+        assert!(!wheel_provides(&sdist, "google.cloud.storage"));
+    }
+
+    #[test]
+    fn test_extract_zip_sdist() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("zope.interface-6.0.zip");
+        let names = [
+            "zope.interface-6.0/setup.py",
+            "zope.interface-6.0/src/zope/interface/__init__.py",
+        ];
+        fs::write(&archive, wheel(&names).into_inner()).unwrap();
+
+        let downloader = PackageDownloader::new("zope.interface".to_string());
+        let extracted = downloader
+            .extract_package(&archive, dir.path(), "extracted")
+            .unwrap();
+        assert!(extracted
+            .join("zope.interface-6.0/src/zope/interface/__init__.py")
+            .is_file());
+    }
+
+    #[test]
+    fn test_unpack_zip_sources_only() {
+        let names = [
+            "heavy/__init__.py",
+            "heavy/core.pyi",
+            "heavy/_speedups.cpython-312-x86_64-linux-gnu.so",
+            "heavy/py.typed",
+            "heavy/data/weights.bin",
+            "heavy-1.0.dist-info/METADATA",
+            "heavy-1.0.data/scripts/heavy-cli.py",
+            "_heavy_impl/core.py",
+            "_heavy_impl/tables.json",
+        ];
+        let dir = tempfile::tempdir().unwrap();
+        unpack_zip(wheel(&names), dir.path(), Some("heavy")).unwrap();
+        let extracted = |name: &str| dir.path().join(name).exists();
+        assert!(extracted("heavy/__init__.py"));
+        assert!(extracted("heavy/core.pyi"));
+        assert!(extracted("heavy/_speedups.cpython-312-x86_64-linux-gnu.so"));
+        assert!(extracted("heavy/py.typed"));
+        assert!(extracted("heavy-1.0.dist-info/METADATA"));
+        assert!(!extracted("heavy/data/weights.bin"));
+        assert!(!extracted("heavy-1.0.data/scripts/heavy-cli.py"));
+        // Private packages the module imports keep their sources
+        assert!(extracted("_heavy_impl/core.py"));
+        assert!(!extracted("_heavy_impl/tables.json"));
+
+        // A layout without the module extracts everything
+        let dir = tempfile::tempdir().unwrap();
+        unpack_zip(wheel(&names), dir.path(), Some("missing")).unwrap();
+        assert!(dir.path().join("heavy/data/weights.bin").exists());
+    }
+
+    #[test]
+    fn test_data_purelib_layout() {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        let record = "odd_dist-1.0.data/purelib/oddpkg/__init__.py,,\n\
+                      odd_dist-1.0.data/platlib/oddpkg_speedups.so,,\n\
+                      odd_dist-1.0.dist-info/METADATA,,\n\
+                      odd_dist-1.0.dist-info/RECORD,,\n";
+        for (name, content) in [
+            (
+                "odd_dist-1.0.data/purelib/oddpkg/__init__.py",
+                "def run(): ...\n",
+            ),
+            ("odd_dist-1.0.data/purelib/oddpkg/data.bin", ""),
+            ("odd_dist-1.0.data/platlib/oddpkg_speedups.so", ""),
+            (
+                "odd_dist-1.0.dist-info/METADATA",
+                "Name: odd-dist\nVersion: 1.0\n",
+            ),
+            ("odd_dist-1.0.dist-info/RECORD", record),
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        let archive = writer.finish().unwrap();
+
+        assert_eq!(
+            installed_entry_path("odd_dist-1.0.data/purelib/oddpkg/__init__.py"),
+            "oddpkg/__init__.py"
+        );
+        assert_eq!(
+            installed_entry_path("odd_dist-1.0.data/scripts/run"),
+            "odd_dist-1.0.data/scripts/run"
+        );
+
+        // Only the module's sources are extracted, found under .data/purelib
+        let dir = tempfile::tempdir().unwrap();
+        unpack_zip(archive, dir.path(), Some("oddpkg")).unwrap();
+        let purelib = dir.path().join("odd_dist-1.0.data").join("purelib");
+        assert!(purelib.join("oddpkg").join("__init__.py").is_file());
+        assert!(!purelib.join("oddpkg").join("data.bin").exists());
+        assert_eq!(
+            extracted_top_levels(dir.path()),
+            vec!["oddpkg", "oddpkg_speedups"]
+        );
+        assert_eq!(
+            extracted_packages(dir.path()),
+            vec!["oddpkg", "oddpkg_speedups"]
+        );
+
+        // By distribution name: the import name comes from RECORD
+        let downloader = PackageDownloader::new("odd-dist".to_string());
+        assert_eq!(downloader.find_package_root(dir.path()).unwrap(), purelib);
+        // By import name: the package directory itself
+        let downloader = PackageDownloader::new("oddpkg".to_string());
+        assert_eq!(
+            downloader.find_package_root(dir.path()).unwrap(),
+            purelib.join("oddpkg")
+        );
+    }
+
+    #[test]
+    fn test_unpack_tar_skips_traversal_and_escaping_links() {
+        // tar::Header refuses unsafe paths, so write the raw name fields
+        fn entry(
+            builder: &mut tar::Builder<Vec<u8>>,
+            name: &str,
+            kind: tar::EntryType,
+            link: &str,
+            data: &[u8],
+        ) {
+            let mut header = tar::Header::new_gnu();
+            let gnu = header.as_gnu_mut().unwrap();
+            gnu.name[..name.len()].copy_from_slice(name.as_bytes());
+            gnu.linkname[..link.len()].copy_from_slice(link.as_bytes());
+            header.set_entry_type(kind);
+            header.set_mode(0o644);
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder.append(&header, data).unwrap();
+        }
+
+        let mut builder = tar::Builder::new(Vec::new());
+        let regular = tar::EntryType::Regular;
+        entry(
+            &mut builder,
+            "pkg-1.0/pkg/__init__.py",
+            regular,
+            "",
+            b"x = 1\n",
+        );
+        entry(&mut builder, "../evil.py", regular, "", b"x = 1\n");
+        entry(&mut builder, "/abs.py", regular, "", b"x = 1\n");
+        let symlink = tar::EntryType::Symlink;
+        entry(
+            &mut builder,
+            "pkg-1.0/escape",
+            symlink,
+            "../../outside",
+            b"",
+        );
+        entry(
+            &mut builder,
+            "pkg-1.0/inside",
+            symlink,
+            "pkg/__init__.py",
+            b"",
+        );
+        entry(
+            &mut builder,
+            "pkg-1.0/hard",
+            tar::EntryType::Link,
+            "../evil.py",
+            b"",
+        );
+        let archive = builder.into_inner().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("extracted");
+        fs::create_dir_all(&root).unwrap();
+        let skipped = unpack_tar(archive.as_slice(), &root).unwrap();
+        assert_eq!(
+            skipped,
+            vec!["../evil.py", "/abs.py", "pkg-1.0/escape", "pkg-1.0/hard"]
+        );
+        assert!(root
+            .join("pkg-1.0")
+            .join("pkg")
+            .join("__init__.py")
+            .is_file());
+        assert!(!dir.path().join("evil.py").exists());
+        assert!(fs::symlink_metadata(root.join("pkg-1.0").join("escape")).is_err());
+    }
+
+    #[test]
+    fn test_link_stays_inside_rejects_chains() {
+        let link = |entry: &str, target: &str| {
+            let entry = PathBuf::from(entry);
+            let walk = link_walk(&entry, Path::new(target)).unwrap();
+            (entry, walk)
+        };
+        let inside = |entry: &str, target: &str, links: &[ExtractedLink]| {
+            link_stays_inside(Path::new(entry), Path::new(target), links)
+        };
+        assert!(inside("pkg-1.0/up", "..", &[]));
+        assert!(!inside("pkg-1.0/escape", "../../outside", &[]));
+
+        // Through up -> .., up2 -> up/.. would lead out of the root
+        let up = [link("pkg-1.0/up", "..")];
+        assert!(!inside("pkg-1.0/up2", "up/..", &up));
+        assert!(inside("pkg-1.0/alias", "up", &up));
+        let through_up = Path::new("pkg-1.0/up/setup.py");
+        assert!(!hard_link_stays_inside(through_up, &up));
+
+        // Nor may a link sit where an earlier link walks through
+        let early = [link("pkg-1.0/early", "d/..")];
+        assert!(!inside("pkg-1.0/d", "..", &early));
+    }
+
+    #[test]
+    fn test_verify_sha256() {
+        let info = |sha256: &str| PackageInfo {
+            url: "https://example.com/pkg-1.0.tar.gz".to_string(),
+            filename: "pkg-1.0.tar.gz".to_string(),
+            sha256: Some(sha256.to_string()),
+            sdist: None,
+        };
+        // sha256("abc")
+        let digest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert!(verify_sha256(&info(digest), b"abc").is_ok());
+        assert!(verify_sha256(&info(&digest.to_uppercase()), b"abc").is_ok());
+        assert!(verify_sha256(&info(digest), b"abd").is_err());
+    }
+
+    #[test]
+    fn test_release_index_latest_skips_prereleases() {
+        let file = ReleaseFile {
+            filename: "pkg.whl".to_string(),
+            url: "https://example.com/pkg.whl".to_string(),
+            sha256: None,
+        };
+        let index = ReleaseIndex {
+            latest: None,
+            releases: BTreeMap::from([
+                ("1.9.0".to_string(), vec![file.clone()]),
+                ("1.10.0".to_string(), vec![file.clone()]),
+                ("2.0.0b1".to_string(), vec![file]),
+                ("3.0.0".to_string(), vec![]),
+            ]),
+        };
+        assert_eq!(index.latest().unwrap(), "1.10.0");
+    }
+}
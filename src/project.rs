@@ -0,0 +1,193 @@
+use crate::explorer::{explore_packages, ModuleTreeExplorer};
+use crate::utils::PathGuard;
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Directories that hold packages but are never the project's API
+const NON_API_DIRS: &[&str] = &["tests", "test", "docs", "examples", "benchmarks", "scripts"];
+
+/// A local project: its name and the top-level packages it provides
+#[derive(Debug, PartialEq)]
+pub struct Project {
+    pub name: String,
+    pub source_root: PathBuf,
+    pub packages: Vec<String>,
+}
+
+/// Whether a tree argument names a project directory rather than a module
+/// e.g., "." or "../other-project" (any directory with a pyproject.toml)
+pub fn is_project_dir(arg: &str) -> bool {
+    let path = Path::new(arg);
+    path.is_dir() && (arg == "." || path.join("pyproject.toml").is_file())
+}
+
+/// Read the project name and find its packages (src, maturin python-source, or flat layout)
+pub fn detect_project(dir: &Path) -> PyResult<Project> {
+    let pyproject: Option<toml::Table> = fs::read_to_string(dir.join("pyproject.toml"))
+        .ok()
+        .and_then(|text| text.parse().ok());
+    let dir_name = dir
+        .canonicalize()
+        .ok()
+        .and_then(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| ".".to_string());
+    let name = pyproject
+        .as_ref()
+        .and_then(|table| table.get("project")?.get("name")?.as_str())
+        .map(str::to_string)
+        .unwrap_or(dir_name);
+    let python_source = pyproject
+        .as_ref()
+        .and_then(|table| table.get("tool")?.get("maturin")?.get("python-source")?.as_str());
+
+    let mut roots = Vec::new();
+    if let Some(python_source) = python_source {
+        roots.push(dir.join(python_source));
+    }
+    roots.push(dir.join("src"));
+    roots.push(dir.to_path_buf());
+
+    let module_name = name.replace(['-', '.'], "_").to_lowercase();
+    for root in roots {
+        let packages = find_packages(&root, &module_name);
+        if !packages.is_empty() {
+            return Ok(Project {
+                name,
+                source_root: root,
+                packages,
+            });
+        }
+    }
+
+    Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(format!(
+        "No packages found in project '{}' ({})",
+        name,
+        dir.display()
+    )))
+}
+
+/// Top-level packages in a source root, plus a single-file module named after the project
+fn find_packages(root: &Path, module_name: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut packages: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let is_identifier = !file_name.starts_with(['_', '.'])
+                && file_name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if path.is_dir() {
+                let is_package =
+                    path.join("__init__.py").exists() || path.join("__init__.pyi").exists();
+                (is_identifier && is_package && !NON_API_DIRS.contains(&file_name.as_str()))
+                    .then_some(file_name)
+            } else {
+                // Flat single-module projects (setup.py, noxfile.py, ... are not the API)
+                file_name
+                    .strip_suffix(".py")
+                    .filter(|stem| *stem == module_name)
+                    .map(str::to_string)
+            }
+        })
+        .collect();
+    packages.sort();
+    packages
+}
+
+/// Explore a project's packages, returning the tree label and tree
+/// A single package is shown as-is; several go under a synthetic root named after the project.
+/// `shape` applies the caller's tree options (imports, pruning, class depth) to each explorer.
+pub fn explore_project(
+    py: Python,
+    dir: &Path,
+    max_depth: usize,
+    verbosity: Verbosity,
+    timeout: Option<Duration>,
+    shape: &dyn Fn(ModuleTreeExplorer) -> ModuleTreeExplorer,
+) -> PyResult<(String, PyObject)> {
+    let project = detect_project(dir)?;
+    verbosity.detail(&format!(
+        "project {} provides {} from {}",
+        project.name,
+        project.packages.join(", "),
+        project.source_root.display()
+    ));
+
+    // The project's own sources win over any installed copy
+    let sys_path = py.import("sys")?.getattr("path")?;
+    let source_root = project.source_root.to_string_lossy().to_string();
+    let _guard = PathGuard::prepend(&sys_path, &source_root)?;
+
+    explore_packages(
+        py,
+        &project.name,
+        &project.packages,
+        max_depth,
+        verbosity,
+        timeout,
+        shape,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn test_src_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"my-tool\"\n",
+        )
+        .unwrap();
+        touch(&dir.path().join("src/my_tool/__init__.py"));
+        touch(&dir.path().join("tests/__init__.py"));
+
+        let project = detect_project(dir.path()).unwrap();
+        assert_eq!(project.name, "my-tool");
+        assert_eq!(project.source_root, dir.path().join("src"));
+        assert_eq!(project.packages, vec!["my_tool"]);
+    }
+
+    #[test]
+    fn test_flat_layout_with_several_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("pyproject.toml"), "[project]\nname = \"suite\"\n").unwrap();
+        touch(&dir.path().join("alpha/__init__.py"));
+        touch(&dir.path().join("beta/__init__.py"));
+        touch(&dir.path().join("tests/__init__.py"));
+        touch(&dir.path().join("docs/conf.py"));
+        touch(&dir.path().join("setup.py"));
+        touch(&dir.path().join("suite.py"));
+
+        let project = detect_project(dir.path()).unwrap();
+        assert_eq!(project.source_root, dir.path());
+        assert_eq!(project.packages, vec!["alpha", "beta", "suite"]);
+    }
+
+    #[test]
+    fn test_maturin_python_source() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"ext\"\n\n[tool.maturin]\npython-source = \"python\"\n",
+        )
+        .unwrap();
+        touch(&dir.path().join("python/ext/__init__.py"));
+
+        let project = detect_project(dir.path()).unwrap();
+        assert_eq!(project.source_root, dir.path().join("python"));
+        assert_eq!(project.packages, vec!["ext"]);
+    }
+}
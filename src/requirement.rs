@@ -0,0 +1,493 @@
+use std::cmp::Ordering;
+
+/// A parsed PEP 508 requirement string
+/// e.g., "uvicorn[standard]>=0.30; python_version >= '3.9'"
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Requirement<'a> {
+    pub name: &'a str,
+    pub extras: Vec<&'a str>,
+    pub specifier: Option<&'a str>, // e.g., ">=0.30,<1"
+    pub url: Option<&'a str>,       // e.g., "https://.../pkg-1.0-py3-none-any.whl"
+    pub marker: Option<&'a str>,    // e.g., "python_version >= '3.9'" (not evaluated)
+}
+
+/// Check whether a string looks like a direct reference URL
+pub fn is_url(value: &str) -> bool {
+    value.contains("://") || value.starts_with("file:")
+}
+
+/// Parse a PEP 508 requirement string
+/// Names may contain dots so that module paths (e.g., "prefect.server>=2") parse too.
+pub fn parse_requirement(spec: &str) -> Requirement<'_> {
+    let spec = spec.trim();
+
+    // Direct references: "name[extras] @ url ; marker"
+    // The URL may itself contain ';', so the marker must be preceded by whitespace
+    let (body, marker) = if let Some(at_pos) = spec.find('@') {
+        if is_url(spec[at_pos + 1..].trim()) {
+            match spec[at_pos..].find(" ;") {
+                Some(pos) => (&spec[..at_pos + pos], Some(&spec[at_pos + pos + 2..])),
+                None => (spec, None),
+            }
+        } else {
+            split_marker(spec)
+        }
+    } else {
+        split_marker(spec)
+    };
+    let marker = marker.map(str::trim).filter(|m| !m.is_empty());
+
+    // Name: letters, digits, and . _ -
+    let name_end = body
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+        .unwrap_or(body.len());
+    let name = &body[..name_end];
+    let mut rest = body[name_end..].trim_start();
+
+    // Extras: "[a, b]"
+    let mut extras = Vec::new();
+    if let Some(stripped) = rest.strip_prefix('[') {
+        if let Some(close) = stripped.find(']') {
+            extras = stripped[..close]
+                .split(',')
+                .map(str::trim)
+                .filter(|e| !e.is_empty())
+                .collect();
+            rest = stripped[close + 1..].trim_start();
+        }
+    }
+
+    // Either a URL or a version specifier set (optionally parenthesized)
+    let mut url = None;
+    let mut specifier = None;
+    if let Some(stripped) = rest.strip_prefix('@') {
+        let target = stripped.trim();
+        if !target.is_empty() {
+            url = Some(target);
+        }
+    } else {
+        let set = rest
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .trim();
+        if !set.is_empty() {
+            specifier = Some(set);
+        }
+    }
+
+    Requirement {
+        name,
+        extras,
+        specifier,
+        url,
+        marker,
+    }
+}
+
+/// Normalize a distribution name per PEP 503 (e.g., "Foo_Bar.baz" -> "foo-bar-baz")
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for ch in name.trim().chars() {
+        if matches!(ch, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(ch.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Split an environment marker off a (non-URL) requirement
+fn split_marker(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once(';') {
+        Some((body, marker)) => (body.trim_end(), Some(marker)),
+        None => (spec, None),
+    }
+}
+
+/// A PEP 440 version, simplified to what is needed for ordering and matching
+#[derive(Debug, Clone)]
+pub struct Version {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Option<(u8, u64)>, // (0 = a, 1 = b, 2 = rc, number)
+    post: Option<u64>,
+    dev: Option<u64>,
+}
+
+impl Version {
+    /// Parse a version string, returning None for anything non-PEP 440
+    pub fn parse(version: &str) -> Option<Self> {
+        let lowered = version.trim().to_lowercase();
+        let mut s = lowered.trim_start_matches('v');
+
+        // Local version labels don't affect selection
+        if let Some((public, _local)) = s.split_once('+') {
+            s = public;
+        }
+
+        let (epoch, s) = match s.split_once('!') {
+            Some((epoch, rest)) => (epoch.parse().ok()?, rest),
+            None => (0, s),
+        };
+
+        // Release segment: digits separated by dots
+        let release_end = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        let release_str = s[..release_end].trim_end_matches('.');
+        if release_str.is_empty() {
+            return None;
+        }
+        let release = release_str
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+
+        let mut rest = &s[release_str.len()..];
+        let mut pre = None;
+        let mut post = None;
+        let mut dev = None;
+
+        while !rest.is_empty() {
+            rest = rest.trim_start_matches(['.', '-', '_']);
+            let tag_end = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let tag = &rest[..tag_end];
+            let after_tag = rest[tag_end..].trim_start_matches(['.', '-', '_']);
+            let num_end = after_tag
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(after_tag.len());
+            let number: u64 = after_tag[..num_end].parse().unwrap_or(0);
+
+            match tag {
+                "a" | "alpha" => pre = Some((0, number)),
+                "b" | "beta" => pre = Some((1, number)),
+                "rc" | "c" | "pre" | "preview" => pre = Some((2, number)),
+                "post" | "rev" | "r" => post = Some(number),
+                // Implicit post release: "1.0-1"
+                "" if num_end > 0 => post = Some(number),
+                "dev" => dev = Some(number),
+                _ => return None,
+            }
+            rest = &after_tag[num_end..];
+        }
+
+        Some(Self {
+            epoch,
+            release,
+            pre,
+            post,
+            dev,
+        })
+    }
+
+    /// Whether this is a pre-release (or development release)
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    fn sort_key(&self) -> (u64, Vec<u64>, (i8, u64), (i8, u64), (i8, u64)) {
+        let mut release = self.release.clone();
+        while release.len() > 1 && release.last() == Some(&0) {
+            release.pop();
+        }
+
+        let pre = match (self.pre, self.post, self.dev) {
+            // A bare dev release sorts before any pre-release of the same version
+            (None, None, Some(_)) => (-1, 0),
+            (None, _, _) => (3, 0),
+            (Some((kind, n)), _, _) => (kind as i8, n),
+        };
+        let post = match self.post {
+            None => (-1, 0),
+            Some(n) => (0, n),
+        };
+        let dev = match self.dev {
+            None => (1, 0),
+            Some(n) => (0, n),
+        };
+
+        (self.epoch, release, pre, post, dev)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+/// Check whether a version satisfies a comma-separated specifier set
+/// e.g., version_matches("0.30.1", ">=0.30,<1") -> true
+pub fn version_matches(version: &str, specifier: &str) -> bool {
+    let Some(candidate) = Version::parse(version) else {
+        return false;
+    };
+
+    specifier
+        .split(',')
+        .map(str::trim)
+        .filter(|clause| !clause.is_empty())
+        .all(|clause| clause_matches(version, &candidate, clause))
+}
+
+/// Check an installed version against a pin ("2.20.0") or a specifier set (">=2,<3")
+/// "latest" accepts any version.
+pub fn pin_matches(version: &str, pin: &str) -> bool {
+    if pin == "latest" {
+        return true;
+    }
+    if pin.starts_with(['<', '>', '=', '!', '~']) {
+        version_matches(version, pin)
+    } else {
+        version_matches(version, &format!("=={}", pin))
+    }
+}
+
+fn clause_matches(raw: &str, candidate: &Version, clause: &str) -> bool {
+    const OPERATORS: [&str; 8] = ["===", "~=", "==", "!=", "<=", ">=", "<", ">"];
+
+    let Some(op) = OPERATORS.iter().find(|op| clause.starts_with(**op)) else {
+        return false;
+    };
+    let target = clause[op.len()..].trim();
+
+    if *op == "===" {
+        return raw.trim() == target;
+    }
+
+    // Prefix matching: "==1.2.*" / "!=1.2.*"
+    if let Some(prefix) = target.strip_suffix(".*") {
+        let Some(prefix) = Version::parse(prefix) else {
+            return false;
+        };
+        let matches = candidate.epoch == prefix.epoch
+            && prefix
+                .release
+                .iter()
+                .enumerate()
+                .all(|(i, part)| candidate.release.get(i).copied().unwrap_or(0) == *part);
+        return match *op {
+            "==" => matches,
+            "!=" => !matches,
+            _ => false,
+        };
+    }
+
+    let Some(target_version) = Version::parse(target) else {
+        return false;
+    };
+
+    match *op {
+        "==" => *candidate == target_version,
+        "!=" => *candidate != target_version,
+        "<=" => *candidate <= target_version,
+        ">=" => *candidate >= target_version,
+        "<" => *candidate < target_version,
+        ">" => *candidate > target_version,
+        "~=" => {
+            // ~=X.Y.Z means >=X.Y.Z, ==X.Y.*
+            if target_version.release.len() < 2 {
+                return false;
+            }
+            let prefix = &target_version.release[..target_version.release.len() - 1];
+            *candidate >= target_version
+                && prefix
+                    .iter()
+                    .enumerate()
+                    .all(|(i, part)| candidate.release.get(i).copied().unwrap_or(0) == *part)
+        }
+        _ => false,
+    }
+}
+
+/// Pick the highest version satisfying a specifier set
+/// Pre-releases are only considered when the specifier mentions one.
+pub fn select_version<'a, I>(versions: I, specifier: &str) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let allow_prereleases = specifier
+        .split(',')
+        .filter_map(|clause| {
+            Version::parse(clause.trim().trim_start_matches(['=', '!', '<', '>', '~']))
+        })
+        .any(|v| v.is_prerelease());
+
+    versions
+        .into_iter()
+        .filter_map(|v| Version::parse(v).map(|parsed| (v, parsed)))
+        .filter(|(_, parsed)| allow_prereleases || !parsed.is_prerelease())
+        .filter(|(raw, _)| version_matches(raw, specifier))
+        .max_by(|a, b| a.1.cmp(&b.1))
+        .map(|(raw, _)| raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("beautifulsoup4"), "beautifulsoup4");
+        assert_eq!(normalize_name("Foo_Bar.baz"), "foo-bar-baz");
+        assert_eq!(normalize_name("zope__interface"), "zope-interface");
+    }
+
+    #[test]
+    fn test_parse_real_world_requirements() {
+        let cases: Vec<(&str, Requirement)> = vec![
+            (
+                "requests",
+                Requirement {
+                    name: "requests",
+                    ..Default::default()
+                },
+            ),
+            (
+                "requests==2.31.0",
+                Requirement {
+                    name: "requests",
+                    specifier: Some("==2.31.0"),
+                    ..Default::default()
+                },
+            ),
+            (
+                "uvicorn[standard]>=0.30; python_version >= '3.9'",
+                Requirement {
+                    name: "uvicorn",
+                    extras: vec!["standard"],
+                    specifier: Some(">=0.30"),
+                    marker: Some("python_version >= '3.9'"),
+                    ..Default::default()
+                },
+            ),
+            (
+                "pydantic[email, timezone] >=2.0,<3",
+                Requirement {
+                    name: "pydantic",
+                    extras: vec!["email", "timezone"],
+                    specifier: Some(">=2.0,<3"),
+                    ..Default::default()
+                },
+            ),
+            (
+                "typing-extensions (>=4.6)",
+                Requirement {
+                    name: "typing-extensions",
+                    specifier: Some(">=4.6"),
+                    ..Default::default()
+                },
+            ),
+            (
+                "importlib_metadata; python_version < \"3.10\"",
+                Requirement {
+                    name: "importlib_metadata",
+                    marker: Some("python_version < \"3.10\""),
+                    ..Default::default()
+                },
+            ),
+            (
+                "attrs ~= 23.1",
+                Requirement {
+                    name: "attrs",
+                    specifier: Some("~= 23.1"),
+                    ..Default::default()
+                },
+            ),
+            (
+                "pip @ https://github.com/pypa/pip/archive/1.3.1.zip#sha1=da9234ee",
+                Requirement {
+                    name: "pip",
+                    url: Some("https://github.com/pypa/pip/archive/1.3.1.zip#sha1=da9234ee"),
+                    ..Default::default()
+                },
+            ),
+            (
+                "name[quux] @ file:///tmp/name-1.0-py3-none-any.whl ; os_name == 'posix'",
+                Requirement {
+                    name: "name",
+                    extras: vec!["quux"],
+                    url: Some("file:///tmp/name-1.0-py3-none-any.whl"),
+                    marker: Some("os_name == 'posix'"),
+                    ..Default::default()
+                },
+            ),
+            (
+                "prefect.server.api>=2",
+                Requirement {
+                    name: "prefect.server.api",
+                    specifier: Some(">=2"),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        for (spec, expected) in cases {
+            assert_eq!(parse_requirement(spec), expected, "parsing {:?}", spec);
+        }
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let ordered = [
+            "1.0.dev1", "1.0a1", "1.0a2", "1.0b1", "1.0rc1", "1.0", "1.0.post1", "1.1", "2.0",
+        ];
+        for pair in ordered.windows(2) {
+            let a = Version::parse(pair[0]).unwrap();
+            let b = Version::parse(pair[1]).unwrap();
+            assert!(a < b, "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(Version::parse("1.0"), Version::parse("1.0.0"));
+    }
+
+    #[test]
+    fn test_version_matches() {
+        assert!(version_matches("0.30.1", ">=0.30"));
+        assert!(version_matches("0.30.1", ">=0.30,<1"));
+        assert!(!version_matches("1.0.0", ">=0.30,<1"));
+        assert!(version_matches("2.31.0", "==2.31.0"));
+        assert!(version_matches("2.31.5", "==2.31.*"));
+        assert!(!version_matches("2.32.0", "==2.31.*"));
+        assert!(version_matches("23.2.0", "~= 23.1"));
+        assert!(!version_matches("24.0", "~=23.1"));
+        assert!(!version_matches("1.5", "!=1.5"));
+    }
+
+    #[test]
+    fn test_pin_matches() {
+        assert!(pin_matches("2.20.0", "2.20.0"));
+        assert!(pin_matches("2.20", "2.20.0"));
+        assert!(!pin_matches("3.0.1", "2.20.0"));
+        assert!(pin_matches("3.0.1", ">=3,<4"));
+        assert!(pin_matches("3.0.1", "latest"));
+    }
+
+    #[test]
+    fn test_select_version() {
+        let versions = ["0.29.0", "0.30.0", "0.30.6", "1.0.0a1", "1.0.0"];
+        assert_eq!(select_version(versions, ">=0.30,<1"), Some("0.30.6"));
+        assert_eq!(select_version(versions, ">=0.30"), Some("1.0.0"));
+        assert_eq!(select_version(versions, "<0.29"), None);
+        assert_eq!(select_version(["1.0.0a1"], ">=1.0.0a1"), Some("1.0.0a1"));
+    }
+}
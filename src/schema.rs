@@ -0,0 +1,409 @@
+use pyo3::prelude::*;
+use serde_json::{json, Value};
+
+/// Bumped whenever the shape of a machine-readable output changes
+pub const SCHEMA_VERSION: u32 = 7;
+
+/// Outputs with a schema, by the command that produces them
+pub const SCHEMA_KINDS: [&str; 7] = [
+    "tree",
+    "signature",
+    "methods",
+    "diff",
+    "coverage",
+    "imports",
+    "stubcheck",
+];
+
+/// The JSON Schema (draft 2020-12) of a machine-readable output
+/// Maintained alongside the serde structures and dicts that produce each output.
+pub fn schema(kind: &str) -> PyResult<Value> {
+    let (title, body) = match kind {
+        "tree" => ("module tree", tree()),
+        "signature" => ("signature", signature_output()),
+        "methods" => ("class methods", class_view()),
+        "diff" => ("API diff", api_diff()),
+        "coverage" => ("annotation coverage", coverage()),
+        "imports" => ("external imports", external_imports()),
+        "stubcheck" => ("stub drift", stub_drift()),
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown schema '{}': expected one of {}",
+                kind,
+                SCHEMA_KINDS.join(", ")
+            )))
+        }
+    };
+    let mut document = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!("https://github.com/zzstoatzz/pretty-mod/schemas/{}.json", kind),
+        "title": format!("pretty-mod {}", title),
+        "schema_version": SCHEMA_VERSION,
+        "$defs": defs(),
+    });
+    if let (Some(document), Value::Object(body)) = (document.as_object_mut(), body) {
+        document.extend(body);
+    }
+    Ok(document)
+}
+
+fn strings() -> Value {
+    json!({"type": "array", "items": {"type": "string"}})
+}
+
+fn nullable_string() -> Value {
+    json!({"type": ["string", "null"]})
+}
+
+fn count() -> Value {
+    json!({"type": "integer", "minimum": 0})
+}
+
+/// An object with exactly these properties, the `required` ones always present
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Names defined in a module, each with the line defining it when known
+fn symbols() -> Value {
+    json!({"type": "array", "items": {"$ref": "#/$defs/symbol"}})
+}
+
+/// Definitions shared by the outputs: modules nest, and signatures appear in several
+fn defs() -> Value {
+    json!({
+        "symbol": object(
+            json!({
+                "name": {"type": "string"},
+                "line": {"type": "integer", "minimum": 1},
+            }),
+            &["name"],
+        ),
+        // A symbol; with `class_depth` 1, also its public methods and how many were left out
+        "class": object(
+            json!({
+                "name": {"type": "string"},
+                "line": {"type": "integer", "minimum": 1},
+                "methods": {
+                    "type": "array",
+                    "items": object(
+                        json!({
+                            "name": {"type": "string"},
+                            "signature": {"type": "string"},
+                        }),
+                        &["name"],
+                    ),
+                },
+                "more": count(),
+            }),
+            &["name"],
+        ),
+        "api": object(
+            json!({
+                "all": strings(),
+                "functions": symbols(),
+                "classes": {"type": "array", "items": {"$ref": "#/$defs/class"}},
+                "abstract_classes": strings(),
+                "constants": symbols(),
+                "reexports": strings(),
+                "dynamic_all": {"const": true},
+                "imports": {
+                    "type": "object",
+                    "additionalProperties": object(
+                        json!({
+                            "from": nullable_string(),
+                            "name": {"type": "string"},
+                            "level": count(),
+                        }),
+                        &["from", "name", "level"],
+                    ),
+                },
+            }),
+            &["all", "functions", "classes", "abstract_classes", "constants"],
+        ),
+        "module": object(
+            json!({
+                "api": {"$ref": "#/$defs/api"},
+                "file": {"type": "string"},
+                "submodules": {
+                    "type": "object",
+                    "additionalProperties": {"$ref": "#/$defs/module"},
+                },
+                "truncated": {"const": true},
+                "hidden_empty": count(),
+                "scripts": {"type": "object", "additionalProperties": {"type": "string"}},
+            }),
+            &["api", "submodules"],
+        ),
+        "dist": object(
+            json!({
+                "name": {"type": "string"},
+                "version": {"type": "string"},
+                "summary": nullable_string(),
+                "requires_python": nullable_string(),
+                "license": nullable_string(),
+            }),
+            &["name", "version"],
+        ),
+        "signature": signature(),
+        "method": object(
+            json!({
+                "name": {"type": "string"},
+                "kind": {"enum": ["method", "property", "classmethod", "staticmethod"]},
+                "signature": {"$ref": "#/$defs/signature"},
+                "defined_in": {"type": "string"},
+            }),
+            &["name", "kind", "signature"],
+        ),
+    })
+}
+
+/// A `FunctionSignature`; methods and the `signature` output share it
+fn signature() -> Value {
+    object(
+        json!({
+            "name": {"type": "string"},
+            "parameters": {"type": "string"},
+            "return_type": nullable_string(),
+            "source": {"type": "string"},
+            "kwargs": object(
+                json!({
+                    "parameter": {"type": "string"},
+                    "typed_dict": {"type": "string"},
+                    "fields": {
+                        "type": "array",
+                        "items": object(
+                            json!({
+                                "name": {"type": "string"},
+                                "annotation": {"type": "string"},
+                                "required": {"type": "boolean"},
+                            }),
+                            &["name", "annotation", "required"],
+                        ),
+                    },
+                }),
+                &["parameter", "typed_dict", "fields"],
+            ),
+            "param_docs": {"type": "object", "additionalProperties": {"type": "string"}},
+        }),
+        &["name", "parameters", "return_type"],
+    )
+}
+
+fn tree() -> Value {
+    object(
+        json!({
+            "module": {"type": "string"},
+            "dist": {"$ref": "#/$defs/dist"},
+            "tree": {"$ref": "#/$defs/module"},
+        }),
+        &["module", "tree"],
+    )
+}
+
+/// A signature, the placeholder written when none was found, or a module's summary
+/// Either of the first two lists every definition when several modules define the name.
+fn signature_output() -> Value {
+    let mut found = signature();
+    found["properties"]["candidates"] = strings();
+    found["properties"]["source_code"] = json!({"type": "string"});
+    json!({
+        "oneOf": [
+            found,
+            object(
+                json!({
+                    "name": {"type": "string"},
+                    "available": {"const": false},
+                    "reason": {"type": "string"},
+                    "candidates": strings(),
+                }),
+                &["name", "available", "reason"],
+            ),
+            object(
+                json!({
+                    "kind": {"const": "module"},
+                    "name": {"type": "string"},
+                    "summary": nullable_string(),
+                    "functions": count(),
+                    "classes": count(),
+                    "constants": count(),
+                    "submodules": count(),
+                    "exports": strings(),
+                    "more_exports": count(),
+                }),
+                &[
+                    "kind",
+                    "name",
+                    "summary",
+                    "functions",
+                    "classes",
+                    "constants",
+                    "submodules",
+                    "exports",
+                    "more_exports",
+                ],
+            ),
+        ],
+    })
+}
+
+fn class_view() -> Value {
+    let methods = json!({"type": "array", "items": {"$ref": "#/$defs/method"}});
+    object(
+        json!({
+            "name": {"type": "string"},
+            "module": {"type": "string"},
+            "bases": strings(),
+            "is_abstract": {"type": "boolean"},
+            "constructors": methods,
+            "methods": methods,
+            "properties": methods,
+            "class_methods": methods,
+            "inherited": methods,
+        }),
+        &[
+            "name",
+            "module",
+            "bases",
+            "is_abstract",
+            "constructors",
+            "methods",
+            "properties",
+            "class_methods",
+            "inherited",
+        ],
+    )
+}
+
+fn api_diff() -> Value {
+    object(
+        json!({
+            "local": {"type": "string"},
+            "release": {"type": "string"},
+            "ok": {"type": "boolean"},
+            "added": strings(),
+            "removed": strings(),
+            "changed": strings(),
+        }),
+        &["local", "release", "ok", "added", "removed", "changed"],
+    )
+}
+
+fn coverage() -> Value {
+    let counts = json!({"full": count(), "partial": count(), "unannotated": count()});
+    // Per-module counts are flattened next to the module name
+    let mut module_counts = counts.clone();
+    module_counts["module"] = json!({"type": "string"});
+    object(
+        json!({
+            "module": {"type": "string"},
+            "total": object(counts, &["full", "partial", "unannotated"]),
+            "modules": {
+                "type": "array",
+                "items": object(module_counts, &["module", "full", "partial", "unannotated"]),
+            },
+            "least_annotated": {
+                "type": "array",
+                "items": object(
+                    json!({"name": {"type": "string"}, "missing": count()}),
+                    &["name", "missing"],
+                ),
+            },
+        }),
+        &["module", "total", "modules", "least_annotated"],
+    )
+}
+
+fn external_imports() -> Value {
+    object(
+        json!({
+            "module": {"type": "string"},
+            "packages": {
+                "type": "array",
+                "items": object(
+                    json!({
+                        "package": {"type": "string"},
+                        "uses": count(),
+                        "modules": {
+                            "type": "array",
+                            "items": object(
+                                json!({"module": {"type": "string"}, "uses": count()}),
+                                &["module", "uses"],
+                            ),
+                        },
+                    }),
+                    &["package", "uses", "modules"],
+                ),
+            },
+        }),
+        &["module", "packages"],
+    )
+}
+
+fn stub_drift() -> Value {
+    json!({
+        "type": "array",
+        "items": object(
+            json!({
+                "module": {"type": "string"},
+                "implementation": {"type": "string"},
+                "stub": {"type": "string"},
+                "ok": {"type": "boolean"},
+                "mismatched": strings(),
+                "stub_only": strings(),
+                "implementation_only": strings(),
+            }),
+            &[
+                "module",
+                "implementation",
+                "stub",
+                "ok",
+                "mismatched",
+                "stub_only",
+                "implementation_only",
+            ],
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module_info::FunctionSignature;
+
+    #[test]
+    fn test_every_kind_has_a_schema() {
+        for kind in SCHEMA_KINDS {
+            let document = schema(kind).unwrap();
+            assert_eq!(document["schema_version"], SCHEMA_VERSION);
+            assert!(document.get("type").is_some() || document.get("oneOf").is_some());
+        }
+    }
+
+    #[test]
+    fn test_signature_schema_lists_serialized_fields() {
+        // A new FunctionSignature field must be added to the schema (and bump the version)
+        let signature = FunctionSignature {
+            name: "f".to_string(),
+            parameters: String::new(),
+            return_type: None,
+            source: Some("docstring".to_string()),
+            kwargs: None,
+            param_docs: Some(Default::default()),
+        };
+        let serialized = serde_json::to_value(&signature).unwrap();
+        let documented = &defs()["signature"]["properties"];
+        for field in serialized.as_object().unwrap().keys() {
+            assert!(
+                documented.get(field).is_some(),
+                "{} is not in the schema",
+                field
+            );
+        }
+    }
+}
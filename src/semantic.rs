@@ -1,9 +1,8 @@
 use ruff_python_ast::{self as ast, visitor::Visitor};
 use ruff_python_parser::parse_module;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::BTreeMap;
 
-use crate::module_info::{FunctionSignature, ModuleInfo};
+use crate::module_info::{ClassInfo, FunctionSignature, MethodInfo, ModuleInfo};
 
 /// Enhanced semantic analysis using ruff's AST visitor pattern
 /// This approach uses what's publicly available from ruff crates
@@ -11,7 +10,9 @@ pub struct SemanticAnalyzer {
     /// Track the current scope stack to classify functions vs methods
     scope_stack: Vec<ScopeContext>,
     /// Map of function/method signatures found
-    signatures: HashMap<String, FunctionSignature>,
+    signatures: BTreeMap<String, FunctionSignature>,
+    /// Bases and methods of module-level classes
+    classes: BTreeMap<String, ClassInfo>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,16 +26,15 @@ impl SemanticAnalyzer {
     pub fn new() -> Self {
         Self {
             scope_stack: vec![ScopeContext::Module],
-            signatures: HashMap::new(),
+            signatures: BTreeMap::new(),
+            classes: BTreeMap::new(),
         }
     }
 
-    /// Analyze a Python file using AST visitor pattern
-    pub fn analyze_file(&mut self, file_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-        let source_code = std::fs::read_to_string(file_path)?;
-
+    /// Analyze Python module source using AST visitor pattern
+    pub fn analyze_source(&mut self, source_code: &str) -> Result<(), Box<dyn std::error::Error>> {
         // Parse using ruff's parser
-        let parsed = parse_module(&source_code)?;
+        let parsed = parse_module(source_code)?;
 
         // Visit the AST to extract semantic information
         let module = parsed.into_syntax();
@@ -54,6 +54,9 @@ impl SemanticAnalyzer {
         for (name, signature) in &self.signatures {
             base_info.signatures.insert(name.clone(), signature.clone());
         }
+        for (name, class_info) in &self.classes {
+            base_info.class_info.insert(name.clone(), class_info.clone());
+        }
 
         Ok(())
     }
@@ -81,6 +84,9 @@ impl Visitor<'_> for SemanticAnalyzer {
                         .returns
                         .as_ref()
                         .map(|ret| crate::signature::format_annotation(ret)),
+                    source: None,
+                    kwargs: crate::typed_dict::unpacked_kwargs(&func_def.parameters),
+                    param_docs: crate::docstring::body_param_docs(&func_def.body),
                 };
 
                 // Classify based on scope context
@@ -95,6 +101,24 @@ impl Visitor<'_> for SemanticAnalyzer {
                         .insert(method_name.clone(), signature.clone());
                     self.signatures.insert(qualified_name, signature.clone());
 
+                    // Record the method (with decorators) when directly in a module-level class
+                    if matches!(self.scope_stack.last(), Some(ScopeContext::Class(_))) {
+                        if let Some(class_info) = self.classes.get_mut(&class_name) {
+                            let decorators: Vec<String> = func_def
+                                .decorator_list
+                                .iter()
+                                .map(|d| crate::signature::format_annotation(&d.expression))
+                                .collect();
+                            if decorators.iter().any(|d| is_abstract_decorator(d)) {
+                                class_info.is_abstract = true;
+                            }
+                            class_info.methods.push(MethodInfo {
+                                name: method_name.clone(),
+                                decorators,
+                            });
+                        }
+                    }
+
                     // Special handling for __call__ methods - also store under class name
                     if method_name == "__call__" {
                         self.signatures.insert(class_name, signature.clone());
@@ -117,6 +141,30 @@ impl Visitor<'_> for SemanticAnalyzer {
                 self.scope_stack.pop();
             }
             ast::Stmt::ClassDef(class_def) => {
+                // Track structure for module-level classes
+                if matches!(self.scope_stack.last(), Some(ScopeContext::Module)) {
+                    let bases: Vec<String> = class_def
+                        .bases()
+                        .iter()
+                        .map(crate::signature::format_annotation)
+                        .collect();
+                    // class X(ABC) or class X(metaclass=ABCMeta)
+                    let is_abstract = bases.iter().any(|b| last_segment(b) == "ABC")
+                        || class_def.keywords().iter().any(|keyword| {
+                            let value = crate::signature::format_annotation(&keyword.value);
+                            keyword.arg.as_ref().is_some_and(|arg| arg.as_str() == "metaclass")
+                                && last_segment(&value) == "ABCMeta"
+                        });
+                    self.classes.insert(
+                        class_def.name.as_str().to_string(),
+                        ClassInfo {
+                            bases,
+                            methods: Vec::new(),
+                            is_abstract,
+                        },
+                    );
+                }
+
                 // Enter class scope
                 self.scope_stack
                     .push(ScopeContext::Class(class_def.name.as_str().to_string()));
@@ -137,3 +185,16 @@ impl Visitor<'_> for SemanticAnalyzer {
         }
     }
 }
+
+/// The final dotted segment of a name, e.g. "abc.ABC" -> "ABC"
+fn last_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Decorators from the abc module that make a class abstract
+fn is_abstract_decorator(decorator: &str) -> bool {
+    matches!(
+        last_segment(decorator),
+        "abstractmethod" | "abstractproperty" | "abstractclassmethod" | "abstractstaticmethod"
+    )
+}
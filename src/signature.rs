@@ -1,17 +1,11 @@
-use crate::config::{colorize, DisplayConfig};
-use crate::module_info::{FunctionSignature, ModuleInfo};
+use crate::config::{colorize, hyperlink, terminal_width, DisplayConfig};
+use crate::module_info::{FunctionSignature, ModuleInfo, TypedDictField};
 use crate::import_resolver::ImportChainResolver;
 use pyo3::prelude::*;
 use ruff_python_ast::{Expr, ParameterWithDefault, Parameters};
-use std::env;
-
-macro_rules! debug_log {
-    ($($arg:tt)*) => {
-        if env::var("PRETTY_MOD_DEBUG").is_ok() {
-            eprintln!("[DEBUG] {}", format!($($arg)*));
-        }
-    };
-}
+use crate::logging::debug_log;
+use crate::verbosity::Verbosity;
+use std::path::{Path, PathBuf};
 
 // ===== AST Parameter Parsing =====
 
@@ -72,49 +66,84 @@ fn format_parameter(param: &ParameterWithDefault) -> String {
 }
 
 pub fn format_annotation(expr: &Expr) -> String {
+    format_annotation_in(expr, false)
+}
+
+/// Format an annotation; `in_literal` marks values (Literal members, Annotated
+/// metadata) whose strings are data rather than forward references
+fn format_annotation_in(expr: &Expr, in_literal: bool) -> String {
     match expr {
         Expr::Name(name) => name.id.as_str().to_string(),
         Expr::Attribute(attr) => {
             format!("{}.{}", format_annotation(&attr.value), attr.attr.as_str())
         }
         Expr::Subscript(sub) => {
-            format!(
-                "{}[{}]",
-                format_annotation(&sub.value),
-                format_annotation(&sub.slice)
-            )
+            let value = format_annotation(&sub.value);
+            let slice = match value.rsplit('.').next().unwrap_or(&value) {
+                "Literal" => format_annotation_in(&sub.slice, true),
+                // Annotated[T, metadata...]: only T is a type
+                "Annotated" => match sub.slice.as_ref() {
+                    Expr::Tuple(tuple) if !tuple.elts.is_empty() => {
+                        let mut items = vec![format_annotation(&tuple.elts[0])];
+                        items.extend(tuple.elts[1..].iter().map(|e| format_annotation_in(e, true)));
+                        items.join(", ")
+                    }
+                    slice => format_annotation(slice),
+                },
+                _ => format_annotation_in(&sub.slice, in_literal),
+            };
+            format!("{}[{}]", value, slice)
         }
         Expr::Tuple(tuple) => {
-            let items: Vec<String> = tuple.elts.iter().map(format_annotation).collect();
+            let items: Vec<String> = tuple
+                .elts
+                .iter()
+                .map(|e| format_annotation_in(e, in_literal))
+                .collect();
             items.join(", ")
         }
         Expr::List(list) => {
-            let items: Vec<String> = list.elts.iter().map(format_annotation).collect();
+            let items: Vec<String> = list
+                .elts
+                .iter()
+                .map(|e| format_annotation_in(e, in_literal))
+                .collect();
             format!("[{}]", items.join(", "))
         }
         Expr::BinOp(binop) => {
             // Handle union types (e.g., str | None)
             format!(
                 "{} | {}",
-                format_annotation(&binop.left),
-                format_annotation(&binop.right)
+                format_annotation_in(&binop.left, in_literal),
+                format_annotation_in(&binop.right, in_literal)
             )
         }
         Expr::NoneLiteral(_) => "None".to_string(),
         Expr::EllipsisLiteral(_) => "...".to_string(),
         Expr::StringLiteral(str_lit) => {
-            // For Literal['string'] types
-            if let Some(single) = str_lit.as_single_part_string() {
-                format!("'{}'", single.as_str())
+            let text = str_lit.value.to_str();
+            if in_literal {
+                // For Literal['string'] types
+                format!("'{}'", text)
             } else {
-                "'...'".to_string()
+                format_forward_reference(text)
             }
         }
         Expr::BooleanLiteral(bool_lit) => if bool_lit.value { "True" } else { "False" }.to_string(),
+        Expr::NumberLiteral(_) => format_default(expr),
         _ => "...".to_string(), // Fallback for truly complex expressions
     }
 }
 
+/// Render a string annotation (e.g., `"list[Run]"`) as the expression it contains
+/// Text that does not parse as an expression is shown as written, without quotes.
+fn format_forward_reference(text: &str) -> String {
+    match ruff_python_parser::parse_expression(text.trim()) {
+        Ok(parsed) => format_annotation(parsed.expr()),
+        Err(_) => text.to_string(),
+    }
+}
+
 fn format_default(expr: &Expr) -> String {
     // Format default values
     match expr {
@@ -143,7 +172,7 @@ fn format_default(expr: &Expr) -> String {
 // ===== Signature Discovery & Display =====
 
 /// Split parameters string respecting nested brackets
-fn split_parameters(params: &str) -> Vec<String> {
+pub fn split_parameters(params: &str) -> Vec<String> {
     let mut result = Vec::new();
     let mut current = String::new();
     let mut depth = 0;
@@ -176,37 +205,121 @@ fn split_parameters(params: &str) -> Vec<String> {
     result
 }
 
-/// Recursively search for a signature in module info
-fn find_signature_recursive<'a>(
+/// Every module in an explored tree that defines `name` at module level, with its signature
+/// and the module's file
+fn find_definitions<'a>(
     module_info: &'a ModuleInfo,
+    module_path: &str,
     name: &str,
-) -> Option<&'a FunctionSignature> {
-    // Check current module
+) -> Vec<(String, &'a FunctionSignature, Option<&'a Path>)> {
+    let mut definitions = Vec::new();
     if let Some(sig) = module_info.signatures.get(name) {
-        return Some(sig);
+        // Methods are also stored under their bare name
+        let is_method = module_info
+            .class_info
+            .values()
+            .any(|class| class.methods.iter().any(|method| method.name == name));
+        let is_module_level = module_info.functions.iter().any(|f| f == name)
+            || module_info.classes.iter().any(|c| c == name);
+        if is_module_level || !is_method {
+            definitions.push((module_path.to_string(), sig, module_info.file.as_deref()));
+        }
+    }
+    for (submodule, info) in &module_info.submodules {
+        let submodule_path = format!("{}.{}", module_path, submodule);
+        definitions.extend(find_definitions(info, &submodule_path, name));
     }
+    definitions
+}
 
-    // Check submodules
-    for submod in module_info.submodules.values() {
-        if let Some(sig) = find_signature_recursive(submod, name) {
-            return Some(sig);
-        }
+/// Pick among the modules of `package` that define `name`
+/// Several definitions resolve through the package's re-exports; failing that, none is
+/// picked and every candidate is listed for a fully-qualified query.
+fn choose_definition(
+    py: Python,
+    package: &str,
+    name: &str,
+    definitions: &[(String, &FunctionSignature, Option<&Path>)],
+    verbosity: Verbosity,
+) -> Option<SignatureResult> {
+    let [first, ..] = definitions else {
+        return None;
+    };
+    if definitions.len() == 1 {
+        return Some(SignatureResult::found_in(first.1.clone(), first.2));
     }
+    let candidates: Vec<String> = definitions
+        .iter()
+        .map(|(module, _, _)| format!("{}:{}", module, name))
+        .collect();
+    verbosity.detail(&format!(
+        "{} is defined in {} modules: {}",
+        name,
+        candidates.len(),
+        candidates.join(", ")
+    ));
+    let resolver = ImportChainResolver::new();
+    let mut result = match resolver.resolve_symbol_definition(py, package, name, verbosity) {
+        Some((sig, file)) => SignatureResult::found_in(sig, file.as_deref()),
+        None => SignatureResult {
+            signature: None,
+            formatted_output: format_candidates_display(name, &candidates),
+            candidates: Vec::new(),
+            file: None,
+        },
+    };
+    result.candidates = candidates;
+    Some(result)
+}
 
-    None
+/// The definitions of an ambiguous name, each as a fully-qualified `sig` path
+pub fn format_candidates_display(name: &str, candidates: &[String]) -> String {
+    let config = DisplayConfig::get();
+    let mut result = format!(
+        "{} {} (ambiguous: defined in {} modules)",
+        colorize(
+            &config.signature_icon,
+            &config.color_scheme.signature_color,
+            config
+        ),
+        colorize(name, &config.color_scheme.signature_color, config),
+        candidates.len()
+    );
+    for (i, candidate) in candidates.iter().enumerate() {
+        let prefix = if i + 1 == candidates.len() {
+            &config.tree_last
+        } else {
+            &config.tree_branch
+        };
+        result.push_str(&format!(
+            "\n{}{}",
+            colorize(prefix, &config.color_scheme.tree_color, config),
+            colorize(candidate, &config.color_scheme.module_color, config)
+        ));
+    }
+    result
 }
 
 /// Format a signature for display
 pub fn format_signature_display(sig: &FunctionSignature) -> String {
+    format_signature_display_with_width(sig, terminal_width())
+}
+
+/// Format a signature as a tree, wrapping parameters longer than `width`
+pub fn format_signature_display_with_width(
+    sig: &FunctionSignature,
+    width: Option<usize>,
+) -> String {
     let config = DisplayConfig::get();
     let mut result = format!(
-        "{} {}\n",
+        "{} {}{}\n",
         colorize(
             &config.signature_icon,
             &config.color_scheme.signature_color,
             config
         ),
-        colorize(&sig.name, &config.color_scheme.signature_color, config)
+        colorize(&sig.name, &config.color_scheme.signature_color, config),
+        source_note(sig)
     );
     result.push_str(&format!(
         "{} Parameters:\n",
@@ -224,16 +337,71 @@ pub fn format_signature_display(sig: &FunctionSignature) -> String {
         let params = split_parameters(&sig.parameters);
         for (i, param) in params.iter().enumerate() {
             let is_last = i == params.len() - 1 && sig.return_type.is_none();
-            let prefix = if is_last {
-                &config.tree_last
+            let (prefix, continuation) = if is_last {
+                (&config.tree_last, &config.tree_empty)
             } else {
-                &config.tree_branch
+                (&config.tree_branch, &config.tree_vertical)
             };
-            result.push_str(&format!(
-                "{} {}\n",
-                colorize(prefix, &config.color_scheme.tree_color, config),
-                colorize(param, &config.color_scheme.param_color, config)
-            ));
+            // Continuation lines are indented two columns past the parameter name
+            let first_column = prefix.chars().count() + 1;
+            let indent =
+                " ".repeat(first_column - continuation.chars().count().min(first_column) + 2);
+            // An expanded **kwargs shows its TypedDict, with the keys nested below
+            let expanded = sig
+                .kwargs
+                .as_ref()
+                .filter(|kwargs| *param == format!("**{}", kwargs.parameter));
+            let param = match expanded {
+                Some(kwargs) => format!("{}: Unpack[{}]", param, kwargs.typed_dict),
+                None => param.clone(),
+            };
+            let lines = match width {
+                Some(width) => wrap_parameter(
+                    &param,
+                    width.saturating_sub(first_column),
+                    width.saturating_sub(first_column + 2),
+                ),
+                None => vec![param],
+            };
+            for (line_no, line) in lines.iter().enumerate() {
+                if line_no == 0 {
+                    result.push_str(&format!(
+                        "{} {}\n",
+                        colorize(prefix, &config.color_scheme.tree_color, config),
+                        colorize(line, &config.color_scheme.param_color, config)
+                    ));
+                } else {
+                    result.push_str(&format!(
+                        "{}{}{}\n",
+                        colorize(continuation, &config.color_scheme.tree_color, config),
+                        indent,
+                        colorize(line, &config.color_scheme.param_color, config)
+                    ));
+                }
+            }
+            if let Some(description) = param_description(sig, &param) {
+                result.push_str(&format!(
+                    "{}{}{}\n",
+                    colorize(continuation, &config.color_scheme.tree_color, config),
+                    indent,
+                    colorize(description, &config.color_scheme.tree_color, config)
+                ));
+            }
+            if let Some(kwargs) = expanded {
+                for (j, field) in kwargs.fields.iter().enumerate() {
+                    let field_prefix = if j + 1 == kwargs.fields.len() {
+                        &config.tree_last
+                    } else {
+                        &config.tree_branch
+                    };
+                    result.push_str(&format!(
+                        "{}{} {}\n",
+                        colorize(continuation, &config.color_scheme.tree_color, config),
+                        colorize(field_prefix, &config.color_scheme.tree_color, config),
+                        colorize(&format_field(field), &config.color_scheme.param_color, config)
+                    ));
+                }
+            }
         }
     }
 
@@ -253,130 +421,317 @@ pub fn format_signature_display(sig: &FunctionSignature) -> String {
     result
 }
 
+/// A TypedDict key as it would be declared, e.g. "timeout: NotRequired[float]"
+fn format_field(field: &TypedDictField) -> String {
+    if field.required {
+        format!("{}: {}", field.name, field.annotation)
+    } else {
+        format!("{}: NotRequired[{}]", field.name, field.annotation)
+    }
+}
+
+/// The docstring description of a parameter, matched by name, e.g. "**kwargs: Any" -> "kwargs"
+fn param_description<'a>(sig: &'a FunctionSignature, param: &str) -> Option<&'a str> {
+    let name = param
+        .trim_start_matches('*')
+        .split([':', '='])
+        .next()
+        .unwrap_or_default()
+        .trim();
+    sig.param_docs.as_ref()?.get(name).map(String::as_str)
+}
+
+/// Suffix marking signatures not read from source, e.g. " (from docstring)"
+fn source_note(sig: &FunctionSignature) -> String {
+    match &sig.source {
+        Some(source) => format!(" (from {})", source),
+        None => String::new(),
+    }
+}
+
+/// Break a single parameter into lines of at most `first_width` (then `rest_width`) columns
+/// Breaks happen after ", " and before " | " or "="; unbreakable runs may overflow
+fn wrap_parameter(param: &str, first_width: usize, rest_width: usize) -> Vec<String> {
+    if param.chars().count() <= first_width {
+        return vec![param.to_string()];
+    }
+
+    // Split into segments at break opportunities
+    let mut segments: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = param.chars().collect();
+    for (i, &ch) in chars.iter().enumerate() {
+        let breaks_before = (ch == '=' || (ch == ' ' && chars.get(i + 1) == Some(&'|')))
+            && !current.is_empty();
+        if breaks_before {
+            segments.push(std::mem::take(&mut current));
+        }
+        current.push(ch);
+        if ch == ' ' && i > 0 && chars[i - 1] == ',' {
+            segments.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+
+    // Greedily fill lines with segments
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for segment in segments {
+        let limit = if lines.is_empty() { first_width } else { rest_width };
+        let segment = if line.is_empty() && !lines.is_empty() {
+            segment.trim_start().to_string()
+        } else {
+            segment
+        };
+        if !line.is_empty() && line.chars().count() + segment.chars().count() > limit {
+            lines.push(line.trim_end().to_string());
+            line = segment.trim_start().to_string();
+        } else {
+            line.push_str(&segment);
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+/// Format a signature on one line: `name(params) -> return_type`
+/// With `max_width`, breaks after commas and aligns continuations after the open paren
+pub fn format_signature_compact(sig: &FunctionSignature, max_width: Option<usize>) -> String {
+    let params = split_parameters(&sig.parameters);
+    let head = format!("{}(", sig.name);
+    let tail = match &sig.return_type {
+        Some(return_type) => format!(") -> {}", return_type),
+        None => ")".to_string(),
+    };
+
+    let tail = format!("{}{}", tail, source_note(sig));
+
+    let Some(max_width) = max_width else {
+        return format!("{}{}{}", head, params.join(", "), tail);
+    };
+    if params.is_empty() {
+        return format!("{}{}", head, tail);
+    }
+
+    let indent = " ".repeat(head.chars().count());
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = head;
+    let mut line_has_param = false;
+    for (i, param) in params.iter().enumerate() {
+        let piece = if i + 1 < params.len() {
+            format!("{},", param)
+        } else {
+            format!("{}{}", param, tail)
+        };
+        if line_has_param && line.chars().count() + 1 + piece.chars().count() > max_width {
+            lines.push(line);
+            line = format!("{}{}", indent, piece);
+        } else {
+            if line_has_param {
+                line.push(' ');
+            }
+            line.push_str(&piece);
+        }
+        line_has_param = true;
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
 /// Result of signature discovery
 pub struct SignatureResult {
     pub signature: Option<FunctionSignature>,
     #[allow(dead_code)]
     pub formatted_output: String,
+    /// Every `module:name` defining the object, when a package search found several
+    /// Without a signature, the package re-exports none of them.
+    pub candidates: Vec<String>,
+    /// The file resolution found the definition in (None when it was inspected at runtime)
+    pub file: Option<PathBuf>,
 }
 
-/// Try to get signature from AST parsing
-pub fn try_ast_signature(py: Python, import_path: &str, quiet: bool) -> Option<SignatureResult> {
-    // Parse the full specification first
-    let (package_override, path_without_package, version) =
-        crate::utils::parse_full_spec(import_path);
-
-    // Parse the import path to extract module and object name
-    let (module_path, object_name) = if path_without_package.contains(':') {
-        let parts: Vec<&str> = path_without_package.split(':').collect();
-        if parts.len() != 2 {
-            return None;
+impl SignatureResult {
+    fn found(sig: FunctionSignature) -> Self {
+        Self::found_in(sig, None)
+    }
+
+    fn found_in(sig: FunctionSignature, file: Option<&Path>) -> Self {
+        Self {
+            formatted_output: format_signature_display(&sig),
+            signature: Some(sig),
+            candidates: Vec::new(),
+            file: file.map(Path::to_path_buf),
         }
-        (parts[0], parts[1])
-    } else if let Some(dot_pos) = path_without_package.rfind('.') {
-        (
-            &path_without_package[..dot_pos],
-            &path_without_package[dot_pos + 1..],
-        )
-    } else {
-        return None;
-    };
+    }
+}
 
-    // Helper function to try exploration and get signature
-    let try_get_signature = |py: Python| -> Option<FunctionSignature> {
-        // For builtin modules (implemented in C), we can't extract signatures from filesystem
-        if crate::stdlib::is_builtin_module(module_path) {
-            return None;
+/// Pick the module/object split of a dot-only spec that resolves on disk
+/// Split points are tried right to left, so the longest importable module wins:
+/// `pkg.sub.func` is `pkg.sub:func` when `pkg/sub.py` defines `func`, and only
+/// falls back to `pkg:sub.func` (e.g., a class `sub` in `pkg`) otherwise.
+/// Returns None when nothing resolves, leaving the last-dot split for the fallbacks.
+pub fn resolve_dotted_spec(py: Python, import_path: &str, verbosity: Verbosity) -> Option<String> {
+    let candidates = crate::utils::dotted_object_specs(import_path);
+    // A single split point has nothing to choose between
+    if candidates.len() < 2 {
+        return None;
+    }
+    candidates.into_iter().find(|candidate| {
+        let Some((_, module_path, object_path, _)) = crate::utils::parse_object_spec(candidate)
+        else {
+            return false;
+        };
+        let explorer = crate::explorer::ModuleTreeExplorer::new(module_path.to_string(), 0);
+        let Ok(module_info) = explorer.explore_module_pure_filesystem(py, module_path) else {
+            return false;
+        };
+        let found = module_info.signatures.contains_key(object_path)
+            || module_info.classes.iter().any(|name| name == object_path)
+            || module_info.functions.iter().any(|name| name == object_path);
+        if found {
+            verbosity.detail(&format!("resolved {} as {}", import_path, candidate));
         }
+        found
+    })
+}
 
-        // First try the exact module path
-        let explorer = crate::explorer::ModuleTreeExplorer::new(module_path.to_string(), 2);
-        if let Ok(module_info) = explorer.explore_module_pure_filesystem(py, module_path) {
-            if let Some(sig) = module_info.signatures.get(object_name) {
-                return Some(sig.clone());
-            }
+/// The signature of `object_name` in an importable module, found without importing it
+/// Looks in the module's sources (including any decorator class), then its root package.
+fn find_local_signature(
+    py: Python,
+    module_path: &str,
+    object_name: &str,
+    verbosity: Verbosity,
+) -> Option<SignatureResult> {
+    // For builtin modules (implemented in C), we can't extract signatures from filesystem
+    if crate::stdlib::is_builtin_module(module_path) {
+        return None;
+    }
 
-            // Check if it's in __all__ and search recursively
-            if let Some(all_exports) = &module_info.all_exports {
-                if all_exports.contains(&object_name.to_string()) {
-                    // Use the recursive search function to find it anywhere in the tree
-                    if let Some(sig) = find_signature_recursive(&module_info, object_name) {
-                        return Some(sig.clone());
-                    }
+    // First try the exact module path
+    let explorer = crate::explorer::ModuleTreeExplorer::new(module_path.to_string(), 2);
+    if let Ok(module_info) = explorer.explore_module_pure_filesystem(py, module_path) {
+        let file = module_info.file.as_deref();
+        if let Some(sig) = module_info.signatures.get(object_name) {
+            return Some(SignatureResult::found_in(sig.clone(), file));
+        }
+
+        // Check if it's in __all__ and search recursively
+        if let Some(all_exports) = &module_info.all_exports {
+            if all_exports.contains(&object_name.to_string()) {
+                // Find it anywhere in the tree
+                let definitions = find_definitions(&module_info, module_path, object_name);
+                let result =
+                    choose_definition(py, module_path, object_name, &definitions, verbosity);
+                if result.is_some() {
+                    return result;
                 }
             }
+        }
+        
+        // NEW: Check for decorator pattern (flow -> FlowDecorator.__call__)
+        let decorator_class = format!("{}Decorator", 
+            object_name.chars().next().unwrap().to_uppercase().collect::<String>() 
+            + &object_name[1..]);
+        
+        debug_log!("Checking for decorator class: {} in module {}", decorator_class, module_path);
+        if module_info.classes.contains(&decorator_class) {
+            debug_log!("🎯 Found decorator class: {}", decorator_class);
             
-            // NEW: Check for decorator pattern (flow -> FlowDecorator.__call__)
-            let decorator_class = format!("{}Decorator", 
-                object_name.chars().next().unwrap().to_uppercase().collect::<String>() 
-                + &object_name[1..]);
+            // Try __call__ first
+            let call_name = format!("{}.__call__", decorator_class);
+            if let Some(sig) = module_info.signatures.get(&call_name) {
+                debug_log!("Found decorator __call__ signature");
+                return Some(SignatureResult::found_in(sig.clone(), file));
+            }
             
-            debug_log!("Checking for decorator class: {} in module {}", decorator_class, module_path);
-            if module_info.classes.contains(&decorator_class) {
-                debug_log!("🎯 Found decorator class: {}", decorator_class);
-                
-                // Try __call__ first
-                let call_name = format!("{}.__call__", decorator_class);
-                if let Some(sig) = module_info.signatures.get(&call_name) {
-                    debug_log!("Found decorator __call__ signature");
-                    return Some(sig.clone());
-                }
-                
-                // Try __init__ as fallback
-                let init_name = format!("{}.__init__", decorator_class);
-                if let Some(sig) = module_info.signatures.get(&init_name) {
-                    debug_log!("Found decorator __init__ signature");
-                    return Some(sig.clone());
-                }
-                
-                // Create smart signature for known decorators
-                debug_log!("Creating smart signature for {} decorator", object_name);
-                let smart_parameters = match object_name {
-                    "flow" => "func=None, *, name=None, description=None, version=None, flow_run_name=None, task_runner=None, timeout_seconds=None, validate_parameters=True, persist_result=None, result_storage=None, result_serializer=None, cache_policy=None, cache_expiration=None, cache_key_fn=None, on_completion=None, on_failure=None, on_cancellation=None, on_crashed=None, on_running=None, retries=None, retry_delay_seconds=None, retry_jitter_factor=None, log_prints=None".to_string(),
-                    "task" => "func=None, *, name=None, description=None, tags=None, version=None, cache_policy=None, cache_expiration=None, cache_key_fn=None, task_run_name=None, retries=None, retry_delay_seconds=None, retry_jitter_factor=None, persist_result=None, result_storage=None, result_serializer=None, timeout_seconds=None, log_prints=None, refresh_cache=None, on_completion=None, on_failure=None".to_string(),
-                    _ => "func=None, *args, **kwargs".to_string(),
-                };
-                
-                return Some(crate::module_info::FunctionSignature {
-                    name: object_name.to_string(),
-                    parameters: smart_parameters,
-                    return_type: Some("Decorated function or decorator".to_string()),
-                });
+            // Try __init__ as fallback
+            let init_name = format!("{}.__init__", decorator_class);
+            if let Some(sig) = module_info.signatures.get(&init_name) {
+                debug_log!("Found decorator __init__ signature");
+                return Some(SignatureResult::found_in(sig.clone(), file));
             }
+            
+            // Create smart signature for known decorators
+            debug_log!("Creating smart signature for {} decorator", object_name);
+            let smart_parameters = match object_name {
+                "flow" => "func=None, *, name=None, description=None, version=None, flow_run_name=None, task_runner=None, timeout_seconds=None, validate_parameters=True, persist_result=None, result_storage=None, result_serializer=None, cache_policy=None, cache_expiration=None, cache_key_fn=None, on_completion=None, on_failure=None, on_cancellation=None, on_crashed=None, on_running=None, retries=None, retry_delay_seconds=None, retry_jitter_factor=None, log_prints=None".to_string(),
+                "task" => "func=None, *, name=None, description=None, tags=None, version=None, cache_policy=None, cache_expiration=None, cache_key_fn=None, task_run_name=None, retries=None, retry_delay_seconds=None, retry_jitter_factor=None, persist_result=None, result_storage=None, result_serializer=None, timeout_seconds=None, log_prints=None, refresh_cache=None, on_completion=None, on_failure=None".to_string(),
+                _ => "func=None, *args, **kwargs".to_string(),
+            };
+            
+            let sig = crate::module_info::FunctionSignature {
+                name: object_name.to_string(),
+                parameters: smart_parameters,
+                return_type: Some("Decorated function or decorator".to_string()),
+                source: None,
+                kwargs: None,
+                param_docs: None,
+            };
+            return Some(SignatureResult::found_in(sig, file));
         }
+    }
 
-        // If not found in the module, try the base package exploration
-        if module_path.contains('.') {
-            // Try the root package
-            let root_package = module_path.split('.').next().unwrap();
-            let explorer = crate::explorer::ModuleTreeExplorer::new(root_package.to_string(), 3);
-            if let Ok(root_info) = explorer.explore_module_pure_filesystem(py, root_package) {
-                // Search recursively for the object
-                if let Some(sig) = find_signature_recursive(&root_info, object_name) {
-                    return Some(sig.clone());
-                }
-            }
-        }
+    // If not found in the module, search the whole root package
+    let root_package = module_path.split('.').next().unwrap();
+    let explorer = crate::explorer::ModuleTreeExplorer::new(root_package.to_string(), 3);
+    if let Ok(root_info) = explorer.explore_module_pure_filesystem(py, root_package) {
+        let definitions = find_definitions(&root_info, root_package, object_name);
+        return choose_definition(py, module_path, object_name, &definitions, verbosity);
+    }
+
+    None
+}
+
+/// Try to get signature from AST parsing
+pub fn try_ast_signature(
+    py: Python,
+    import_path: &str,
+    verbosity: Verbosity,
+) -> Option<SignatureResult> {
+    // Dot syntax is ambiguous: settle on a module/object split before anything else
+    let resolved_path = resolve_dotted_spec(py, import_path, verbosity);
+    let import_path = resolved_path.as_deref().unwrap_or(import_path);
+
+    // Parse the full specification: [package::]module:object[@version]
+    let (package_override, module_path, object_name, version) =
+        crate::utils::parse_object_spec(import_path)?;
 
+    // The module may be named after an installed distribution (beautifulsoup4 -> bs4)
+    let local_candidate = if package_override.is_none() {
+        crate::dist_metadata::local_import_candidates(py, module_path)
+            .into_iter()
+            .next()
+    } else {
         None
     };
+    if let Some(candidate) = &local_candidate {
+        verbosity.detail(&format!("{} is installed as '{}'", module_path, candidate));
+    }
+    let requested_module = module_path;
+    let module_path = local_candidate.as_deref().unwrap_or(module_path);
+    debug_log!(
+        "Resolving signature for {}:{} (package override: {:?}, version: {:?})",
+        module_path, object_name, package_override, version
+    );
+
+    let try_get_signature =
+        |py: Python| find_local_signature(py, module_path, object_name, verbosity);
 
     // First try direct filesystem exploration
-    if let Some(sig) = try_get_signature(py) {
-        return Some(SignatureResult {
-            signature: Some(sig.clone()),
-            formatted_output: format_signature_display(&sig),
-        });
+    if let Some(result) = try_get_signature(py) {
+        return Some(result);
     }
 
     // If not found directly, try following import chains for known patterns
     // Use the import chain resolver which now includes smart signatures
     let import_resolver = ImportChainResolver::new();
-    if let Some(sig) = import_resolver.resolve_symbol_signature(py, module_path, object_name) {
-        return Some(SignatureResult {
-            signature: Some(sig.clone()),
-            formatted_output: format_signature_display(&sig),
-        });
+    if let Some((sig, file)) = import_resolver.resolve_symbol_definition(py, module_path, object_name, verbosity) {
+        return Some(SignatureResult::found_in(sig, file.as_deref()));
     }
 
     // Check if this is a stdlib module - if so, don't try to download
@@ -384,90 +739,290 @@ pub fn try_ast_signature(py: Python, import_path: &str, quiet: bool) -> Option<S
         return None;
     }
 
-    // If not found and not stdlib, try downloading the package
-    let download_package = if let Some(pkg) = package_override {
-        pkg
-    } else {
-        crate::utils::extract_base_package(module_path)
-    };
-
-    let download_spec = if let Some(v) = version {
-        format!("{}@{}", download_package, v)
-    } else {
-        download_package.to_string()
-    };
-
-    // Try downloading (message is printed by try_download_and_import)
+    // If not found and not stdlib, try downloading the package's sources
+    // (message is printed by try_download_and_import)
     // Need to capture the result inside the closure while sys.path is modified
     let mut download_result = None;
-    if let Ok(()) = crate::utils::try_download_and_import(py, &download_spec, quiet, || {
+    let download = crate::utils::try_download_module(py, requested_module, package_override, version, true, verbosity, |_| {
+        // A namespace package's candidate distributions may not contain the module
+        if !crate::utils::provides_module(py, module_path) {
+            return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(format!(
+                "No module named '{}'",
+                module_path
+            )));
+        }
+
         // Try direct signature first
         download_result = try_get_signature(py);
         
         // If not found, try import chain resolver
         if download_result.is_none() {
             let resolver = ImportChainResolver::new();
-            if let Some(sig) = resolver.resolve_symbol_signature(py, module_path, object_name) {
-                download_result = Some(sig);
+            if let Some((sig, file)) = resolver.resolve_symbol_definition(py, module_path, object_name, verbosity) {
+                download_result = Some(SignatureResult::found_in(sig, file.as_deref()));
             }
         }
         
         // Last resort: try to import and inspect the actual object
         if download_result.is_none() {
-            debug_log!("Trying direct import inspection for {}:{}", module_path, object_name);
-            if let Ok(module) = py.import(module_path) {
-                if let Ok(obj) = module.getattr(object_name) {
-                    // Check if it's callable and has __call__
-                    if obj.is_callable() {
-                        // Try to get the signature from the callable object
-                        if let Ok(inspect) = py.import("inspect") {
-                            if let Ok(sig_obj) = inspect.call_method1("signature", (&obj,)) {
-                                if let Ok(sig_str) = sig_obj.str() {
-                                    // Parse the signature string into our format
-                                    let sig_string = sig_str.to_string();
-                                    debug_log!("Got signature from inspect: {}", sig_string);
-                                    
-                                    // Create a simple signature from the inspect result
-                                    download_result = Some(FunctionSignature {
-                                        name: object_name.to_string(),
-                                        parameters: sig_string.trim_start_matches('(').trim_end_matches(')').to_string(),
-                                        return_type: None, // Could parse from annotations
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            download_result =
+                inspect_runtime_signature(py, module_path, object_name).map(SignatureResult::found);
         }
         Ok(())
-    }) {
-        if let Some(sig) = download_result {
-            return Some(SignatureResult {
-                signature: Some(sig.clone()),
-                formatted_output: format_signature_display(&sig),
-            });
+    });
+
+    match download {
+        Ok(()) => {
+            if download_result.is_some() {
+                return download_result;
+            }
+        }
+        Err(e) => {
+            let config = DisplayConfig::get();
+            verbosity.notice(&format!(
+                "{} Could not download '{}': {}",
+                colorize("⚠️ ", &config.color_scheme.warning_color, config),
+                requested_module,
+                e
+            ));
         }
     }
 
     None
 }
 
+/// Import an object and read its signature at runtime
+/// Falls back to a docstring-embedded signature when `inspect.signature` fails
+fn inspect_runtime_signature(
+    py: Python,
+    module_path: &str,
+    object_name: &str,
+) -> Option<FunctionSignature> {
+    // Importing here would load the current interpreter's copy, not the target's
+    if crate::interpreter::is_foreign() {
+        return None;
+    }
+    debug_log!("Trying direct import inspection for {}:{}", module_path, object_name);
+    let module = py.import(module_path).ok()?;
+    let obj = module.getattr(object_name).ok()?;
+    inspect_object_signature(py, &obj, object_name)
+}
+
+/// Read a live object's signature with `inspect`, or from its docstring
+fn inspect_object_signature(
+    py: Python,
+    obj: &Bound<'_, PyAny>,
+    object_name: &str,
+) -> Option<FunctionSignature> {
+    if !obj.is_callable() {
+        return None;
+    }
+
+    let inspect = py.import("inspect").ok()?;
+    match inspect.call_method1("signature", (obj,)) {
+        Ok(sig_obj) => {
+            // Parse the signature string into our format
+            let sig_string = sig_obj.str().ok()?.to_string();
+            debug_log!("Got signature from inspect: {}", sig_string);
+
+            // Create a simple signature from the inspect result
+            Some(FunctionSignature {
+                name: object_name.to_string(),
+                parameters: sig_string.trim_start_matches('(').trim_end_matches(')').to_string(),
+                return_type: None, // Could parse from annotations
+                source: None,
+                kwargs: None,
+                param_docs: None,
+            })
+        }
+        Err(_) => {
+            // C extensions often only document their signature in __doc__
+            let doc: String = obj.getattr("__doc__").ok()?.extract().ok()?;
+            let sig = parse_docstring_signature(&doc, object_name)?;
+            debug_log!("Got signature from docstring: {}({})", sig.name, sig.parameters);
+            Some(sig)
+        }
+    }
+}
+
+/// Where a live function, class or method is defined, as (module, qualified name)
+/// Decorators are unwrapped through `__wrapped__` and methods through `__func__`. None for
+/// objects without a static location, e.g. those defined inside a function.
+pub fn object_location(obj: &Bound<'_, PyAny>) -> PyResult<Option<(String, String)>> {
+    let mut target = obj.clone();
+    // inspect.unwrap's guard against __wrapped__ cycles
+    for _ in 0..100 {
+        let next = match target.getattr("__func__") {
+            Ok(func) => func,
+            Err(_) => match target.getattr("__wrapped__") {
+                Ok(wrapped) => wrapped,
+                Err(_) => break,
+            },
+        };
+        target = next;
+    }
+
+    let Ok(qualname) = target.getattr("__qualname__") else {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "sig_of needs a function, class or method, not {}",
+            obj.get_type().name()?
+        )));
+    };
+    let qualname: String = qualname.extract()?;
+    let module: Option<String> = target
+        .getattr("__module__")
+        .ok()
+        .and_then(|module| module.extract().ok());
+    Ok(module
+        .filter(|module| module != "__main__")
+        .filter(|_| !qualname.contains("<locals>"))
+        .map(|module| (module, qualname)))
+}
+
+/// The signature of a live object
+/// Resolved statically from its defining module's sources, falling back to runtime inspection.
+pub fn live_object_signature(
+    py: Python,
+    obj: &Bound<'_, PyAny>,
+    verbosity: Verbosity,
+) -> PyResult<Option<FunctionSignature>> {
+    let location = object_location(obj)?;
+    if let Some((module_path, qualname)) = &location {
+        verbosity.detail(&format!("resolved object as {}:{}", module_path, qualname));
+        let result = find_local_signature(py, module_path, qualname, verbosity);
+        if let Some(sig) = result.and_then(|result| result.signature) {
+            return Ok(Some(sig));
+        }
+        let resolver = ImportChainResolver::new();
+        if let Some(sig) = resolver.resolve_symbol_signature(py, module_path, qualname, verbosity) {
+            return Ok(Some(sig));
+        }
+    }
+    verbosity.detail("no static signature found, inspecting the object at runtime");
+    let name = match &location {
+        Some((_, qualname)) => qualname.rsplit('.').next().unwrap_or(qualname).to_string(),
+        None => obj
+            .getattr("__name__")
+            .and_then(|name| name.extract())
+            .unwrap_or_default(),
+    };
+    Ok(inspect_object_signature(py, obj, &name))
+}
+
+/// Parse a leading `name(...)` line from a docstring
+/// e.g., "concatenate((a1, a2, ...), axis=0, out=None)\n\nJoin a sequence..."
+/// The call may span several lines; an optional `-> type` becomes the return type.
+fn parse_docstring_signature(doc: &str, object_name: &str) -> Option<FunctionSignature> {
+    let (_name, parameters, return_type) = parse_call_signature(doc)?;
+    Some(FunctionSignature {
+        name: object_name.to_string(),
+        parameters,
+        return_type,
+        source: Some("docstring".to_string()),
+        kwargs: None,
+        param_docs: None,
+    })
+}
+
+/// Split a compact `name(params) -> return` string into its parts
+/// Returns None unless the text starts with a (possibly dotted) name and a balanced call.
+pub fn parse_call_signature(text: &str) -> Option<(String, String, Option<String>)> {
+    let text = text.trim_start();
+    let open = text.find('(')?;
+    let name = text[..open].trim();
+    // Accept "name(" or "module.name(" but nothing else before the paren
+    let short_name = name.rsplit('.').next()?;
+    if short_name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+    {
+        return None;
+    }
+
+    // Find the matching close paren
+    let mut depth = 0;
+    let mut close = None;
+    for (i, ch) in text[open..].char_indices() {
+        match ch {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+
+    // Collapse wrapped continuation lines into single spaces
+    let parameters = text[open + 1..close]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let rest = text[close + 1..].lines().next().unwrap_or("").trim();
+    let return_type = rest
+        .strip_prefix("->")
+        .map(|ret| ret.trim().to_string())
+        .filter(|ret| !ret.is_empty());
+
+    Some((short_name.to_string(), parameters, return_type))
+}
+
+/// A "defined in <file>" line linking to the file a signature was resolved from
+/// Only shown when terminal hyperlinks are enabled (the path is the point of the link),
+/// and not for files since removed (e.g., a download's temporary directory).
+pub fn defined_in_footer(path: &Path) -> Option<String> {
+    let config = DisplayConfig::get();
+    if !config.hyperlinks || !path.exists() {
+        return None;
+    }
+    let label = colorize(
+        &path.display().to_string(),
+        &config.color_scheme.module_color,
+        config,
+    );
+    Some(format!(
+        "{}{}",
+        colorize("defined in ", &config.color_scheme.tree_color, config),
+        hyperlink(&label, path, config)
+    ))
+}
+
+/// The definitions a re-export was picked from, when several modules define the name
+pub fn candidates_footer(candidates: &[String]) -> String {
+    let config = DisplayConfig::get();
+    format!(
+        "{}{}",
+        colorize(
+            &format!("re-exported one of {} definitions: ", candidates.len()),
+            &config.color_scheme.tree_color,
+            config
+        ),
+        colorize(
+            &candidates.join(", "),
+            &config.color_scheme.module_color,
+            config
+        )
+    )
+}
+
 /// Display a function signature
 #[allow(dead_code)]
-pub fn display_signature(py: Python, import_path: &str, quiet: bool) -> PyResult<String> {
+pub fn display_signature(py: Python, import_path: &str, verbosity: Verbosity) -> PyResult<String> {
     // First try to get signature from AST
-    if let Some(result) = try_ast_signature(py, import_path, quiet) {
+    if let Some(result) = try_ast_signature(py, import_path, verbosity) {
         return Ok(result.formatted_output);
     }
 
     // If AST parsing didn't find it, return a simple message
     let config = DisplayConfig::get();
-    let object_name = if import_path.contains(':') {
-        import_path.split(':').last().unwrap_or(import_path)
-    } else {
-        import_path.split('.').last().unwrap_or(import_path)
-    };
+    let object_name = crate::utils::parse_object_spec(import_path)
+        .map(|(_, _, object_name, _)| object_name)
+        .unwrap_or(import_path);
 
     Ok(format!(
         "{} {} (signature not available)",
@@ -479,3 +1034,86 @@ pub fn display_signature(py: Python, import_path: &str, quiet: bool) -> PyResult
         colorize(object_name, &config.color_scheme.signature_color, config)
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature(parameters: &str, return_type: Option<&str>) -> FunctionSignature {
+        FunctionSignature {
+            name: "flow".to_string(),
+            parameters: parameters.to_string(),
+            return_type: return_type.map(str::to_string),
+            source: None,
+            kwargs: None,
+            param_docs: None,
+        }
+    }
+
+    #[test]
+    fn test_compact_single_line_without_width() {
+        let sig = signature("name: str, retries: int=0", Some("Flow"));
+        assert_eq!(
+            format_signature_compact(&sig, None),
+            "flow(name: str, retries: int=0) -> Flow"
+        );
+    }
+
+    #[test]
+    fn test_compact_wraps_after_commas() {
+        let sig = signature("name: str, version: str, retries: int=0", Some("Flow"));
+        assert_eq!(
+            format_signature_compact(&sig, Some(26)),
+            "flow(name: str,\n     version: str,\n     retries: int=0) -> Flow"
+        );
+    }
+
+    #[test]
+    fn test_parse_docstring_signature() {
+        let doc = "concatenate((a1, a2, ...), axis=0, out=None, dtype=None,\n            casting=\"same_kind\")\n\nJoin a sequence of arrays.";
+        let sig = parse_docstring_signature(doc, "concatenate").unwrap();
+        assert_eq!(sig.name, "concatenate");
+        assert_eq!(
+            sig.parameters,
+            "(a1, a2, ...), axis=0, out=None, dtype=None, casting=\"same_kind\""
+        );
+        assert_eq!(sig.return_type, None);
+        assert_eq!(sig.source.as_deref(), Some("docstring"));
+    }
+
+    #[test]
+    fn test_parse_docstring_signature_return_and_prose() {
+        let sig = parse_docstring_signature("sqrt(x) -> float\n", "sqrt").unwrap();
+        assert_eq!(sig.return_type.as_deref(), Some("float"));
+        assert!(parse_docstring_signature("Return the square root (of x).", "sqrt").is_none());
+    }
+
+    fn annotation(source: &str) -> String {
+        let parsed = ruff_python_parser::parse_expression(source).unwrap();
+        format_annotation(parsed.expr())
+    }
+
+    #[test]
+    fn test_forward_references_render_unquoted() {
+        assert_eq!(annotation("'Flow'"), "Flow");
+        assert_eq!(annotation("\"list[Run]\""), "list[Run]");
+        assert_eq!(annotation("dict[str, 'Run'] | None"), "dict[str, Run] | None");
+        assert_eq!(annotation("'Flow' 'Run'"), "FlowRun");
+        assert_eq!(annotation("'not valid['"), "not valid[");
+    }
+
+    #[test]
+    fn test_literal_strings_keep_quotes() {
+        assert_eq!(annotation("Literal['r', 'w']"), "Literal['r', 'w']");
+        assert_eq!(annotation("typing.Literal['r'] | None"), "typing.Literal['r'] | None");
+        assert_eq!(annotation("'Literal[\"r\"]'"), "Literal['r']");
+        assert_eq!(annotation("Annotated['Run', 'doc']"), "Annotated[Run, 'doc']");
+    }
+
+    #[test]
+    fn test_wrap_parameter_breaks_unions_and_defaults() {
+        let lines = wrap_parameter("timeout: int | float | None=None", 20, 18);
+        assert_eq!(lines, vec!["timeout: int | float", "| None=None"]);
+        assert_eq!(wrap_parameter("x: int", 20, 18), vec!["x: int"]);
+    }
+}
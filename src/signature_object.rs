@@ -0,0 +1,261 @@
+use crate::module_info::FunctionSignature;
+use crate::signature::{format_signature_compact, split_parameters};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::Serialize;
+
+/// A single parameter, with its kind named as in `inspect.Parameter`
+#[pyclass(get_all, frozen)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Parameter {
+    pub name: String,
+    pub kind: String, // "positional_only", "positional_or_keyword", "var_positional", "keyword_only", "var_keyword"
+    pub annotation: Option<String>,
+    pub default: Option<String>,
+}
+
+#[pymethods]
+impl Parameter {
+    fn __repr__(&self) -> String {
+        format!("<Parameter {} ({})>", self.to_text(), self.kind)
+    }
+
+    fn __str__(&self) -> String {
+        self.to_text()
+    }
+
+    /// An `inspect.Parameter` with a string annotation and, when it is a literal, the default
+    fn to_inspect_parameter(&self, py: Python) -> PyResult<PyObject> {
+        let class = py.import("inspect")?.getattr("Parameter")?;
+        let empty = class.getattr("empty")?;
+        let kwargs = PyDict::new(py);
+        let annotation = match &self.annotation {
+            Some(annotation) => annotation.into_pyobject(py)?.into_any(),
+            None => empty.clone(),
+        };
+        kwargs.set_item("annotation", annotation)?;
+        let default = match &self.default {
+            // Defaults are source text; anything but a literal has no value without importing
+            Some(default) => py
+                .import("ast")?
+                .call_method1("literal_eval", (default,))
+                .unwrap_or(empty),
+            None => empty,
+        };
+        kwargs.set_item("default", default)?;
+        let kind = class.getattr(self.kind.to_uppercase().as_str())?;
+        Ok(class.call((&self.name, kind), Some(&kwargs))?.unbind())
+    }
+}
+
+impl Parameter {
+    /// Render as written in a signature, e.g. "*args: int" or "retries: int=0"
+    pub fn to_text(&self) -> String {
+        let prefix = match self.kind.as_str() {
+            "var_positional" => "*",
+            "var_keyword" => "**",
+            _ => "",
+        };
+        let mut text = format!("{}{}", prefix, self.name);
+        if let Some(annotation) = &self.annotation {
+            text.push_str(&format!(": {}", annotation));
+        }
+        if let Some(default) = &self.default {
+            text.push_str(&format!("={}", default));
+        }
+        text
+    }
+}
+
+/// A resolved signature with structured parameters
+#[pyclass(name = "Signature", frozen)]
+#[derive(Clone, Debug, Serialize)]
+pub struct SignatureObject {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub parameters: Vec<Parameter>,
+    #[pyo3(get)]
+    pub return_type: Option<String>,
+    #[pyo3(get)]
+    pub source: Option<String>,
+    #[serde(skip)]
+    signature: FunctionSignature,
+}
+
+#[pymethods]
+impl SignatureObject {
+    /// The compact one-line form, e.g. "loads(s, *, cls=None, **kw)"
+    fn __str__(&self) -> String {
+        format_signature_compact(&self.signature, None)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Signature {}>", self.__str__())
+    }
+
+    /// Rich display in notebooks
+    fn _repr_html_(&self) -> String {
+        crate::html_formatter::format_signature_html(&self.signature)
+    }
+
+    /// An `inspect.Signature` built from the structured parameters
+    /// Annotations stay strings; defaults that are not literals become `Parameter.empty`.
+    fn to_inspect_signature(&self, py: Python) -> PyResult<PyObject> {
+        let class = py.import("inspect")?.getattr("Signature")?;
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|param| param.to_inspect_parameter(py))
+            .collect::<PyResult<Vec<_>>>()?;
+        let kwargs = PyDict::new(py);
+        let return_annotation = match &self.return_type {
+            Some(return_type) => return_type.into_pyobject(py)?.into_any(),
+            None => class.getattr("empty")?,
+        };
+        kwargs.set_item("return_annotation", return_annotation)?;
+        // An unevaluated default can leave a required parameter after a defaulted one
+        kwargs.set_item("__validate_parameters__", false)?;
+        Ok(class.call((parameters,), Some(&kwargs))?.unbind())
+    }
+}
+
+impl SignatureObject {
+    pub fn new(signature: FunctionSignature) -> Self {
+        Self {
+            name: signature.name.clone(),
+            parameters: parse_parameters(&signature.parameters),
+            return_type: signature.return_type.clone(),
+            source: signature.source.clone(),
+            signature,
+        }
+    }
+}
+
+/// Parse a parameter list (as produced by `format_parameters`) into structured parameters
+/// The `/` and `*` markers set the kinds of the surrounding parameters and are dropped.
+pub fn parse_parameters(parameters: &str) -> Vec<Parameter> {
+    let mut result: Vec<Parameter> = Vec::new();
+    let mut keyword_only = false;
+
+    for raw in split_parameters(parameters) {
+        match raw.as_str() {
+            "" => continue,
+            "/" => {
+                // Everything before "/" is positional-only
+                for param in result.iter_mut() {
+                    param.kind = "positional_only".to_string();
+                }
+                continue;
+            }
+            "*" => {
+                keyword_only = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        let (kind, rest) = if let Some(rest) = raw.strip_prefix("**") {
+            ("var_keyword", rest)
+        } else if let Some(rest) = raw.strip_prefix('*') {
+            keyword_only = true;
+            ("var_positional", rest)
+        } else if keyword_only {
+            ("keyword_only", raw.as_str())
+        } else {
+            ("positional_or_keyword", raw.as_str())
+        };
+
+        let (head, default) = match find_top_level(rest, '=') {
+            Some(pos) => (&rest[..pos], Some(rest[pos + 1..].trim().to_string())),
+            None => (rest, None),
+        };
+        let (name, annotation) = match find_top_level(head, ':') {
+            Some(pos) => (&head[..pos], Some(head[pos + 1..].trim().to_string())),
+            None => (head, None),
+        };
+
+        result.push(Parameter {
+            name: name.trim().to_string(),
+            kind: kind.to_string(),
+            annotation,
+            default,
+        });
+    }
+
+    result
+}
+
+/// Find the first occurrence of `target` outside brackets and quotes
+fn find_top_level(text: &str, target: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '\'' | '"' if quote == Some(ch) => quote = None,
+            '\'' | '"' if quote.is_none() => quote = Some(ch),
+            _ if quote.is_some() => {}
+            '[' | '(' | '{' => depth += 1,
+            ']' | ')' | '}' => depth -= 1,
+            _ if ch == target && depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str, kind: &str, annotation: Option<&str>, default: Option<&str>) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            annotation: annotation.map(str::to_string),
+            default: default.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_parameters_kinds() {
+        let params = parse_parameters("a, /, b: int=1, *args, c: dict[str, int]={}, **kw");
+        assert_eq!(
+            params,
+            vec![
+                param("a", "positional_only", None, None),
+                param("b", "positional_or_keyword", Some("int"), Some("1")),
+                param("args", "var_positional", None, None),
+                param("c", "keyword_only", Some("dict[str, int]"), Some("{}")),
+                param("kw", "var_keyword", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_parameters_bare_star() {
+        let params = parse_parameters("s, *, cls=None");
+        assert_eq!(params[1], param("cls", "keyword_only", None, Some("None")));
+    }
+
+    #[test]
+    fn test_parse_parameters_ignores_separators_in_literals() {
+        let params = parse_parameters("mode: Literal['a=b', 'c:d']='a=b'");
+        assert_eq!(
+            params,
+            vec![param(
+                "mode",
+                "positional_or_keyword",
+                Some("Literal['a=b', 'c:d']"),
+                Some("'a=b'")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parameter_round_trip() {
+        for text in ["x", "*args: int", "**kw", "retries: int=0"] {
+            assert_eq!(parse_parameters(text)[0].to_text(), text);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::requirement::normalize_name;
+use std::collections::BTreeMap;
+
+/// The PyPI simple index, used when the JSON API is unavailable
+pub const PYPI_SIMPLE_URL: &str = "https://pypi.org/simple/";
+
+/// Media types to request, preferring the PEP 691 JSON form over PEP 503 HTML
+pub const ACCEPT: &str =
+    "application/vnd.pypi.simple.v1+json, application/vnd.pypi.simple.v1+html;q=0.2, text/html;q=0.1";
+
+/// A downloadable file of a release
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseFile {
+    pub filename: String,
+    pub url: String,
+    pub sha256: Option<String>,
+}
+
+/// A custom index configured via `PRETTY_MOD_INDEX_URL` (or pip's `PIP_INDEX_URL`)
+pub fn configured_index_url() -> Option<String> {
+    ["PRETTY_MOD_INDEX_URL", "PIP_INDEX_URL"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// The project page of a simple index, e.g., "https://pypi.org/simple/typing-extensions/"
+pub fn project_url(index_url: &str, name: &str) -> String {
+    format!("{}/{}/", index_url.trim_end_matches('/'), normalize_name(name))
+}
+
+/// Read the `sha256=` hash from a URL fragment
+/// e.g., "https://host/pkg-1.0.tar.gz#sha256=abc" -> Some("abc")
+pub fn fragment_sha256(url: &str) -> Option<String> {
+    let (_, fragment) = url.split_once('#')?;
+    fragment
+        .split('&')
+        .find_map(|part| part.strip_prefix("sha256="))
+        .map(str::to_string)
+}
+
+/// Parse a PEP 691 JSON project page into files grouped by version (yanked files are skipped)
+pub fn parse_json_page(body: &str, page_url: &str) -> Option<BTreeMap<String, Vec<ReleaseFile>>> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    let files = json["files"].as_array()?;
+    let mut releases = BTreeMap::new();
+    for file in files {
+        let yanked = file["yanked"].as_bool().unwrap_or(false) || file["yanked"].is_string();
+        let (Some(filename), Some(url)) = (file["filename"].as_str(), file["url"].as_str()) else {
+            continue;
+        };
+        if yanked {
+            continue;
+        }
+        let url = resolve_url(page_url, url);
+        let sha256 = file["hashes"]["sha256"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| fragment_sha256(&url));
+        add_file(&mut releases, filename, url, sha256);
+    }
+    Some(releases)
+}
+
+/// Parse a PEP 503 HTML project page (a list of anchors) into files grouped by version
+pub fn parse_html_page(body: &str, page_url: &str) -> BTreeMap<String, Vec<ReleaseFile>> {
+    let mut releases = BTreeMap::new();
+    // ASCII lowercasing keeps byte offsets, so positions found in `lower` index `body`
+    let lower = body.to_ascii_lowercase();
+    let mut pos = 0;
+    while let Some(start) = find_anchor(&lower[pos..]) {
+        let tag_start = pos + start;
+        let Some(tag_len) = body[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &body[tag_start..tag_start + tag_len];
+        let text_start = tag_start + tag_len + 1;
+        let text_end = lower[text_start..]
+            .find("</a")
+            .map_or(body.len(), |end| text_start + end);
+        let text = body[text_start..text_end].trim();
+        pos = text_end;
+
+        if attribute(tag, "data-yanked").is_some() {
+            continue;
+        }
+        let Some(href) = attribute(tag, "href") else {
+            continue;
+        };
+        let url = resolve_url(page_url, &unescape(&href));
+        let filename = if text.is_empty() {
+            url.split('#')
+                .next()
+                .unwrap_or("")
+                .rsplit('/')
+                .next()
+                .unwrap_or("")
+                .to_string()
+        } else {
+            unescape(text)
+        };
+        let sha256 = fragment_sha256(&url);
+        add_file(&mut releases, &filename, url, sha256);
+    }
+    releases
+}
+
+/// The version encoded in a wheel or sdist filename
+/// e.g., "typing_extensions-4.12.2-py3-none-any.whl" -> "4.12.2", "toml-0.10.2.tar.gz" -> "0.10.2"
+pub fn version_from_filename(filename: &str) -> Option<String> {
+    if let Some(stem) = filename.strip_suffix(".whl") {
+        return stem.split('-').nth(1).map(str::to_string);
+    }
+    let stem = [".tar.gz", ".zip", ".tar.bz2", ".tgz"]
+        .iter()
+        .find_map(|ext| filename.strip_suffix(ext))?;
+    let (_, version) = stem.rsplit_once('-')?;
+    Some(version.to_string())
+}
+
+fn add_file(
+    releases: &mut BTreeMap<String, Vec<ReleaseFile>>,
+    filename: &str,
+    url: String,
+    sha256: Option<String>,
+) {
+    let Some(version) = version_from_filename(filename) else {
+        return;
+    };
+    releases.entry(version).or_default().push(ReleaseFile {
+        filename: filename.to_string(),
+        url,
+        sha256,
+    });
+}
+
+/// Position of the next `<a ` tag in lowercased text
+fn find_anchor(lower: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find("<a") {
+        let start = from + pos;
+        if lower[start + 2..].starts_with(|c: char| c.is_whitespace()) {
+            return Some(start);
+        }
+        from = start + 2;
+    }
+    None
+}
+
+/// Value of a (quoted or bare) attribute in an HTML tag
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search_from = 0;
+    while let Some(pos) = lower[search_from..].find(name) {
+        let start = search_from + pos;
+        search_from = start + name.len();
+        // Must be a whole attribute name, not a suffix of another
+        let preceded_by_space = tag[..start].ends_with(|c: char| c.is_whitespace());
+        if !preceded_by_space {
+            continue;
+        }
+        let after = tag[start + name.len()..].trim_start();
+        let Some(value) = after.strip_prefix('=') else {
+            // Boolean attribute, e.g., <a data-yanked href=...>
+            return Some(String::new());
+        };
+        let value = value.trim_start();
+        return match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let inner = &value[1..];
+                Some(inner[..inner.find(quote).unwrap_or(inner.len())].to_string())
+            }
+            _ => Some(
+                value
+                    .split(|c: char| c.is_whitespace() || c == '>')
+                    .next()
+                    .unwrap_or("")
+                    .to_string(),
+            ),
+        };
+    }
+    None
+}
+
+/// Decode the HTML entities that appear in index pages
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Resolve a possibly relative link against the page it appeared on
+fn resolve_url(page_url: &str, href: &str) -> String {
+    reqwest::Url::parse(page_url)
+        .and_then(|base| base.join(href))
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| href.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_from_filename() {
+        assert_eq!(
+            version_from_filename("typing_extensions-4.12.2-py3-none-any.whl").as_deref(),
+            Some("4.12.2")
+        );
+        assert_eq!(version_from_filename("toml-0.10.2.tar.gz").as_deref(), Some("0.10.2"));
+        assert_eq!(
+            version_from_filename("zope.interface-6.0.zip").as_deref(),
+            Some("6.0")
+        );
+        assert_eq!(version_from_filename("README.txt"), None);
+    }
+
+    #[test]
+    fn test_parse_html_page() {
+        let body = r#"<!DOCTYPE html>
+<html><body>
+<a href="../../packages/toml-0.10.1.tar.gz#sha256=926b612b">toml-0.10.1.tar.gz</a><br/>
+<A HREF="https://files.example.com/toml-0.10.2-py2.py3-none-any.whl#sha256=806143ae">toml-0.10.2-py2.py3-none-any.whl</A>
+<a href="toml-0.11.0.tar.gz" data-yanked="broken">toml-0.11.0.tar.gz</a>
+</body></html>"#;
+        let releases = parse_html_page(body, "https://mirror.example.com/simple/toml/");
+        assert_eq!(releases.keys().collect::<Vec<_>>(), vec!["0.10.1", "0.10.2"]);
+        assert_eq!(
+            releases["0.10.1"][0],
+            ReleaseFile {
+                filename: "toml-0.10.1.tar.gz".to_string(),
+                url: "https://mirror.example.com/packages/toml-0.10.1.tar.gz#sha256=926b612b"
+                    .to_string(),
+                sha256: Some("926b612b".to_string()),
+            }
+        );
+        assert_eq!(releases["0.10.2"][0].sha256.as_deref(), Some("806143ae"));
+    }
+
+    #[test]
+    fn test_parse_json_page() {
+        let body = r#"{
+  "meta": {"api-version": "1.1"},
+  "name": "toml",
+  "files": [
+    {"filename": "toml-0.10.2.tar.gz", "url": "/files/toml-0.10.2.tar.gz", "hashes": {"sha256": "b3bd"}},
+    {"filename": "toml-0.10.3.tar.gz", "url": "/files/toml-0.10.3.tar.gz", "hashes": {}, "yanked": true}
+  ]
+}"#;
+        let releases = parse_json_page(body, "https://mirror.example.com/simple/toml/").unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(
+            releases["0.10.2"][0].url,
+            "https://mirror.example.com/files/toml-0.10.2.tar.gz"
+        );
+        assert_eq!(releases["0.10.2"][0].sha256.as_deref(), Some("b3bd"));
+    }
+
+    #[test]
+    fn test_fragment_sha256_and_project_url() {
+        assert_eq!(
+            fragment_sha256("https://h/p-1.0.tar.gz#md5=x&sha256=abc").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(fragment_sha256("https://h/p-1.0.tar.gz"), None);
+        assert_eq!(
+            project_url("https://mirror.example.com/simple", "Typing_Extensions"),
+            "https://mirror.example.com/simple/typing-extensions/"
+        );
+    }
+}
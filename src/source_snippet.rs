@@ -0,0 +1,284 @@
+use crate::explorer::ModuleTreeExplorer;
+use crate::module_info::{relative_import_base, ImportInfo, ModuleInfo};
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use ruff_python_ast::Stmt;
+use ruff_python_parser::parse_module;
+use std::fs;
+
+/// Lines of a definition `sig --source` shows by default
+pub const DEFAULT_SOURCE_LINES: usize = 20;
+
+/// The most of a definition's source read, however long the function
+const MAX_SOURCE_BYTES: usize = 16 * 1024;
+
+/// Ends a definition cut short by `MAX_SOURCE_BYTES`
+const CAPPED_NOTE: &str = "# ... (source cut at the size limit)";
+
+/// How many re-exports are followed looking for the defining module
+const MAX_HOPS: usize = 8;
+
+/// The source of the function or class a `sig` path names, decorators included
+/// Only the first `max_lines` lines are kept (0 keeps them all); None unless the
+/// definition was found in a file, through re-exports if need be.
+pub fn source_snippet(
+    py: Python,
+    import_path: &str,
+    max_lines: usize,
+    verbosity: Verbosity,
+) -> Option<String> {
+    let resolved_path = crate::signature::resolve_dotted_spec(py, import_path, verbosity);
+    let import_path = resolved_path.as_deref().unwrap_or(import_path);
+    let (package_override, module_path, object_name, _) =
+        crate::utils::parse_object_spec(import_path)?;
+    let local_candidate = if package_override.is_none() {
+        crate::dist_metadata::local_import_candidates(py, module_path)
+            .into_iter()
+            .next()
+    } else {
+        None
+    };
+    let module_path = local_candidate.as_deref().unwrap_or(module_path);
+
+    let (path, source) = locate(py, module_path, object_name, MAX_HOPS)?;
+    verbosity.detail(&format!("source of {} from {}", object_name, path));
+    Some(truncate_lines(&source, max_lines))
+}
+
+/// The file defining `name` (e.g. "Client.get") as seen from `module_path`, and its source
+fn locate(py: Python, module_path: &str, name: &str, hops: usize) -> Option<(String, String)> {
+    let info = ModuleTreeExplorer::new(module_path.to_string(), 2)
+        .explore_module_pure_filesystem(py, module_path)
+        .ok()?;
+    if let Some(found) = defined_in(&info, name) {
+        return Some(found);
+    }
+
+    // Re-exported: follow the import to the module it came from
+    let (head, rest) = match name.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (name, None),
+    };
+    if let Some(import) = info.import_map.get(head) {
+        if hops == 0 {
+            return None;
+        }
+        let target = import_target(module_path, info.is_package(), import)?;
+        let target_name = match (&import.from_module, rest) {
+            (Some(_), Some(rest)) => format!("{}.{}", import.import_name, rest),
+            (Some(_), None) => import.import_name.clone(),
+            // `import pkg.mod` or `from . import mod`: the name is a module
+            (None, rest) => rest?.to_string(),
+        };
+        return locate(py, &target, &target_name, hops - 1);
+    }
+
+    // Defined somewhere in the package, as `sig` searches for `__all__` names
+    find_in_submodules(&info, head, name)
+}
+
+/// The module a name imported into `module_path` is looked up in
+/// For `import pkg.mod` and `from . import mod`, the module is the imported name itself.
+fn import_target(module_path: &str, is_package: bool, import: &ImportInfo) -> Option<String> {
+    match &import.from_module {
+        Some(_) => import.source_module(module_path, is_package),
+        None if import.level > 0 => Some(format!(
+            "{}.{}",
+            relative_import_base(module_path, is_package, import.level)?,
+            import.import_name
+        )),
+        None => Some(import.import_name.clone()),
+    }
+}
+
+/// The first submodule, at any depth, defining `name` (`head` being its class for a method)
+fn find_in_submodules(info: &ModuleInfo, head: &str, name: &str) -> Option<(String, String)> {
+    info.submodules.values().find_map(|submodule| {
+        let defines = submodule.signatures.contains_key(name)
+            || submodule.classes.iter().any(|class| class == head);
+        let found = if defines {
+            defined_in(submodule, name)
+        } else {
+            None
+        };
+        found.or_else(|| find_in_submodules(submodule, head, name))
+    })
+}
+
+/// The source of `name` when the module's own file defines it
+fn defined_in(info: &ModuleInfo, name: &str) -> Option<(String, String)> {
+    let path = info.file.as_deref()?;
+    let source = fs::read_to_string(path).ok()?;
+    let segment = definition_source(&source, name)?;
+    Some((path.display().to_string(), segment))
+}
+
+/// The source of a function or class defined in `source`, decorators included
+/// Dotted names look in class bodies, e.g. "Client.get"; a method is dedented to column 0.
+fn definition_source(source: &str, name: &str) -> Option<String> {
+    let body = parse_module(source).ok()?.into_syntax().body;
+    let (start, end) = find_definition(&body, &name.split('.').collect::<Vec<_>>())?;
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let capped = end - line_start > MAX_SOURCE_BYTES;
+    let mut end = end.min(line_start + MAX_SOURCE_BYTES);
+    while !source.is_char_boundary(end) {
+        end -= 1;
+    }
+    let segment = &source[line_start..end];
+    let indent = &segment[..segment.len() - segment.trim_start_matches([' ', '\t']).len()];
+    let mut lines: Vec<&str> = segment
+        .lines()
+        .map(|line| line.strip_prefix(indent).unwrap_or(line))
+        .collect();
+    if capped {
+        // The cap likely cut a line short
+        lines.pop();
+        lines.push(CAPPED_NOTE);
+    }
+    Some(lines.join("\n"))
+}
+
+/// The byte range of the definition at `path`, from its first decorator
+fn find_definition(stmts: &[Stmt], path: &[&str]) -> Option<(usize, usize)> {
+    let (name, rest) = path.split_first()?;
+    for stmt in stmts {
+        match stmt {
+            Stmt::FunctionDef(func_def) if func_def.name.as_str() == *name && rest.is_empty() => {
+                let start = func_def
+                    .decorator_list
+                    .first()
+                    .map_or(func_def.range.start(), |decorator| decorator.range.start());
+                return Some((usize::from(start), usize::from(func_def.range.end())));
+            }
+            Stmt::ClassDef(class_def) if class_def.name.as_str() == *name => {
+                if !rest.is_empty() {
+                    return find_definition(&class_def.body, rest);
+                }
+                let start = class_def
+                    .decorator_list
+                    .first()
+                    .map_or(class_def.range.start(), |decorator| decorator.range.start());
+                return Some((usize::from(start), usize::from(class_def.range.end())));
+            }
+            // As in `process_statement`, e.g. `if TYPE_CHECKING:`
+            Stmt::If(if_stmt) => {
+                let found = find_definition(&if_stmt.body, path).or_else(|| {
+                    if_stmt
+                        .elif_else_clauses
+                        .iter()
+                        .find_map(|clause| find_definition(&clause.body, path))
+                });
+                if found.is_some() {
+                    return found;
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Keep the first `max_lines` lines, noting how many were left out
+fn truncate_lines(source: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    if max_lines == 0 || lines.len() <= max_lines {
+        return source.to_string();
+    }
+    let omitted = lines.len() - max_lines;
+    format!(
+        "{}\n# ... ({} more {})",
+        lines[..max_lines].join("\n"),
+        omitted,
+        if omitted == 1 { "line" } else { "lines" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+import functools
+
+
+@functools.cache
+@staticmethod
+def cached(a, b=1):
+    return a + b
+
+
+class Client:
+    def __init__(self):
+        self.open = True
+
+    @property
+    def closed(self):
+        return not self.open
+";
+
+    #[test]
+    fn test_definition_source_includes_decorators() {
+        assert_eq!(
+            definition_source(SOURCE, "cached").unwrap(),
+            "@functools.cache\n@staticmethod\ndef cached(a, b=1):\n    return a + b"
+        );
+    }
+
+    #[test]
+    fn test_method_source_is_dedented() {
+        assert_eq!(
+            definition_source(SOURCE, "Client.closed").unwrap(),
+            "@property\ndef closed(self):\n    return not self.open"
+        );
+        assert!(definition_source(SOURCE, "Client.missing").is_none());
+        assert!(definition_source(SOURCE, "closed").is_none());
+    }
+
+    #[test]
+    fn test_enormous_definitions_are_capped() {
+        let body = "    x = 1\n".repeat(MAX_SOURCE_BYTES);
+        let source = format!("def huge():\n{}", body);
+        let segment = definition_source(&source, "huge").unwrap();
+        assert!(segment.len() <= MAX_SOURCE_BYTES + CAPPED_NOTE.len());
+        assert!(segment.ends_with(CAPPED_NOTE));
+    }
+
+    #[test]
+    fn test_truncate_lines() {
+        assert_eq!(truncate_lines("a\nb\nc", 2), "a\nb\n# ... (1 more line)");
+        assert_eq!(truncate_lines("a\nb\nc", 3), "a\nb\nc");
+        assert_eq!(truncate_lines("a\nb\nc", 0), "a\nb\nc");
+    }
+
+    #[test]
+    fn test_import_target() {
+        let info = ModuleInfo::from_source(
+            "from .core import Client\n\
+             from ..impl import f\n\
+             from . import mod\n\
+             from other.mod import g\n\
+             import json\n",
+            "api",
+        )
+        .unwrap();
+        let target = |module_path: &str, is_package: bool, name: &str| {
+            import_target(module_path, is_package, &info.import_map[name])
+        };
+
+        assert_eq!(target("pkg", true, "Client").as_deref(), Some("pkg.core"));
+        assert_eq!(
+            target("pkg.sub", false, "Client").as_deref(),
+            Some("pkg.core")
+        );
+        assert_eq!(
+            target("pkg.sub.api", false, "f").as_deref(),
+            Some("pkg.impl")
+        );
+        assert_eq!(target("pkg.sub", true, "f").as_deref(), Some("pkg.impl"));
+        assert_eq!(target("pkg.sub", false, "f"), None);
+        assert_eq!(target("pkg.sub", false, "mod").as_deref(), Some("pkg.mod"));
+        assert_eq!(target("pkg", true, "g").as_deref(), Some("other.mod"));
+        assert_eq!(target("pkg", true, "json").as_deref(), Some("json"));
+        assert_eq!(target("mod", false, "Client"), None);
+    }
+}
@@ -0,0 +1,311 @@
+use crate::check::normalize;
+use crate::config::{colorize, DisplayConfig};
+use crate::explorer::{module_source_path, ModuleTreeExplorer};
+use crate::module_info::{is_public_name, FunctionSignature, ModuleInfo};
+use crate::signature_object::{parse_parameters, Parameter};
+use crate::stubs::{stub_file, stubs_root, StubPreference};
+use crate::verbosity::Verbosity;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// How a module's stub disagrees with its implementation
+#[pyclass(get_all, frozen)]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StubDrift {
+    pub module: String,
+    pub implementation: String, // Path of the parsed .py
+    pub stub: String,           // Path of the parsed .pyi
+    pub ok: bool,
+    pub mismatched: Vec<String>, // e.g., "load: strict has a default only in the stub"
+    pub stub_only: Vec<String>,
+    pub implementation_only: Vec<String>,
+}
+
+#[pymethods]
+impl StubDrift {
+    fn __bool__(&self) -> bool {
+        self.ok
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<StubDrift {} ok={} mismatched={:?} stub_only={:?} implementation_only={:?}>",
+            self.module,
+            if self.ok { "True" } else { "False" },
+            self.mismatched,
+            self.stub_only,
+            self.implementation_only
+        )
+    }
+}
+
+/// Compare every module under `module_path` that has a stub (an adjacent `.pyi`
+/// or one in an installed `<name>-stubs` package) against its implementation
+pub fn check_stub_drift(
+    py: Python,
+    module_path: &str,
+    max_depth: usize,
+    verbosity: Verbosity,
+) -> PyResult<Vec<StubDrift>> {
+    let explorer = ModuleTreeExplorer::new(module_path.to_string(), max_depth)
+        .with_verbosity(verbosity)
+        .with_stub_preference(StubPreference::Implementation);
+    let tree = explorer.explore_module_pure_filesystem(py, module_path)?;
+    let top_level = module_path.split('.').next().unwrap_or(module_path);
+    let stubs = stubs_root(&explorer.get_sys_path(py)?, top_level);
+
+    let mut modules = Vec::new();
+    collect_modules(&tree, module_path, &mut modules);
+
+    let mut reports = Vec::new();
+    for module in modules {
+        let Some(implementation) = module_source_path(py, &module) else {
+            continue;
+        };
+        // Namespace packages and stub-only modules have nothing to compare
+        if implementation.extension().and_then(|ext| ext.to_str()) != Some("py") {
+            continue;
+        }
+        let Some(stub) = module_stub(&implementation, stubs.as_deref(), &module) else {
+            continue;
+        };
+        verbosity.detail(&format!(
+            "comparing {} with {}",
+            implementation.display(),
+            stub.display()
+        ));
+        let mut report = compare_modules(
+            &ModuleInfo::from_python_file(&implementation)?,
+            &ModuleInfo::from_python_file(&stub)?,
+        );
+        report.module = module;
+        report.implementation = implementation.display().to_string();
+        report.stub = stub.display().to_string();
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// The stub for a module: next to the implementation, else in the stubs package
+fn module_stub(implementation: &Path, stubs: Option<&Path>, module: &str) -> Option<PathBuf> {
+    let adjacent = implementation.with_extension("pyi");
+    if adjacent.is_file() {
+        return Some(adjacent);
+    }
+    stubs.and_then(|root| stub_file(root, module))
+}
+
+/// Dotted paths of a module and its explored submodules
+fn collect_modules(info: &ModuleInfo, module_path: &str, modules: &mut Vec<String>) {
+    modules.push(module_path.to_string());
+    for (name, submodule) in &info.submodules {
+        collect_modules(submodule, &format!("{}.{}", module_path, name), modules);
+    }
+}
+
+/// Compare the public signatures and classes of an implementation and its stub
+pub fn compare_modules(implementation: &ModuleInfo, stub: &ModuleInfo) -> StubDrift {
+    let mut report = StubDrift::default();
+    let impl_names = public_names(implementation);
+    let stub_names = public_names(stub);
+
+    for name in impl_names.intersection(&stub_names) {
+        if let (Some(a), Some(b)) = (
+            implementation.signatures.get(name),
+            stub.signatures.get(name),
+        ) {
+            report.mismatched.extend(
+                compare_signatures(a, b)
+                    .into_iter()
+                    .map(|difference| format!("{}: {}", name, difference)),
+            );
+        }
+    }
+    report.stub_only = stub_names.difference(&impl_names).cloned().collect();
+    report.implementation_only = impl_names.difference(&stub_names).cloned().collect();
+    report.ok = report.mismatched.is_empty()
+        && report.stub_only.is_empty()
+        && report.implementation_only.is_empty();
+    report
+}
+
+/// Public functions, classes, and methods (dunder methods count as public)
+fn public_names(info: &ModuleInfo) -> BTreeSet<String> {
+    info.signatures
+        .keys()
+        .chain(&info.classes)
+        .filter(|name| is_public_name(name))
+        .cloned()
+        .collect()
+}
+
+/// Differences in parameter names, kinds, default presence, and (annotated) return types
+fn compare_signatures(implementation: &FunctionSignature, stub: &FunctionSignature) -> Vec<String> {
+    let mut differences = Vec::new();
+    let impl_params = parse_parameters(&implementation.parameters);
+    let stub_params = parse_parameters(&stub.parameters);
+
+    let names = |params: &[Parameter]| {
+        params
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if names(&impl_params) != names(&stub_params) {
+        differences.push(format!(
+            "parameters ({}) in implementation, ({}) in stub",
+            names(&impl_params),
+            names(&stub_params)
+        ));
+    } else {
+        for (a, b) in impl_params.iter().zip(&stub_params) {
+            if a.kind != b.kind {
+                differences.push(format!(
+                    "{} is {} in implementation, {} in stub",
+                    a.name, a.kind, b.kind
+                ));
+            }
+            match (a.default.is_some(), b.default.is_some()) {
+                (true, false) => differences.push(format!(
+                    "{} has a default only in the implementation",
+                    a.name
+                )),
+                (false, true) => {
+                    differences.push(format!("{} has a default only in the stub", a.name))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // An unannotated implementation isn't drift; two different annotations are
+    if let (Some(a), Some(b)) = (&implementation.return_type, &stub.return_type) {
+        if normalize(a) != normalize(b) {
+            differences.push(format!(
+                "returns '{}' in implementation, '{}' in stub",
+                a, b
+            ));
+        }
+    }
+    differences
+}
+
+/// Format drift reports as a tree per module
+pub fn format_drift_display(module_path: &str, reports: &[StubDrift]) -> String {
+    let config = DisplayConfig::get();
+    if reports.is_empty() {
+        return format!("No stubs found for {}", module_path);
+    }
+
+    let mut result = String::new();
+    for report in reports {
+        result.push_str(&format!(
+            "{} {}\n",
+            colorize(
+                &config.module_icon,
+                &config.color_scheme.module_color,
+                config
+            ),
+            colorize(&report.module, &config.color_scheme.module_color, config)
+        ));
+
+        let mut items: Vec<String> = report
+            .mismatched
+            .iter()
+            .map(|mismatch| colorize(mismatch, &config.color_scheme.warning_color, config))
+            .collect();
+        if !report.stub_only.is_empty() {
+            items.push(format!("only in stub: {}", report.stub_only.join(", ")));
+        }
+        if !report.implementation_only.is_empty() {
+            items.push(format!(
+                "only in implementation: {}",
+                report.implementation_only.join(", ")
+            ));
+        }
+        if items.is_empty() {
+            items.push("in sync".to_string());
+        }
+
+        for (i, item) in items.iter().enumerate() {
+            let prefix = if i == items.len() - 1 {
+                &config.tree_last
+            } else {
+                &config.tree_branch
+            };
+            result.push_str(&format!(
+                "{}{}\n",
+                colorize(prefix, &config.color_scheme.tree_color, config),
+                item
+            ));
+        }
+    }
+    result.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(source: &str) -> ModuleInfo {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("module.pyi");
+        std::fs::write(&path, source).unwrap();
+        ModuleInfo::from_python_file(&path).unwrap()
+    }
+
+    #[test]
+    fn test_compare_modules_reports_drift() {
+        let implementation = module(
+            r#"
+def load(path, *, strict=False):
+    pass
+
+def dump(obj, fp):
+    pass
+
+def _private():
+    pass
+
+class Reader:
+    def __init__(self, path):
+        pass
+
+    def read(self, size=-1) -> bytes:
+        pass
+"#,
+        );
+        let stub = module(
+            r#"
+def load(path: str, strict: bool) -> dict: ...
+def dumps(obj: object) -> str: ...
+
+class Reader:
+    def __init__(self, path: str) -> None: ...
+    def read(self, size: int = ...) -> str: ...
+"#,
+        );
+        let report = compare_modules(&implementation, &stub);
+        assert!(!report.ok);
+        assert_eq!(
+            report.mismatched,
+            vec![
+                "Reader.read: returns 'bytes' in implementation, 'str' in stub",
+                "load: strict is keyword_only in implementation, positional_or_keyword in stub",
+                "load: strict has a default only in the implementation",
+            ]
+        );
+        assert_eq!(report.stub_only, vec!["dumps"]);
+        assert_eq!(report.implementation_only, vec!["dump"]);
+    }
+
+    #[test]
+    fn test_matching_stub_is_ok() {
+        let implementation = module("def load(path, *, strict=False):\n    pass\n");
+        let stub = module("def load(path: str, *, strict: bool = ...) -> dict: ...\n");
+        assert!(compare_modules(&implementation, &stub).ok);
+    }
+}
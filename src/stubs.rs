@@ -0,0 +1,254 @@
+use crate::module_info::{FunctionSignature, ModuleInfo};
+use crate::signature::split_parameters;
+use crate::signature_object::parse_parameters;
+use pyo3::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Which source wins when a PEP 561 `<name>-stubs` package is installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StubPreference {
+    /// Symbols from the implementation, types from the stubs
+    #[default]
+    Merge,
+    /// Only the stubs (when a module has one)
+    Stubs,
+    /// Ignore stub packages
+    Implementation,
+}
+
+impl StubPreference {
+    /// Parse "merge", "stubs", or "implementation"
+    pub fn parse(value: &str) -> PyResult<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "merge" => Ok(StubPreference::Merge),
+            "stubs" => Ok(StubPreference::Stubs),
+            "implementation" => Ok(StubPreference::Implementation),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid prefer option '{}': use 'merge', 'stubs', or 'implementation'",
+                value
+            ))),
+        }
+    }
+
+    /// The preference set by `PRETTY_MOD_PREFER` (merge when unset or invalid)
+    pub fn from_env() -> Self {
+        std::env::var("PRETTY_MOD_PREFER")
+            .ok()
+            .and_then(|value| Self::parse(&value).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// The installed `<top>-stubs` package for a top-level module, if any
+pub fn stubs_root(sys_path: &[PathBuf], top_level: &str) -> Option<PathBuf> {
+    sys_path
+        .iter()
+        .map(|entry| entry.join(format!("{}-stubs", top_level)))
+        .find(|candidate| candidate.is_dir())
+}
+
+/// The stub file for a dotted module path inside a stubs package
+/// e.g., ("site/pandas-stubs", "pandas.core.frame") -> "site/pandas-stubs/core/frame.pyi"
+pub fn stub_file(stubs_root: &Path, module_path: &str) -> Option<PathBuf> {
+    let mut path = stubs_root.to_path_buf();
+    for part in module_path.split('.').skip(1) {
+        path.push(part);
+    }
+    [path.with_extension("pyi"), path.join("__init__.pyi")]
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+}
+
+/// Merge a stub's types into a module parsed from the implementation
+/// Listings (functions, classes, constants) stay the implementation's; signatures
+/// take their annotations from the stub.
+pub fn merge_stub(info: &mut ModuleInfo, stub: ModuleInfo) {
+    for (name, stub_sig) in stub.signatures {
+        let merged = match info.signatures.get(&name) {
+            Some(implementation) => merge_signature(implementation, stub_sig),
+            None => stub_sig,
+        };
+        info.signatures.insert(name, merged);
+    }
+    for (name, class_info) in stub.class_info {
+        info.class_info.entry(name).or_insert(class_info);
+    }
+}
+
+/// Merge the `.pyi` next to a module that defines no functions or classes itself (e.g., an
+/// `__init__.py` re-exporting a compiled extension)
+/// The stub's listings are added to the module's own, whose constants, `__all__` and imports
+/// are kept; signatures are merged as in `merge_stub`.
+pub fn merge_adjacent_stub(info: &mut ModuleInfo, mut stub: ModuleInfo) {
+    for (listed, stub_listed) in [
+        (&mut info.functions, std::mem::take(&mut stub.functions)),
+        (&mut info.classes, std::mem::take(&mut stub.classes)),
+        (&mut info.constants, std::mem::take(&mut stub.constants)),
+        (&mut info.reexports, std::mem::take(&mut stub.reexports)),
+    ] {
+        for name in stub_listed {
+            if !listed.contains(&name) {
+                listed.push(name);
+            }
+        }
+    }
+    if info.all_exports.is_none() && !info.dynamic_all {
+        info.all_exports = stub.all_exports.take();
+    }
+    merge_stub(info, stub);
+}
+
+/// Combine two signatures of the same callable: annotations from the stub,
+/// defaults from the implementation (stubs write them as `...`)
+/// When the parameter lists disagree the stub is taken as-is.
+fn merge_signature(
+    implementation: &FunctionSignature,
+    stub: FunctionSignature,
+) -> FunctionSignature {
+    let impl_params = split_parameters(&implementation.parameters);
+    let stub_params = split_parameters(&stub.parameters);
+    let same_shape = impl_params.len() == stub_params.len()
+        && impl_params
+            .iter()
+            .zip(&stub_params)
+            .all(|(a, b)| parameter_name(a) == parameter_name(b));
+
+    let parameters = if same_shape {
+        impl_params
+            .iter()
+            .zip(&stub_params)
+            .map(|(impl_text, stub_text)| merge_parameter(impl_text, stub_text))
+            .collect::<Vec<_>>()
+            .join(", ")
+    } else {
+        stub.parameters.clone()
+    };
+
+    FunctionSignature {
+        parameters,
+        return_type: stub
+            .return_type
+            .or_else(|| implementation.return_type.clone()),
+        kwargs: stub.kwargs.or_else(|| implementation.kwargs.clone()),
+        param_docs: stub
+            .param_docs
+            .or_else(|| implementation.param_docs.clone()),
+        ..stub
+    }
+}
+
+/// A parameter's name with its `*`/`**` prefix, or the marker itself ("/", "*")
+fn parameter_name(text: &str) -> &str {
+    text.split([':', '=']).next().unwrap_or(text).trim()
+}
+
+fn merge_parameter(impl_text: &str, stub_text: &str) -> String {
+    let (Some(mut merged), Some(implementation)) = (
+        parse_parameters(stub_text).into_iter().next(),
+        parse_parameters(impl_text).into_iter().next(),
+    ) else {
+        // Markers such as "/" and "*"
+        return stub_text.to_string();
+    };
+    if merged.annotation.is_none() {
+        merged.annotation = implementation.annotation;
+    }
+    if merged.default.is_none() || merged.default.as_deref() == Some("...") {
+        merged.default = implementation.default.or(merged.default);
+    }
+    merged.to_text()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sig(parameters: &str, return_type: Option<&str>) -> FunctionSignature {
+        FunctionSignature {
+            name: "read_csv".to_string(),
+            parameters: parameters.to_string(),
+            return_type: return_type.map(str::to_string),
+            source: None,
+            kwargs: None,
+            param_docs: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_signature_takes_types_from_stub() {
+        let merged = merge_signature(
+            &sig("path, *, sep=\",\", header=None", None),
+            sig(
+                "path: str | Path, *, sep: str=..., header: int | None=...",
+                Some("DataFrame"),
+            ),
+        );
+        assert_eq!(
+            merged.parameters,
+            "path: str | Path, *, sep: str=\",\", header: int | None=None"
+        );
+        assert_eq!(merged.return_type.as_deref(), Some("DataFrame"));
+    }
+
+    #[test]
+    fn test_merge_signature_mismatch_uses_stub() {
+        let merged = merge_signature(&sig("a, b", None), sig("a: int", Some("int")));
+        assert_eq!(merged.parameters, "a: int");
+    }
+
+    #[test]
+    fn test_stub_file_and_merge_stub() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("pandas-stubs");
+        std::fs::create_dir_all(root.join("core")).unwrap();
+        std::fs::write(root.join("__init__.pyi"), "").unwrap();
+        std::fs::write(root.join("core").join("frame.pyi"), "").unwrap();
+        assert_eq!(stub_file(&root, "pandas"), Some(root.join("__init__.pyi")));
+        assert_eq!(
+            stub_file(&root, "pandas.core.frame"),
+            Some(root.join("core").join("frame.pyi"))
+        );
+        assert_eq!(stub_file(&root, "pandas.io"), None);
+        assert_eq!(
+            stubs_root(&[dir.path().to_path_buf()], "pandas"),
+            Some(root)
+        );
+
+        let mut info = ModuleInfo::new();
+        info.functions = vec!["read_csv".to_string(), "_runtime_only".to_string()];
+        info.signatures
+            .insert("read_csv".to_string(), sig("path", None));
+        let mut stub = ModuleInfo::new();
+        stub.signatures
+            .insert("read_csv".to_string(), sig("path: str", Some("DataFrame")));
+        merge_stub(&mut info, stub);
+        assert_eq!(info.functions.len(), 2);
+        assert_eq!(info.signatures["read_csv"].parameters, "path: str");
+    }
+
+    #[test]
+    fn test_merge_adjacent_stub_keeps_the_module_api() {
+        let mut info = ModuleInfo::new();
+        info.constants = vec!["VERSION".to_string()];
+        info.all_exports = Some(vec!["loads".to_string(), "VERSION".to_string()]);
+        let mut stub = ModuleInfo::new();
+        stub.functions = vec!["loads".to_string()];
+        stub.constants = vec!["VERSION".to_string()];
+        stub.signatures
+            .insert("loads".to_string(), sig("s: str", Some("object")));
+        merge_adjacent_stub(&mut info, stub);
+        assert_eq!(info.functions, ["loads"]);
+        assert_eq!(info.constants, ["VERSION"]);
+        assert_eq!(info.all_exports.unwrap(), ["loads", "VERSION"]);
+        assert_eq!(info.signatures["loads"].parameters, "s: str");
+    }
+
+    #[test]
+    fn test_preference_parse() {
+        assert_eq!(
+            StubPreference::parse("Stubs").unwrap(),
+            StubPreference::Stubs
+        );
+        assert!(StubPreference::parse("both").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use pyo3::prelude::*;
-use std::collections::HashMap;
-use crate::config::{DisplayConfig, colorize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use crate::config::{DisplayConfig, colorize, hyperlink};
 
 /// Format tree display for wrapped format (with api/submodules structure)
 pub fn format_tree_display(
@@ -13,7 +14,7 @@ pub fn format_tree_display(
 
     let mut result = format!("{} {}\n", 
         colorize(&config.module_icon, &config.color_scheme.module_color, config),
-        colorize(module_name, &config.color_scheme.module_color, config)
+        module_label(py, module_name, &tree_dict, config)
     );
 
     // Check if there are submodules
@@ -37,6 +38,21 @@ pub fn format_tree_display(
                     colorize(&config.exports_icon, &config.color_scheme.exports_color, config),
                     exports.join(", ")
                 ));
+            } else if is_dynamic_all(py, &api_dict) {
+                items.push(format!("{} __all__: (computed at import time)", 
+                    colorize(&config.exports_icon, &config.color_scheme.exports_color, config)
+                ));
+            }
+        }
+
+        // Explicit `import x as x` re-exports (modules without __all__)
+        if let Some(reexports) = api_dict.get("reexports") {
+            let names: Vec<String> = reexports.extract(py)?;
+            if !names.is_empty() {
+                items.push(format!("{} re-exports: {}", 
+                    colorize(&config.exports_icon, &config.color_scheme.exports_color, config),
+                    names.join(", ")
+                ));
             }
         }
 
@@ -55,9 +71,10 @@ pub fn format_tree_display(
         if let Some(classes) = api_dict.get("classes") {
             let cls: Vec<String> = classes.extract(py)?;
             if !cls.is_empty() {
-                items.push(format!("{} classes: {}", 
+                items.push(format!("{} classes: {}{}", 
                     colorize(&config.class_icon, &config.color_scheme.class_color, config),
-                    cls.join(", ")
+                    mark_abstract(py, &api_dict, cls.clone())?.join(", "),
+                    class_method_lines(py, &api_dict, &cls, config)?
                 ));
             }
         }
@@ -73,15 +90,35 @@ pub fn format_tree_display(
             }
         }
 
-        // Print items
-        for (i, item) in items.iter().enumerate() {
-            let is_last = i == items.len() - 1 && !has_submodules;
-            let prefix = if is_last { &config.tree_last } else { &config.tree_branch };
-            result.push_str(&format!("{}{}\n", 
-                colorize(prefix, &config.color_scheme.tree_color, config),
-                item
+        // Console scripts of the distribution, one per line
+        if let Some(scripts) = tree_dict.get("scripts") {
+            let scripts: BTreeMap<String, String> = scripts.extract(py)?;
+            items.push(format!(
+                "{} scripts:{}",
+                colorize(&config.signature_icon, &config.color_scheme.signature_color, config),
+                script_lines(&scripts, config)
             ));
         }
+
+        // Submodules left unexplored when the time budget ran out
+        if is_truncated(py, &tree_dict) {
+            items.push(format!("{} truncated (time budget exhausted)",
+                colorize("…", &config.color_scheme.tree_color, config)
+            ));
+        }
+
+        // Submodules pruned by hide_empty
+        if let Some(hidden) = tree_dict.get("hidden_empty") {
+            let hidden: usize = hidden.extract(py)?;
+            items.push(format!("{} {} empty {} hidden",
+                colorize("…", &config.color_scheme.tree_color, config),
+                hidden,
+                if hidden == 1 { "submodule" } else { "submodules" }
+            ));
+        }
+
+        // Print items
+        push_items(&mut result, "", &items, has_submodules, config);
     }
 
     // submodules
@@ -94,10 +131,11 @@ pub fn format_tree_display(
             for (i, name) in submod_names.iter().enumerate() {
                 let is_last = i == submod_names.len() - 1;
                 let prefix = if is_last { &config.tree_last } else { &config.tree_branch };
+                let submodule_path = format!("{}.{}", module_name, name);
                 result.push_str(&format!("{}{} {}\n", 
                     colorize(prefix, &config.color_scheme.tree_color, config),
                     colorize(&config.module_icon, &config.color_scheme.module_color, config),
-                    colorize(name, &config.color_scheme.module_color, config)
+                    submodule_label(py, name, &submods, config)
                 ));
 
                 if let Some(submod_tree) = submods.get(name) {
@@ -105,6 +143,7 @@ pub fn format_tree_display(
                         py,
                         submod_tree,
                         if is_last { &config.tree_empty } else { &config.tree_vertical },
+                        &submodule_path,
                     )?;
                     result.push_str(&submod_content);
                 }
@@ -115,7 +154,197 @@ pub fn format_tree_display(
     Ok(result)
 }
 
-fn format_tree_recursive(py: Python, tree: &PyObject, prefix: &str) -> PyResult<String> {
+/// A module's name, linked to the source file its node was explored from (the node's
+/// "file") when hyperlinks are enabled
+fn module_label(
+    py: Python,
+    name: &str,
+    node: &HashMap<String, PyObject>,
+    config: &DisplayConfig,
+) -> String {
+    let label = colorize(name, &config.color_scheme.module_color, config);
+    if !config.hyperlinks {
+        return label;
+    }
+    let file = node
+        .get("file")
+        .and_then(|file| file.extract::<PathBuf>(py).ok());
+    match file {
+        Some(path) => hyperlink(&label, &path, config),
+        None => label,
+    }
+}
+
+/// `module_label` for the submodule `name` of a node's submodules
+fn submodule_label(
+    py: Python,
+    name: &str,
+    submodules: &HashMap<String, PyObject>,
+    config: &DisplayConfig,
+) -> String {
+    let node = submodules
+        .get(name)
+        .and_then(|node| node.extract::<HashMap<String, PyObject>>(py).ok())
+        .unwrap_or_default();
+    module_label(py, name, &node, config)
+}
+
+/// Whether a node carries the `truncated` marker
+fn is_truncated(py: Python, tree_dict: &HashMap<String, PyObject>) -> bool {
+    tree_dict
+        .get("truncated")
+        .and_then(|t| t.extract::<bool>(py).ok())
+        .unwrap_or(false)
+}
+
+/// Write a node's items; lines after an item's first (expanded classes) continue its branch
+fn push_items(
+    result: &mut String,
+    prefix: &str,
+    items: &[String],
+    has_submodules: bool,
+    config: &DisplayConfig,
+) {
+    for (i, item) in items.iter().enumerate() {
+        let is_last = i == items.len() - 1 && !has_submodules;
+        let (branch, continuation) = if is_last {
+            (&config.tree_last, &config.tree_empty)
+        } else {
+            (&config.tree_branch, &config.tree_vertical)
+        };
+        for (j, line) in item.split('\n').enumerate() {
+            let line_prefix = if j == 0 { branch } else { continuation };
+            result.push_str(&format!(
+                "{}{}{}\n",
+                prefix,
+                colorize(line_prefix, &config.color_scheme.tree_color, config),
+                line
+            ));
+        }
+    }
+}
+
+/// Each class's public methods on its own line, when the tree was explored with `class_depth=1`
+/// Explored with `signatures`, each method gets its own line under the class instead.
+fn class_method_lines(
+    py: Python,
+    api_dict: &HashMap<String, PyObject>,
+    classes: &[String],
+    config: &DisplayConfig,
+) -> PyResult<String> {
+    let Some(class_methods) = api_dict.get("class_methods") else {
+        return Ok(String::new());
+    };
+    let class_methods: HashMap<String, HashMap<String, PyObject>> = class_methods.extract(py)?;
+    let mut lines = String::new();
+    for (i, class) in classes.iter().enumerate() {
+        let is_last = i == classes.len() - 1;
+        let branch = if is_last { &config.tree_last } else { &config.tree_branch };
+        let continuation = if is_last {
+            &config.tree_empty
+        } else {
+            &config.tree_vertical
+        };
+        let mut line = colorize(class, &config.color_scheme.class_color, config);
+        let mut nested = Vec::new();
+        if let Some(entry) = class_methods.get(class) {
+            let mut methods: Vec<String> = match entry.get("methods") {
+                Some(methods) => methods.extract(py)?,
+                None => Vec::new(),
+            };
+            if let Some(signatures) = entry.get("signatures") {
+                let signatures: HashMap<String, String> = signatures.extract(py)?;
+                for method in methods.iter_mut() {
+                    if let Some(signature) = signatures.get(method.as_str()) {
+                        *method = colorize(signature, &config.color_scheme.signature_color, config);
+                    }
+                }
+            }
+            if let Some(more) = entry.get("more") {
+                methods.push(format!("… {} more", more.extract::<usize>(py)?));
+            }
+            if entry.contains_key("signatures") {
+                nested = methods;
+            } else if !methods.is_empty() {
+                line.push_str(&format!(": {}", methods.join(", ")));
+            }
+        }
+        lines.push_str(&format!(
+            "\n{}{}",
+            colorize(branch, &config.color_scheme.tree_color, config),
+            line
+        ));
+        for (j, method) in nested.iter().enumerate() {
+            let method_branch = if j == nested.len() - 1 {
+                &config.tree_last
+            } else {
+                &config.tree_branch
+            };
+            lines.push_str(&format!(
+                "\n{}{}",
+                colorize(
+                    &format!("{}{}", continuation, method_branch),
+                    &config.color_scheme.tree_color,
+                    config
+                ),
+                method
+            ));
+        }
+    }
+    Ok(lines)
+}
+
+/// Each script and the function it runs, e.g. "httpx → httpx:main"
+fn script_lines(scripts: &BTreeMap<String, String>, config: &DisplayConfig) -> String {
+    let mut lines = String::new();
+    for (i, (name, target)) in scripts.iter().enumerate() {
+        let branch = if i == scripts.len() - 1 { &config.tree_last } else { &config.tree_branch };
+        lines.push_str(&format!(
+            "\n{}{} → {}",
+            colorize(branch, &config.color_scheme.tree_color, config),
+            colorize(name, &config.color_scheme.signature_color, config),
+            target
+        ));
+    }
+    lines
+}
+
+/// Whether `__all__` is assigned from something only known at runtime
+fn is_dynamic_all(py: Python, api_dict: &HashMap<String, PyObject>) -> bool {
+    api_dict
+        .get("dynamic_all")
+        .and_then(|d| d.extract::<bool>(py).ok())
+        .unwrap_or(false)
+}
+
+/// Suffix abstract classes with "(abstract)"
+fn mark_abstract(
+    py: Python,
+    api_dict: &HashMap<String, PyObject>,
+    classes: Vec<String>,
+) -> PyResult<Vec<String>> {
+    let abstract_classes: Vec<String> = match api_dict.get("abstract_classes") {
+        Some(abstract_classes) => abstract_classes.extract(py)?,
+        None => Vec::new(),
+    };
+    Ok(classes
+        .into_iter()
+        .map(|name| {
+            if abstract_classes.contains(&name) {
+                format!("{} (abstract)", name)
+            } else {
+                name
+            }
+        })
+        .collect())
+}
+
+fn format_tree_recursive(
+    py: Python,
+    tree: &PyObject,
+    prefix: &str,
+    module_path: &str,
+) -> PyResult<String> {
     let tree_dict: HashMap<String, PyObject> = tree.extract(py)?;
     let config = DisplayConfig::get();
 
@@ -135,6 +364,21 @@ fn format_tree_recursive(py: Python, tree: &PyObject, prefix: &str) -> PyResult<
                     colorize(&config.exports_icon, &config.color_scheme.exports_color, config),
                     exports.join(", ")
                 ));
+            } else if is_dynamic_all(py, &api_dict) {
+                items.push(format!("{} __all__: (computed at import time)", 
+                    colorize(&config.exports_icon, &config.color_scheme.exports_color, config)
+                ));
+            }
+        }
+
+        // Explicit `import x as x` re-exports (modules without __all__)
+        if let Some(reexports) = api_dict.get("reexports") {
+            let names: Vec<String> = reexports.extract(py)?;
+            if !names.is_empty() {
+                items.push(format!("{} re-exports: {}", 
+                    colorize(&config.exports_icon, &config.color_scheme.exports_color, config),
+                    names.join(", ")
+                ));
             }
         }
 
@@ -153,9 +397,10 @@ fn format_tree_recursive(py: Python, tree: &PyObject, prefix: &str) -> PyResult<
         if let Some(classes) = api_dict.get("classes") {
             let cls: Vec<String> = classes.extract(py)?;
             if !cls.is_empty() {
-                items.push(format!("{} classes: {}", 
+                items.push(format!("{} classes: {}{}", 
                     colorize(&config.class_icon, &config.color_scheme.class_color, config),
-                    cls.join(", ")
+                    mark_abstract(py, &api_dict, cls.clone())?.join(", "),
+                    class_method_lines(py, &api_dict, &cls, config)?
                 ));
             }
         }
@@ -171,6 +416,13 @@ fn format_tree_recursive(py: Python, tree: &PyObject, prefix: &str) -> PyResult<
             }
         }
 
+        // Submodules left unexplored when the time budget ran out
+        if is_truncated(py, &tree_dict) {
+            items.push(format!("{} truncated (time budget exhausted)",
+                colorize("…", &config.color_scheme.tree_color, config)
+            ));
+        }
+
         // Check if there are submodules
         let has_submodules = tree_dict
             .get("submodules")
@@ -179,14 +431,7 @@ fn format_tree_recursive(py: Python, tree: &PyObject, prefix: &str) -> PyResult<
             .unwrap_or(false);
 
         // Print items
-        for (i, item) in items.iter().enumerate() {
-            let is_last = i == items.len() - 1 && !has_submodules;
-            let item_prefix = if is_last { &config.tree_last } else { &config.tree_branch };
-            result.push_str(&format!("{}{}{}\n", prefix, 
-                colorize(item_prefix, &config.color_scheme.tree_color, config), 
-                item
-            ));
-        }
+        push_items(&mut result, prefix, &items, has_submodules, config);
     }
 
     // Process submodules recursively
@@ -199,10 +444,11 @@ fn format_tree_recursive(py: Python, tree: &PyObject, prefix: &str) -> PyResult<
             let is_last = i == submod_names.len() - 1;
             let submod_prefix = if is_last { &config.tree_last } else { &config.tree_branch };
 
+            let submodule_path = format!("{}.{}", module_path, name);
             result.push_str(&format!("{}{}{} {}\n", prefix, 
                 colorize(submod_prefix, &config.color_scheme.tree_color, config),
                 colorize(&config.module_icon, &config.color_scheme.module_color, config),
-                colorize(name, &config.color_scheme.module_color, config)
+                submodule_label(py, name, &submods, config)
             ));
 
             if let Some(submod_tree) = submods.get(name) {
@@ -210,6 +456,7 @@ fn format_tree_recursive(py: Python, tree: &PyObject, prefix: &str) -> PyResult<
                     py,
                     submod_tree,
                     &format!("{}{}", prefix, if is_last { &config.tree_empty } else { &config.tree_vertical }),
+                    &submodule_path,
                 )?;
                 result.push_str(&submod_content);
             }
@@ -0,0 +1,321 @@
+use crate::module_info::{ModuleInfo, TypedDictField, UnpackedKwargs};
+use crate::signature::format_annotation;
+use ruff_python_ast::{Expr, Parameters, Stmt};
+use ruff_python_parser::parse_module;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// How many re-exports to follow when looking for an imported TypedDict
+const MAX_IMPORT_HOPS: usize = 3;
+
+/// A `from module import name` binding: (level, module, imported name)
+type ImportBinding = (u32, Option<String>, String);
+
+/// `**kwargs: Unpack[Options]`, with the fields still to be resolved
+pub fn unpacked_kwargs(params: &Parameters) -> Option<UnpackedKwargs> {
+    let kwarg = params.kwarg.as_ref()?;
+    let Expr::Subscript(sub) = kwarg.annotation.as_deref()? else {
+        return None;
+    };
+    if last_segment(&format_annotation(&sub.value)) != "Unpack" {
+        return None;
+    }
+    let typed_dict = match sub.slice.as_ref() {
+        Expr::Name(name) => name.id.to_string(),
+        // Forward reference, e.g. Unpack["Options"]
+        Expr::StringLiteral(lit) => lit.as_single_part_string()?.as_str().trim().to_string(),
+        _ => return None,
+    };
+    Some(UnpackedKwargs {
+        parameter: kwarg.name.to_string(),
+        typed_dict,
+        fields: Vec::new(),
+    })
+}
+
+/// Fill in the fields of every `Unpack[...]` kwargs in a parsed module
+/// The TypedDict may be defined in the module or imported (a few hops at most);
+/// unresolvable ones are dropped so the signature shows plain `**kwargs`.
+pub fn expand_unpacked_kwargs(info: &mut ModuleInfo, body: &[Stmt], file_path: &Path) {
+    if info.signatures.values().all(|sig| sig.kwargs.is_none()) {
+        return;
+    }
+    let typed_dicts = collect_typed_dicts(body);
+    let imports = collect_imports(body);
+    let mut resolved: HashMap<String, Option<Vec<TypedDictField>>> = HashMap::new();
+
+    for sig in info.signatures.values_mut() {
+        let Some(kwargs) = &mut sig.kwargs else {
+            continue;
+        };
+        let fields = resolved
+            .entry(kwargs.typed_dict.clone())
+            .or_insert_with(|| {
+                resolve(&kwargs.typed_dict, &typed_dicts, &imports, file_path, 0)
+            })
+            .clone();
+        match fields {
+            Some(fields) => kwargs.fields = fields,
+            None => sig.kwargs = None,
+        }
+    }
+}
+
+/// Find a TypedDict's fields, following imports into sibling modules
+fn resolve(
+    name: &str,
+    typed_dicts: &BTreeMap<String, Vec<TypedDictField>>,
+    imports: &HashMap<String, ImportBinding>,
+    file_path: &Path,
+    hops: usize,
+) -> Option<Vec<TypedDictField>> {
+    if let Some(fields) = typed_dicts.get(name) {
+        return Some(fields.clone());
+    }
+    if hops >= MAX_IMPORT_HOPS {
+        return None;
+    }
+    let (level, module, imported) = imports.get(name)?;
+    let target = import_target(file_path, *level, module.as_deref())?;
+    let source = std::fs::read_to_string(&target).ok()?;
+    let body = parse_module(&source).ok()?.into_syntax().body;
+    resolve(
+        imported,
+        &collect_typed_dicts(&body),
+        &collect_imports(&body),
+        &target,
+        hops + 1,
+    )
+}
+
+/// TypedDict classes defined at module level (including `if TYPE_CHECKING:` blocks)
+/// Fields inherited from TypedDicts earlier in the same module are included.
+fn collect_typed_dicts(body: &[Stmt]) -> BTreeMap<String, Vec<TypedDictField>> {
+    let mut typed_dicts = BTreeMap::new();
+    for stmt in module_level(body) {
+        let Stmt::ClassDef(class_def) = stmt else {
+            continue;
+        };
+        let bases: Vec<String> = class_def.bases().iter().map(format_annotation).collect();
+        let is_typed_dict = bases
+            .iter()
+            .any(|base| last_segment(base) == "TypedDict" || typed_dicts.contains_key(base));
+        if !is_typed_dict {
+            continue;
+        }
+        let total = !class_def.keywords().iter().any(|keyword| {
+            keyword.arg.as_ref().is_some_and(|arg| arg.as_str() == "total")
+                && matches!(&keyword.value, Expr::BooleanLiteral(b) if !b.value)
+        });
+
+        let mut fields: Vec<TypedDictField> = bases
+            .iter()
+            .filter_map(|base| typed_dicts.get(base))
+            .flatten()
+            .cloned()
+            .collect();
+        for stmt in &class_def.body {
+            let Stmt::AnnAssign(assign) = stmt else {
+                continue;
+            };
+            let Expr::Name(target) = assign.target.as_ref() else {
+                continue;
+            };
+            let (annotation, required) = field_annotation(&assign.annotation, total);
+            fields.retain(|field| field.name != target.id.as_str());
+            fields.push(TypedDictField {
+                name: target.id.to_string(),
+                annotation,
+                required,
+            });
+        }
+        typed_dicts.insert(class_def.name.to_string(), fields);
+    }
+    typed_dicts
+}
+
+/// A field's type with `Required`/`NotRequired`/`ReadOnly` unwrapped, and whether it is required
+fn field_annotation(annotation: &Expr, total: bool) -> (String, bool) {
+    if let Expr::Subscript(sub) = annotation {
+        match last_segment(&format_annotation(&sub.value)) {
+            "Required" => return (field_annotation(&sub.slice, total).0, true),
+            "NotRequired" => return (field_annotation(&sub.slice, total).0, false),
+            "ReadOnly" => return field_annotation(&sub.slice, total),
+            _ => {}
+        }
+    }
+    (format_annotation(annotation), total)
+}
+
+/// Names bound by `from ... import ...` at module level
+fn collect_imports(body: &[Stmt]) -> HashMap<String, ImportBinding> {
+    let mut imports = HashMap::new();
+    for stmt in module_level(body) {
+        let Stmt::ImportFrom(import_from) = stmt else {
+            continue;
+        };
+        let module = import_from.module.as_ref().map(|m| m.to_string());
+        for alias in &import_from.names {
+            let bound = alias.asname.as_ref().unwrap_or(&alias.name);
+            imports.insert(
+                bound.to_string(),
+                (import_from.level, module.clone(), alias.name.to_string()),
+            );
+        }
+    }
+    imports
+}
+
+/// Top-level statements plus those nested in `if` blocks
+pub fn module_level(body: &[Stmt]) -> Vec<&Stmt> {
+    let mut stmts = Vec::new();
+    for stmt in body {
+        if let Stmt::If(if_stmt) = stmt {
+            stmts.extend(module_level(&if_stmt.body));
+            for clause in &if_stmt.elif_else_clauses {
+                stmts.extend(module_level(&clause.body));
+            }
+        } else {
+            stmts.push(stmt);
+        }
+    }
+    stmts
+}
+
+/// The file a `from <module> import` in `file_path` refers to
+/// Absolute imports are looked up from the root of the package containing `file_path`.
+pub fn import_target(file_path: &Path, level: u32, module: Option<&str>) -> Option<PathBuf> {
+    crate::daemon::reading_other_file();
+    let mut base = file_path.parent()?.to_path_buf();
+    if level > 0 {
+        for _ in 1..level {
+            base = base.parent()?.to_path_buf();
+        }
+    } else {
+        while is_package(&base) {
+            base = base.parent()?.to_path_buf();
+        }
+    }
+
+    let mut path = base;
+    for part in module.unwrap_or("").split('.').filter(|part| !part.is_empty()) {
+        path.push(part);
+    }
+    // Stubs describe types best, so they win over the implementation
+    [
+        path.with_extension("pyi"),
+        path.with_extension("py"),
+        path.join("__init__.pyi"),
+        path.join("__init__.py"),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.is_file())
+}
+
+fn is_package(dir: &Path) -> bool {
+    dir.join("__init__.py").exists() || dir.join("__init__.pyi").exists()
+}
+
+/// The final dotted segment of a name, e.g. "typing.Unpack" -> "Unpack"
+fn last_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn fields(source: &str) -> BTreeMap<String, Vec<TypedDictField>> {
+        collect_typed_dicts(&parse_module(source).unwrap().into_syntax().body)
+    }
+
+    fn field(name: &str, annotation: &str, required: bool) -> TypedDictField {
+        TypedDictField {
+            name: name.to_string(),
+            annotation: annotation.to_string(),
+            required,
+        }
+    }
+
+    #[test]
+    fn test_collect_typed_dicts_totality_and_inheritance() {
+        let typed_dicts = fields(
+            r#"
+from typing import TypedDict, NotRequired, Required
+
+class Base(TypedDict, total=False):
+    timeout: float
+    auth: Required[str]
+
+class Options(Base):
+    headers: dict[str, str]
+    follow_redirects: NotRequired[bool]
+
+class NotADict:
+    x: int
+"#,
+        );
+        assert_eq!(
+            typed_dicts["Options"],
+            vec![
+                field("timeout", "float", false),
+                field("auth", "str", true),
+                field("headers", "dict[str, str]", true),
+                field("follow_redirects", "bool", false),
+            ]
+        );
+        assert!(!typed_dicts.contains_key("NotADict"));
+    }
+
+    #[test]
+    fn test_unpacked_kwargs() {
+        let body = parse_module(
+            "def request(url: str, **kwargs: typing_extensions.Unpack['Options']) -> None: ...\ndef plain(**kwargs: int): ...\n",
+        )
+        .unwrap()
+        .into_syntax()
+        .body;
+        let Stmt::FunctionDef(request) = &body[0] else {
+            panic!("expected a function");
+        };
+        let kwargs = unpacked_kwargs(&request.parameters).unwrap();
+        assert_eq!(kwargs.parameter, "kwargs");
+        assert_eq!(kwargs.typed_dict, "Options");
+        let Stmt::FunctionDef(plain) = &body[1] else {
+            panic!("expected a function");
+        };
+        assert!(unpacked_kwargs(&plain.parameters).is_none());
+    }
+
+    #[test]
+    fn test_resolve_through_relative_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("client");
+        fs::create_dir(&package).unwrap();
+        fs::write(package.join("__init__.py"), "").unwrap();
+        fs::write(
+            package.join("_types.py"),
+            "from typing import TypedDict\nclass Options(TypedDict):\n    timeout: float\n",
+        )
+        .unwrap();
+        let api = package.join("api.py");
+        let source = "from ._types import Options as Opts\n";
+        fs::write(&api, source).unwrap();
+
+        let body = parse_module(source).unwrap().into_syntax().body;
+        let resolved = resolve(
+            "Opts",
+            &collect_typed_dicts(&body),
+            &collect_imports(&body),
+            &api,
+            0,
+        );
+        assert_eq!(resolved, Some(vec![field("timeout", "float", true)]));
+        // Absolute imports resolve from the package root
+        assert_eq!(
+            import_target(&api, 0, Some("client._types")),
+            Some(package.join("_types.py"))
+        );
+    }
+}
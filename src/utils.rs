@@ -1,107 +1,433 @@
 use pyo3::prelude::*;
 use crate::config::{DisplayConfig, colorize};
+use crate::package_downloader::PackageDownloader;
+use crate::verbosity::Verbosity;
+use crate::requirement::{is_url, parse_requirement, pin_matches};
+use crate::module_info::ModuleInfo;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
-/// RAII guard for sys.path cleanup
-struct PathGuard<'py> {
+/// Module path that reads one module's source from stdin (`tree -`, `sig -:name`)
+pub const STDIN_MODULE: &str = "-";
+/// How a module read from stdin is labelled
+pub const STDIN_NAME: &str = "<stdin>";
+
+/// Parse the module source piped on stdin (nothing is imported or downloaded)
+/// Reads through Python's sys.stdin so redirections made in Python apply.
+pub fn module_from_stdin(py: Python) -> PyResult<ModuleInfo> {
+    let source: String = py
+        .import("sys")?
+        .getattr("stdin")?
+        .call_method0("read")?
+        .extract()?;
+    ModuleInfo::from_source(&source, STDIN_NAME)
+}
+
+/// Paths inserted into sys.path by live `PathGuard`s, with how many guards share each
+/// Identical inserts share one entry, which is removed when the last guard drops.
+static SYS_PATH_USERS: Mutex<BTreeMap<String, usize>> = Mutex::new(BTreeMap::new());
+
+/// Lock the sys.path registry, releasing the GIL while another thread holds it
+fn lock_sys_path_users(py: Python) -> MutexGuard<'static, BTreeMap<String, usize>> {
+    // Blocking with the GIL held could deadlock against the holder
+    let users = py.allow_threads(|| Unattached(SYS_PATH_USERS.lock()));
+    users.0.unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A lock guard taken while the GIL is released, to hand back out of `allow_threads`
+struct Unattached<T>(T);
+
+// SAFETY: `allow_threads` runs its closure on the calling thread, so the guard is used and
+// released by the thread that locked it
+unsafe impl<T> Send for Unattached<T> {}
+
+/// RAII guard for sys.path cleanup, safe to use from several threads at once
+pub struct PathGuard<'py> {
     sys_path: &'py pyo3::Bound<'py, pyo3::PyAny>,
     path: &'py str,
 }
 
+impl<'py> PathGuard<'py> {
+    /// Put `path` first on sys.path until the guard is dropped
+    /// A path another guard already inserted is shared rather than inserted again.
+    pub fn prepend(sys_path: &'py pyo3::Bound<'py, pyo3::PyAny>, path: &'py str) -> PyResult<Self> {
+        let mut users = lock_sys_path_users(sys_path.py());
+        let count = users.get(path).copied().unwrap_or(0);
+        if count == 0 {
+            sys_path.call_method1("insert", (0, path))?;
+        }
+        users.insert(path.to_string(), count + 1);
+        Ok(Self { sys_path, path })
+    }
+}
+
 impl Drop for PathGuard<'_> {
     fn drop(&mut self) {
-        // Best effort removal - don't panic in drop
-        let _ = self.sys_path.call_method1("remove", (self.path,));
+        let mut users = lock_sys_path_users(self.sys_path.py());
+        match users.get_mut(self.path) {
+            Some(count) if *count > 1 => *count -= 1,
+            _ => {
+                users.remove(self.path);
+                // Best effort removal - the entry may already be gone, don't panic in drop
+                if self.sys_path.contains(self.path).unwrap_or(false) {
+                    let _ = self.sys_path.call_method1("remove", (self.path,));
+                }
+            }
+        }
     }
 }
 
 /// Parse a package specification into name and version
 /// e.g., "package@1.2.3" -> ("package", Some("1.2.3"))
+/// e.g., "package==1.2.3" -> ("package", Some("1.2.3"))
 /// e.g., "package" -> ("package", None)
+/// PEP 508 direct references ("package @ https://...") are not version pins
 pub fn parse_package_spec(spec: &str) -> (&str, Option<&str>) {
     if let Some((name, version)) = spec.split_once('@') {
-        if version.is_empty() {
+        if version.is_empty() || is_url(version.trim()) {
             (spec, None)
         } else {
             (name, Some(version))
         }
+    } else if let Some((name, version)) = split_exact_pin(spec) {
+        (name, Some(version))
     } else {
         (spec, None)
     }
 }
 
+/// Split a pip-style exact pin off a spec
+/// e.g., "pkg[x]==1.2" -> Some(("pkg[x]", "1.2"))
+/// Specifier sets ("==1.*", "==1.0,<2"), arbitrary equality ("===1.0"), and
+/// requirements with markers are not exact pins.
+fn split_exact_pin(spec: &str) -> Option<(&str, &str)> {
+    let (name, version) = spec.split_once("==")?;
+    let (name, version) = (name.trim_end(), version.trim());
+    let is_version = !version.is_empty()
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '!' | '-' | '_'));
+    (!name.is_empty() && is_version).then_some((name, version))
+}
+
+/// Reject a spec that pins its version twice, e.g. "pkg@1.0==2.0"
+pub fn check_pins(spec: &str) -> PyResult<()> {
+    let at_pin = spec
+        .rfind('@')
+        .is_some_and(|at_pos| !is_url(spec[at_pos + 1..].trim()));
+    if at_pin && spec.contains("==") {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Conflicting version pins in '{}': pin with either '@VERSION' or '==VERSION', not both",
+            spec
+        )));
+    }
+    Ok(())
+}
+
 /// Parse a full module specification with all components
-/// Format: [package::]module[.submodule...][@version]
+/// Format: [package::]module[.submodule...][@version] or a PEP 508 requirement
+/// (e.g., "uvicorn[standard]>=0.30; python_version >= '3.9'")
 /// Returns: (package_override, module_path, version)
+/// The version is either a pin ("1.2.3", "latest"), a specifier set (">=0.30"),
+/// or a direct reference URL; see `download_spec`.
 pub fn parse_full_spec(spec: &str) -> (Option<&str>, &str, Option<&str>) {
-    // First extract version from the end
-    let (spec_without_version, version) = if let Some(at_pos) = spec.rfind('@') {
-        let (before, after) = spec.split_at(at_pos);
-        (before, Some(&after[1..]))
-    } else {
-        (spec, None)
+    let spec = spec.trim();
+
+    // First extract a pretty-mod style @version (or pip-style ==version) from the end
+    let (spec_without_version, version) = match spec.rfind('@') {
+        Some(at_pos) if !is_url(spec[at_pos + 1..].trim()) => {
+            (&spec[..at_pos], Some(&spec[at_pos + 1..]))
+        }
+        _ => match split_exact_pin(spec) {
+            Some((rest, version)) => (rest, Some(version)),
+            None => (spec, None),
+        },
     };
     
     // Then parse package::module syntax
-    let (package_override, module_path) = if let Some((package, module)) = spec_without_version.split_once("::") {
+    let (package_override, module_spec) = if let Some((package, module)) = spec_without_version.split_once("::") {
         (Some(package), module)
     } else {
         (None, spec_without_version)
     };
+
+    // Finally parse the module as a PEP 508 requirement
+    let requirement = parse_requirement(module_spec);
+    let version = version.or(requirement.specifier).or(requirement.url);
     
-    (package_override, module_path, version)
+    (package_override, requirement.name, version)
 }
 
-/// Extract the base package name from a module path
-/// e.g., "prefect.server.api" -> "prefect"
-pub fn extract_base_package(module_path: &str) -> &str {
-    // First parse any @ version specifier
-    let (module_path, _version) = parse_package_spec(module_path);
+/// Build the spec handed to `PackageDownloader` from a package name and the
+/// version returned by `parse_full_spec`
+/// e.g., ("toml", Some("0.10.2")) -> "toml@0.10.2"
+/// e.g., ("uvicorn", Some(">=0.30")) -> "uvicorn>=0.30"
+pub fn download_spec(package: &str, version: Option<&str>) -> String {
+    match version {
+        None => package.to_string(),
+        Some(url) if is_url(url) => format!("{} @ {}", package, url),
+        Some(specifier) if specifier.starts_with(['<', '>', '=', '!', '~']) => {
+            format!("{}{}", package, specifier)
+        }
+        Some(version) => format!("{}@{}", package, version),
+    }
+}
 
-    // Then remove any PEP 508 version specifiers
-    let module_name = module_path
-        .split(&['[', '>', '<', '=', '!'][..])
-        .next()
-        .unwrap_or(module_path)
-        .trim();
+/// Parse an object specification as used by `sig`
+/// Format: [package::]module[@version]:object or [package::]module:object[@version]
+/// Dot syntax (module.object[@version]) splits at the last dot.
+/// Returns: (package_override, module_path, object_path, version)
+pub fn parse_object_spec(spec: &str) -> Option<(Option<&str>, &str, &str, Option<&str>)> {
+    // The package override never contains a version, so split it off first
+    let (package_override, rest) = match spec.split_once("::") {
+        Some((package, rest)) => (Some(package), rest),
+        None => (None, spec),
+    };
 
-    // Then get the first component
-    module_name.split('.').next().unwrap_or(module_name)
+    if let Some((module_part, object_part)) = rest.split_once(':') {
+        if object_part.contains(':') {
+            return None;
+        }
+        // The version may follow either the module or the object
+        let (module_path, module_version) = split_version(module_part);
+        let (object_path, object_version) = split_version(object_part);
+        if module_path.is_empty() || object_path.is_empty() {
+            return None;
+        }
+        Some((
+            package_override,
+            module_path,
+            object_path,
+            module_version.or(object_version),
+        ))
+    } else {
+        let (path, version) = split_version(rest);
+        let dot_pos = path.rfind('.')?;
+        Some((
+            package_override,
+            &path[..dot_pos],
+            &path[dot_pos + 1..],
+            version,
+        ))
+    }
+}
+
+/// Candidate colon specs for a dot-only object spec, one per split point with
+/// the longest module first
+/// e.g., "pkg.Client.send@1.0" -> ["pkg.Client:send@1.0", "pkg:Client.send@1.0"]
+/// Colon syntax is already unambiguous and yields no candidates.
+pub fn dotted_object_specs(spec: &str) -> Vec<String> {
+    let (prefix, rest) = match spec.split_once("::") {
+        Some((package, rest)) => (format!("{}::", package), rest),
+        None => (String::new(), spec),
+    };
+    if rest.contains(':') {
+        return Vec::new();
+    }
+    let (path, version) = split_version(rest);
+    let suffix = version.map(|v| format!("@{}", v)).unwrap_or_default();
+    path.match_indices('.')
+        .rev()
+        .filter(|(i, _)| *i > 0 && *i + 1 < path.len())
+        .map(|(i, _)| format!("{}{}:{}{}", prefix, &path[..i], &path[i + 1..], suffix))
+        .collect()
+}
+
+/// Split a trailing "@version" (or "==version") off a path segment
+fn split_version(segment: &str) -> (&str, Option<&str>) {
+    match segment.rsplit_once('@') {
+        Some((path, version)) if !version.is_empty() => (path, Some(version)),
+        Some((path, _)) => (path, None),
+        None => match split_exact_pin(segment) {
+            Some((path, version)) => (path, Some(version)),
+            None => (segment, None),
+        },
+    }
 }
 
 /// Try to download and temporarily add a package to sys.path
+/// With `sources_of`, only that module's sources are extracted from a wheel; with
+/// `expected_module` (a guessed name), only a wheel containing that module is.
 pub fn try_download_and_import<F, R>(
     py: Python,
     package_name: &str,
-    quiet: bool,
+    sources_of: Option<&str>,
+    expected_module: Option<&str>,
+    verbosity: Verbosity,
     f: F,
 ) -> PyResult<R>
 where
-    F: FnOnce() -> PyResult<R>,
+    F: FnOnce(&PackageDownloader) -> PyResult<R>,
 {
-    // Show download message if not quiet
-    if !quiet {
-        let config = DisplayConfig::get();
-        let sys = py.import("sys")?;
-        let stderr = sys.getattr("stderr")?;
-        
-        // Format the message with colors
-        let message = format!(
-            "{} Module '{}' not found locally. Attempting to download from PyPI...\n",
-            colorize("⚠️ ", &config.color_scheme.warning_color, config),
-            colorize(package_name, &config.color_scheme.module_color, config)
-        );
-        
-        stderr.call_method1("write", (message,))?;
-        stderr.call_method0("flush")?;
-    }
-
-    // Parse package name (without version) for path operations
-    let (base_name, _) = parse_package_spec(package_name);
+    // Another interpreter's environment is explored as-is
+    if let Some(target) = crate::interpreter::target() {
+        return Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(format!(
+            "No module named '{}' in {} (downloads are disabled with --python)",
+            package_name, target.executable
+        )));
+    }
+
+    // A daemon keeps earlier downloads extracted
+    let requirement = format!("{}|{}", package_name, sources_of.unwrap_or_default());
+    if let Some(downloader) = crate::daemon::cached_download(&requirement) {
+        verbosity.detail(&format!("reusing the download of {}", package_name));
+        return with_downloaded_package(py, &downloader, f);
+    }
+
+    // Show download notice unless quiet
+    let config = DisplayConfig::get();
+    verbosity.notice(&format!(
+        "{} Module '{}' not found locally. Attempting to download from PyPI...",
+        colorize("⚠️ ", &config.color_scheme.warning_color, config),
+        colorize(package_name, &config.color_scheme.module_color, config)
+    ));
 
     // Download and extract the package (with version if specified)
     let mut downloader =
-        crate::package_downloader::PackageDownloader::new(package_name.to_string());
-    let package_path = downloader.download_and_extract()?;
+        PackageDownloader::new(package_name.to_string()).with_verbosity(verbosity);
+    if let Some(module_path) = sources_of {
+        downloader = downloader.with_sources_only(module_path);
+    }
+    if let Some(module_path) = expected_module {
+        downloader = downloader.with_expected_module(module_path);
+    }
+    downloader.download_and_extract()?;
+
+    let downloader = crate::daemon::keep_download(&requirement, downloader);
+    with_downloaded_package(py, &downloader, f)
+}
+
+/// Top-level packages that distributions share as a namespace, each published under a
+/// dashed name (e.g., google-cloud-storage provides google.cloud.storage)
+const NAMESPACE_ROOTS: &[&str] = &[
+    "azure",
+    "backports",
+    "google",
+    "jaraco",
+    "opentelemetry",
+    "sphinxcontrib",
+    "zope",
+];
+
+/// Distributions that might provide a module: its base package, then for a namespace
+/// root the dashed names, most specific first
+/// e.g., "google.cloud.storage" -> ["google", "google-cloud-storage", "google-cloud"]
+pub fn download_candidates(module_path: &str) -> Vec<String> {
+    let parts: Vec<&str> = module_path.split('.').collect();
+    let mut candidates = vec![parts[0].to_string()];
+    if NAMESPACE_ROOTS.contains(&parts[0]) {
+        candidates.extend((2..=parts.len()).rev().map(|n| parts[..n].join("-")));
+    }
+    candidates
+}
+
+/// Download the distribution that provides `module_path` and run `f` with it on sys.path
+/// Without a package override each of `download_candidates` is tried in turn; one
+/// that isn't on the index, or doesn't contain the module, moves on to the next.
+/// Guessed dashed names are only extracted once their wheel is seen to contain the
+/// module, and a version pin only applies to the distributions named by the request:
+/// the override, or else the base package and the module's full dashed name.
+/// `sources_only` extracts just the module's Python sources (enough for signatures).
+pub fn try_download_module<F, R>(
+    py: Python,
+    module_path: &str,
+    package_override: Option<&str>,
+    version: Option<&str>,
+    sources_only: bool,
+    verbosity: Verbosity,
+    mut f: F,
+) -> PyResult<R>
+where
+    F: FnMut(&PackageDownloader) -> PyResult<R>,
+{
+    let candidates = match package_override {
+        Some(package) => vec![package.to_string()],
+        None => download_candidates(module_path),
+    };
+    let sources_of = sources_only.then_some(module_path);
+    if candidates.len() == 1 {
+        let spec = download_spec(&candidates[0], version);
+        return try_download_and_import(py, &spec, sources_of, None, verbosity, f);
+    }
+
+    let full_name = module_path.replace('.', "-");
+    for (i, candidate) in candidates.iter().enumerate() {
+        let guessed = i > 0;
+        if version.is_some() && guessed && *candidate != full_name {
+            verbosity.detail(&format!(
+                "skipping {}: the version pin is for another distribution",
+                candidate
+            ));
+            continue;
+        }
+        let spec = download_spec(candidate, version);
+        let expected_module = guessed.then_some(module_path);
+        match try_download_and_import(py, &spec, sources_of, expected_module, verbosity, &mut f) {
+            Err(e) if lacks_module(py, &e, module_path) => {
+                verbosity.detail(&format!(
+                    "{} does not provide {}: {}",
+                    candidate, module_path, e
+                ));
+            }
+            result => return result,
+        }
+    }
+    let message = format!(
+        "No distribution provides module '{}' (tried {}). Import names can differ from \
+         distribution names; name the distribution explicitly, e.g. 'DIST::{}'",
+        module_path,
+        candidates.join(", "),
+        module_path
+    );
+    Err(PyErr::new::<pyo3::exceptions::PyModuleNotFoundError, _>(
+        message,
+    ))
+}
+
+/// Whether sys.path provides the parent package of a dotted module (the module itself
+/// may be compiled); a namespace distribution can ship the parent but not the module
+pub fn provides_module(py: Python, module_path: &str) -> bool {
+    match module_path.rsplit_once('.') {
+        Some((parent, _)) => crate::explorer::module_source_path(py, parent).is_some(),
+        None => true,
+    }
+}
+
+/// Whether a download attempt failed because the distribution doesn't exist or doesn't
+/// contain the module (rather than, say, the module missing a dependency)
+fn lacks_module(py: Python, error: &PyErr, module_path: &str) -> bool {
+    if !error.is_instance_of::<pyo3::exceptions::PyModuleNotFoundError>(py) {
+        return false;
+    }
+    let message = error.to_string();
+    if message.contains("not found on") {
+        return true;
+    }
+    message
+        .split("No module named '")
+        .nth(1)
+        .and_then(|rest| rest.split('\'').next())
+        .is_some_and(|missing| {
+            module_path == missing || module_path.starts_with(&format!("{}.", missing))
+        })
+}
+
+/// Run `f` with an already downloaded package temporarily on sys.path
+pub fn with_downloaded_package<F, R>(
+    py: Python,
+    downloader: &PackageDownloader,
+    f: F,
+) -> PyResult<R>
+where
+    F: FnOnce(&PackageDownloader) -> PyResult<R>,
+{
+    let base_name = downloader.package_name();
+    let package_path = downloader.package_path().ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+            "{} has not been downloaded",
+            base_name
+        ))
+    })?;
 
     // Add to sys.path temporarily with RAII cleanup
     let sys = py.import("sys")?;
@@ -113,20 +439,49 @@ where
     {
         package_path.parent().unwrap()
     } else {
-        &package_path
+        package_path
     };
 
     let parent_dir_str = parent_dir.to_str().unwrap();
-    sys_path.call_method1("insert", (0, parent_dir_str))?;
 
     // Create guard for cleanup
-    let _guard = PathGuard {
-        sys_path: &sys_path,
-        path: parent_dir_str,
-    };
+    let _guard = PathGuard::prepend(&sys_path, parent_dir_str)?;
 
     // Execute the provided function
-    f()
+    f(downloader)
+}
+
+/// Split the version pin off an `import_object` path, wherever it appears
+/// e.g., "prefect@2.20.0:flow" -> ("prefect:flow", Some("2.20.0"))
+/// e.g., "prefect.flow@2.20.0" -> ("prefect.flow", Some("2.20.0"))
+pub fn strip_object_version(path: &str) -> (String, Option<&str>) {
+    match parse_object_spec(path) {
+        Some((_, module_path, object_path, version)) => {
+            let separator = if path.contains(':') { ":" } else { "." };
+            (format!("{}{}{}", module_path, separator, object_path), version)
+        }
+        None => {
+            let (module_path, version) = parse_package_spec(path);
+            (module_path.to_string(), version)
+        }
+    }
+}
+
+/// Fail when the locally installed distribution providing `module_name` doesn't
+/// satisfy an explicit pin (distributions without metadata aren't checked)
+pub fn check_version_pin(py: Python, module_name: &str, pin: &str) -> PyResult<()> {
+    if is_url(pin) {
+        return Ok(());
+    }
+    match crate::dist_metadata::DistMetadata::from_installed(py, module_name) {
+        Some(installed) if !pin_matches(&installed.version, pin) => {
+            Err(PyErr::new::<pyo3::exceptions::PyImportError, _>(format!(
+                "{} {} is installed, but {} was requested",
+                installed.name, installed.version, pin
+            )))
+        }
+        _ => Ok(()),
+    }
 }
 
 /// Import an object from a module path (internal implementation)
@@ -187,3 +542,173 @@ pub fn import_object_impl(py: Python, import_path: &str) -> PyResult<PyObject> {
         py.import(module_name).map(|m| m.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_spec_requirements() {
+        assert_eq!(parse_full_spec("json"), (None, "json", None));
+        assert_eq!(
+            parse_full_spec("pillow::PIL@10.0.0"),
+            (Some("pillow"), "PIL", Some("10.0.0"))
+        );
+        assert_eq!(
+            parse_full_spec("uvicorn[standard]>=0.30; python_version >= '3.9'"),
+            (None, "uvicorn", Some(">=0.30"))
+        );
+        assert_eq!(
+            parse_full_spec("pkg @ https://example.com/pkg-1.0-py3-none-any.whl"),
+            (None, "pkg", Some("https://example.com/pkg-1.0-py3-none-any.whl"))
+        );
+    }
+
+    #[test]
+    fn test_exact_pins() {
+        assert_eq!(parse_package_spec("requests==2.31.0"), ("requests", Some("2.31.0")));
+        assert_eq!(parse_package_spec("pkg[x]==1.2"), ("pkg[x]", Some("1.2")));
+        assert_eq!(parse_package_spec("pkg==1.*"), ("pkg==1.*", None));
+        assert_eq!(parse_package_spec("pkg==1.0,<2"), ("pkg==1.0,<2", None));
+        assert_eq!(parse_package_spec("pkg===1.0"), ("pkg===1.0", None));
+        assert_eq!(
+            parse_full_spec("requests==2.31.0"),
+            (None, "requests", Some("2.31.0"))
+        );
+        assert_eq!(parse_full_spec("pkg[x]==1.2"), (None, "pkg", Some("1.2")));
+        assert_eq!(
+            parse_full_spec("pillow::PIL==10.0.0"),
+            (Some("pillow"), "PIL", Some("10.0.0"))
+        );
+        assert_eq!(parse_full_spec("pkg>=1.0"), (None, "pkg", Some(">=1.0")));
+        assert_eq!(
+            parse_object_spec("toml==0.10.2:loads"),
+            Some((None, "toml", "loads", Some("0.10.2")))
+        );
+        assert_eq!(
+            parse_object_spec("toml.loads==0.10.2"),
+            Some((None, "toml", "loads", Some("0.10.2")))
+        );
+        assert_eq!(
+            strip_object_version("prefect==2.20.0:flow"),
+            ("prefect:flow".to_string(), Some("2.20.0"))
+        );
+    }
+
+    #[test]
+    fn test_check_pins() {
+        assert!(check_pins("pkg@1.0").is_ok());
+        assert!(check_pins("pkg==1.0").is_ok());
+        assert!(check_pins("pkg[x]==1.2").is_ok());
+        assert!(check_pins("pkg @ https://example.com/pkg-1.0.tar.gz").is_ok());
+        assert!(check_pins("pkg@1.0==2.0").is_err());
+        assert!(check_pins("pkg==1.0:func@2.0").is_err());
+    }
+
+    #[test]
+    fn test_download_spec() {
+        assert_eq!(download_spec("toml", None), "toml");
+        assert_eq!(download_spec("toml", Some("0.10.2")), "toml@0.10.2");
+        assert_eq!(download_spec("uvicorn", Some(">=0.30")), "uvicorn>=0.30");
+        assert_eq!(
+            download_spec("pkg", Some("https://example.com/pkg.whl")),
+            "pkg @ https://example.com/pkg.whl"
+        );
+    }
+
+    #[test]
+    fn test_download_candidates() {
+        assert_eq!(
+            download_candidates("google.cloud.storage"),
+            vec!["google", "google-cloud-storage", "google-cloud"]
+        );
+        assert_eq!(
+            download_candidates("opentelemetry.sdk"),
+            vec!["opentelemetry", "opentelemetry-sdk"]
+        );
+        assert_eq!(download_candidates("toml"), vec!["toml"]);
+        assert_eq!(download_candidates("requests.adapters"), vec!["requests"]);
+    }
+
+    #[test]
+    fn test_parse_object_spec_colon() {
+        assert_eq!(
+            parse_object_spec("json:loads"),
+            Some((None, "json", "loads", None))
+        );
+    }
+
+    #[test]
+    fn test_parse_object_spec_dot() {
+        assert_eq!(
+            parse_object_spec("os.path.join"),
+            Some((None, "os.path", "join", None))
+        );
+        assert_eq!(parse_object_spec("json"), None);
+    }
+
+    #[test]
+    fn test_dotted_object_specs() {
+        assert_eq!(
+            dotted_object_specs("pkg.Client.send@1.0"),
+            vec!["pkg.Client:send@1.0", "pkg:Client.send@1.0"]
+        );
+        assert_eq!(
+            dotted_object_specs("pillow::PIL.Image.open"),
+            vec!["pillow::PIL.Image:open", "pillow::PIL:Image.open"]
+        );
+        assert!(dotted_object_specs("json:loads").is_empty());
+        assert!(dotted_object_specs("json").is_empty());
+    }
+
+    #[test]
+    fn test_strip_object_version() {
+        assert_eq!(
+            strip_object_version("prefect@2.20.0:flow"),
+            ("prefect:flow".to_string(), Some("2.20.0"))
+        );
+        assert_eq!(
+            strip_object_version("prefect:flow@2.20.0"),
+            ("prefect:flow".to_string(), Some("2.20.0"))
+        );
+        assert_eq!(
+            strip_object_version("os.path.join"),
+            ("os.path.join".to_string(), None)
+        );
+        assert_eq!(
+            strip_object_version("toml@0.10.2"),
+            ("toml".to_string(), Some("0.10.2"))
+        );
+    }
+
+    #[test]
+    fn test_parse_object_spec_package_override() {
+        assert_eq!(
+            parse_object_spec("beautifulsoup4::bs4:BeautifulSoup"),
+            Some((Some("beautifulsoup4"), "bs4", "BeautifulSoup", None))
+        );
+        assert_eq!(
+            parse_object_spec("pillow::PIL.Image:open"),
+            Some((Some("pillow"), "PIL.Image", "open", None))
+        );
+    }
+
+    #[test]
+    fn test_parse_object_spec_version_positions() {
+        // Version after the object
+        assert_eq!(
+            parse_object_spec("beautifulsoup4::bs4:BeautifulSoup@4.12.3"),
+            Some((Some("beautifulsoup4"), "bs4", "BeautifulSoup", Some("4.12.3")))
+        );
+        // Version after the module
+        assert_eq!(
+            parse_object_spec("toml@0.10.2:loads"),
+            Some((None, "toml", "loads", Some("0.10.2")))
+        );
+        // Version with dot syntax
+        assert_eq!(
+            parse_object_spec("toml.loads@0.10.2"),
+            Some((None, "toml", "loads", Some("0.10.2")))
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use pyo3::prelude::*;
+
+/// How much pretty-mod writes to stderr
+/// - Quiet: nothing, ever
+/// - Normal: one-line notices and warnings
+/// - Verbose: per-step details (modules explored, chain hops, downloads)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+impl Verbosity {
+    /// Map the public `quiet`/`verbose` flags onto a verbosity level (quiet wins)
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    /// Write a one-line notice or warning (normal and verbose)
+    pub fn notice(self, message: &str) {
+        if self >= Verbosity::Normal {
+            write_stderr(message);
+        }
+    }
+
+    /// Write a per-step detail line (verbose only)
+    pub fn detail(self, message: &str) {
+        if self >= Verbosity::Verbose {
+            write_stderr(&format!("  {}", message));
+        }
+    }
+}
+
+/// Write a line to Python's sys.stderr so it interleaves with Python output
+fn write_stderr(message: &str) {
+    Python::with_gil(|py| {
+        let Ok(stderr) = py.import("sys").and_then(|sys| sys.getattr("stderr")) else {
+            return;
+        };
+        let line = if message.ends_with('\n') {
+            message.to_string()
+        } else {
+            format!("{}\n", message)
+        };
+        let _ = stderr.call_method1("write", (line,));
+        let _ = stderr.call_method0("flush");
+    });
+}
@@ -5,3 +5,25 @@ import pytest
 def disable_colors_for_tests(monkeypatch):
     """Disable colors for all tests."""
     monkeypatch.setenv("PRETTY_MOD_NO_COLOR", "1")
+
+
+@pytest.fixture
+def make_package(tmp_path, monkeypatch):
+    """Write `{path: content}` files under a new sys.path entry and return the entry.
+
+    Paths are relative to the entry, e.g. {"pkg/__init__.py": "", "pkg/core.py": "..."};
+    bytes are written as-is (e.g., empty stand-ins for compiled extensions).
+    """
+
+    def make(files):
+        for relative, content in files.items():
+            path = tmp_path / relative
+            path.parent.mkdir(parents=True, exist_ok=True)
+            if isinstance(content, bytes):
+                path.write_bytes(content)
+            else:
+                path.write_text(content)
+        monkeypatch.syspath_prepend(str(tmp_path))
+        return tmp_path
+
+    return make
@@ -0,0 +1,7 @@
+"""Fixture package for golden output tests."""
+
+from .shapes import Circle, area
+
+__all__ = ["Circle", "area", "VERSION"]
+
+VERSION = "1.0"
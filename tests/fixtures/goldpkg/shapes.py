@@ -0,0 +1,30 @@
+import math
+from abc import ABC, abstractmethod
+
+PI = math.pi
+UNIT = 1
+
+
+class Shape(ABC):
+    @abstractmethod
+    def area(self) -> float: ...
+
+
+class Circle(Shape):
+    def __init__(self, radius: float = 1.0):
+        self.radius = radius
+
+    def area(self) -> float:
+        return PI * self.radius**2
+
+
+def area(shape: Shape) -> float:
+    return shape.area()
+
+
+def zoom(shape: Shape, factor: int = 2) -> Shape:
+    return shape
+
+
+def _private_helper():
+    pass
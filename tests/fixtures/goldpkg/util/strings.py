@@ -0,0 +1,5 @@
+MAX_LEN = 10
+
+
+def slugify(text: str, sep: str = "-") -> str:
+    return sep.join(text.lower().split())
@@ -0,0 +1,180 @@
+import importlib
+import inspect
+
+import pytest
+from pretty_mod import check_exports, check_signature, get_signature, sig_of
+
+JSON_LOADS = (
+    "loads(s, *, cls=None, object_hook=None, parse_float=None, parse_int=None, "
+    "parse_constant=None, object_pairs_hook=None, **kw)"
+)
+
+
+class TestGetSignature:
+    def test_structured_parameters(self):
+        sig = get_signature("json:loads")
+        assert sig is not None
+        assert sig.name == "loads"
+        kinds = {p.name: p.kind for p in sig.parameters}
+        assert kinds["s"] == "positional_or_keyword"
+        assert kinds["cls"] == "keyword_only"
+        assert kinds["kw"] == "var_keyword"
+        assert str(sig) == JSON_LOADS
+
+    def test_not_found(self):
+        assert get_signature("json:does_not_exist") is None
+
+
+SIMPLE = '''
+from __future__ import annotations
+
+import time
+
+
+def fetch(url: str, /, retries: int = 3, *args: str, timeout: float = 1.5, **kw) -> bytes:
+    return b""
+
+
+def configure(name="default", flags=(1, 2), enabled=True, extra=None):
+    pass
+
+
+def timed(clock=time.monotonic, repeat: int = 1):
+    pass
+'''
+
+
+class TestToInspectSignature:
+    @pytest.fixture
+    def module(self, make_package):
+        make_package({"simple_mod.py": SIMPLE})
+        return importlib.import_module("simple_mod")
+
+    @pytest.mark.parametrize("name", ["fetch", "configure"])
+    def test_round_trip(self, module, name):
+        sig = get_signature(f"simple_mod:{name}")
+        assert sig.to_inspect_signature() == inspect.signature(getattr(module, name))
+
+    def test_json_loads(self):
+        from json import loads
+
+        converted = get_signature("json:loads").to_inspect_signature()
+        assert converted == inspect.signature(loads)
+
+    def test_non_literal_default_is_empty(self, module):
+        sig = get_signature("simple_mod:timed").to_inspect_signature()
+        assert sig.parameters["clock"].default is inspect.Parameter.empty
+        assert sig.parameters["repeat"].default == 1
+        assert sig.return_annotation is inspect.Signature.empty
+
+    def test_parameter(self):
+        param = get_signature("json:loads").parameters[1]
+        converted = param.to_inspect_parameter()
+        assert converted.name == "cls"
+        assert converted.kind is inspect.Parameter.KEYWORD_ONLY
+        assert converted.default is None
+
+
+DECORATED = '''
+import functools
+
+
+def logged(fn):
+    @functools.wraps(fn)
+    def wrapper(*args, **kwargs):
+        return fn(*args, **kwargs)
+
+    return wrapper
+
+
+@logged
+def fetch(url: str, retries: int = 3) -> bytes:
+    return b""
+
+
+class Client:
+    def get(self, path: str, *, timeout: float = 1.0) -> bytes:
+        return b""
+'''
+
+
+class TestSigOf:
+    @pytest.fixture
+    def module(self, make_package):
+        make_package({"decorated_mod.py": DECORATED})
+        return importlib.import_module("decorated_mod")
+
+    def test_decorated_function(self, module):
+        sig = sig_of(module.fetch)
+        assert str(sig) == "fetch(url: str, retries: int=3) -> bytes"
+
+    def test_methods(self, module):
+        assert str(sig_of(module.Client.get)) == str(sig_of(module.Client().get))
+        assert [p.name for p in sig_of(module.Client().get).parameters] == [
+            "self",
+            "path",
+            "timeout",
+        ]
+
+    def test_matches_get_signature(self):
+        from json import loads
+
+        assert str(sig_of(loads)) == str(get_signature("json:loads"))
+
+    def test_runtime_fallback_for_local_functions(self):
+        def local(a, b=2):
+            pass
+
+        sig = sig_of(local)
+        assert sig.name == "local"
+        assert [p.name for p in sig.parameters] == ["a", "b"]
+
+    def test_rejects_instances(self):
+        with pytest.raises(TypeError, match="function, class or method"):
+            sig_of(42)
+
+
+class TestCheckSignature:
+    def test_partial_params(self):
+        report = check_signature("json:loads", params=["s", "cls", "**kw"])
+        assert report.ok
+        assert bool(report)
+        assert report.matched == ["s", "cls", "kw"]
+        assert "object_hook=None" in report.unexpected
+
+    def test_missing_param(self):
+        report = check_signature("json:loads", params=["s", "encoding"])
+        assert not report.ok
+        assert report.missing == ["encoding"]
+
+    def test_kinds_are_checked_with_markers(self):
+        report = check_signature("json:loads", params=["s", "/"])
+        assert report.mismatched == [
+            "s: expected positional_only, found positional_or_keyword"
+        ]
+
+    def test_full_expected_signature(self):
+        assert check_signature("json:loads", expected=JSON_LOADS).ok
+        report = check_signature("json:loads", expected="loads(s, *, cls=None)")
+        assert not report.ok
+        assert "**kw" in report.unexpected
+
+    def test_strict_raises(self):
+        with pytest.raises(AssertionError, match="missing: encoding"):
+            check_signature("json:loads", params=["encoding"], strict=True)
+
+    def test_requires_expectation(self):
+        with pytest.raises(ValueError):
+            check_signature("json:loads")
+
+
+class TestCheckExports:
+    def test_exports(self):
+        report = check_exports("json", ["dump", "dumps", "load", "loads"])
+        assert report.ok
+        assert "JSONDecoder" in report.unexpected
+
+    def test_exact_and_strict(self):
+        assert not check_exports("json", ["dump", "dumps"], exact=True).ok
+        with pytest.raises(AssertionError, match="missing: parse"):
+            check_exports("json", ["parse"], strict=True)
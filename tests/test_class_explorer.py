@@ -0,0 +1,150 @@
+import json
+import sys
+from unittest.mock import patch
+
+import pytest
+from pretty_mod.cli import main
+from pretty_mod.explorer import display_methods, explore_class
+
+BASE = '''
+class Shape:
+    def __init__(self, name: str):
+        self.name = name
+
+    def area(self) -> float:
+        return 0.0
+
+    def describe(self) -> str:
+        return self.name
+'''
+
+MODELS = '''
+from .base import Shape
+
+
+class Circle(Shape):
+    def __init__(self, radius: float):
+        self._radius = radius
+
+    @property
+    def radius(self) -> float:
+        return self._radius
+
+    @radius.setter
+    def radius(self, value: float) -> None:
+        self._radius = value
+
+    @classmethod
+    def unit(cls) -> "Circle":
+        return cls(1.0)
+
+    @staticmethod
+    def validate(radius: float) -> bool:
+        return radius > 0
+
+    def area(self) -> float:
+        return 3.14 * self._radius**2
+
+    def _private(self):
+        pass
+
+    def __enter__(self) -> "Circle":
+        return self
+
+    def __exit__(self, *exc) -> None:
+        pass
+
+    def __repr__(self) -> str:
+        return f"Circle({self._radius})"
+'''
+
+
+@pytest.fixture
+def shapes_package(make_package):
+    make_package(
+        {
+            "shapes/__init__.py": "from .models import Circle\n",
+            "shapes/base.py": BASE,
+            "shapes/models.py": MODELS,
+        }
+    )
+    return "shapes"
+
+
+class TestExploreClass:
+    def test_groups_methods(self, shapes_package):
+        view = explore_class("shapes:Circle")
+        assert view["name"] == "Circle"
+        # Resolved through the re-export in shapes/__init__.py
+        assert view["module"] == "shapes.models"
+        assert view["bases"] == ["Shape"]
+        assert [m["name"] for m in view["constructors"]] == ["__init__"]
+        assert [m["name"] for m in view["methods"]] == ["area"]
+        assert [m["name"] for m in view["properties"]] == ["radius"]
+        assert {m["name"]: m["kind"] for m in view["class_methods"]} == {
+            "unit": "classmethod",
+            "validate": "staticmethod",
+        }
+        assert view["inherited"] == []
+
+    def test_inherited_members(self, shapes_package):
+        view = explore_class("shapes:Circle", include_inherited=True)
+        inherited = {m["name"]: m["defined_in"] for m in view["inherited"]}
+        # area is overridden, so only describe comes from the base
+        assert inherited == {"describe": "Shape"}
+
+    def test_forward_reference_return_type(self, shapes_package):
+        view = explore_class("shapes:Circle")
+        unit = next(m for m in view["class_methods"] if m["name"] == "unit")
+        # -> "Circle" is shown without quotes
+        assert unit["signature"]["return_type"] == "Circle"
+
+    def test_missing_class(self, shapes_package):
+        with pytest.raises(ImportError):
+            explore_class("shapes:Square", quiet=True)
+
+
+class TestDisplayMethods:
+    def test_pretty(self, shapes_package):
+        result = display_methods("shapes:Circle", include_inherited=True)
+        assert result.startswith("🔷 Circle (shapes.models)")
+        assert "├── Bases: Shape" in result
+        assert "radius: float" in result
+        assert "unit(cls) -> 'Circle' [classmethod]" in result
+        assert "describe(self) -> str (from Shape)" in result
+        assert "_private" not in result
+
+    def test_json(self, shapes_package):
+        data = json.loads(display_methods("shapes:Circle", format="json"))
+        assert data["methods"][0]["signature"]["return_type"] == "float"
+        assert "defined_in" not in data["methods"][0]
+
+    def test_cli(self, shapes_package, capsys):
+        with patch.object(
+            sys, "argv", ["pretty-mod", "methods", "shapes:Circle", "--inherited"]
+        ):
+            main()
+        assert "(from Shape)" in capsys.readouterr().out
+
+
+class TestDunders:
+    def test_hidden_by_default(self, shapes_package):
+        view = explore_class("shapes:Circle")
+        assert [m["name"] for m in view["methods"]] == ["area"]
+
+    def test_protocol(self, shapes_package):
+        view = explore_class("shapes:Circle", dunders="protocol")
+        assert [m["name"] for m in view["methods"]] == ["area", "__enter__", "__exit__"]
+
+    def test_all_in_json(self, shapes_package):
+        data = json.loads(display_methods("shapes:Circle", format="json", dunders="all"))
+        assert [m["name"] for m in data["methods"]] == [
+            "area",
+            "__enter__",
+            "__exit__",
+            "__repr__",
+        ]
+
+    def test_invalid(self, shapes_package):
+        with pytest.raises(ValueError, match="dunders"):
+            explore_class("shapes:Circle", dunders="some")
@@ -1,4 +1,9 @@
+import io
+import json
+import os
+import subprocess
 import sys
+from concurrent.futures import ThreadPoolExecutor
 from unittest.mock import patch
 
 import pytest
@@ -26,6 +31,162 @@ class TestCLIDisplayFunctions:
         assert "📎 print" in result  # The function name is normalized to 'print'
         # print_ is a function in six that maps to print
 
+    def test_display_signature_never_imports_stdlib(self):
+        # Stdlib modules are read from source, never imported for runtime inspection
+        sys.modules.pop("timeit", None)
+        result = display_signature("timeit:no_such_function")
+        assert "signature not available" in result
+        assert "timeit" not in sys.modules
+
+        assert "source" not in json.loads(display_signature("json:loads", format="json"))
+
+    def test_installed_distribution_name_maps_to_import_name(self, capfd):
+        # rich (a dev dependency) pulls in markdown-it-py, imported as markdown_it
+        pytest.importorskip("markdown_it")
+        display_tree("markdown-it-py", 1, quiet=True)
+        out = capfd.readouterr().out
+        assert "📦 markdown_it" in out
+        assert "not found locally" not in out
+
+        result = display_signature("markdown-it-py:MarkdownIt", quiet=True)
+        assert "signature not available" not in result
+
+    def test_display_signature_compact(self):
+        result = display_signature("json:dumps", format="compact")
+        assert result.startswith("dumps(obj, *, skipkeys=False")
+        assert "\n" not in result
+
+    def test_display_signature_compact_max_width(self):
+        result = display_signature("json:dumps", format="compact", width=40)
+        lines = result.splitlines()
+        assert len(lines) > 1
+        assert all(len(line) <= 40 for line in lines)
+        # Continuations align after "dumps("
+        assert all(line.startswith(" " * 6) for line in lines[1:])
+
+    def test_display_signature_pretty_wraps_long_parameters(self):
+        result = display_signature("json:dumps", width=20)
+        lines = result.splitlines()
+        # "ensure_ascii=True" no longer fits and continues under the name
+        assert "├──  ensure_ascii" in lines
+        continuation = lines[lines.index("├──  ensure_ascii") + 1]
+        assert continuation == "│      =True"
+
+
+class TestJsonOut:
+    """`json_out` writes the JSON result alongside the formatted output"""
+
+    def test_tree_json_out(self, tmp_path, capfd):
+        path = tmp_path / "tree.json"
+        display_tree("json", 1, json_out=str(path))
+        assert "📦 json" in capfd.readouterr().out
+        data = json.loads(path.read_text())
+        assert data["module"] == "json"
+        assert "decoder" in data["tree"]["submodules"]
+        assert list(tmp_path.iterdir()) == [path]
+
+    def test_signature_json_out(self, tmp_path):
+        path = tmp_path / "sig.json"
+        result = display_signature("json:loads", json_out=str(path))
+        assert "📎 loads" in result
+        assert json.loads(path.read_text())["name"] == "loads"
+
+    def test_failed_write_keeps_output(self, tmp_path):
+        path = tmp_path / "missing" / "sig.json"
+        with pytest.raises(OSError, match="Failed to write JSON output") as exc_info:
+            display_signature("json:loads", json_out=str(path))
+        assert "📎 loads" in exc_info.value.output  # type: ignore[attr-defined]
+
+    def test_cli_failed_write_prints_signature(self, tmp_path, capsys):
+        path = tmp_path / "missing" / "sig.json"
+        argv = ["pretty-mod", "sig", "json:loads", "--json-out", str(path)]
+        with patch.object(sys, "argv", argv):
+            with pytest.raises(SystemExit) as exc_info:
+                main()
+        assert exc_info.value.code == 1  # type: ignore[attr-defined]
+        captured = capsys.readouterr()
+        assert "📎 loads" in captured.out
+        assert "Failed to write JSON output" in captured.err
+
+
+class TestStdin:
+    """`-` parses a single module's source piped on stdin"""
+
+    SOURCE = (
+        "import httpx\n"
+        "LIMIT = 3\n"
+        "def make_client(base_url: str, *, retries: int = LIMIT) -> httpx.Client: ...\n"
+        "class Codec:\n"
+        "    def encode(self, value): ...\n"
+    )
+
+    @pytest.fixture
+    def piped(self, monkeypatch):
+        monkeypatch.setattr(sys, "stdin", io.StringIO(self.SOURCE))
+
+    def test_tree(self, piped, capfd):
+        display_tree("-", format="json")
+        data = json.loads(capfd.readouterr().out)
+        assert data["module"] == "<stdin>"
+        api = data["tree"]["api"]
+        assert api["functions"] == [{"name": "make_client", "line": 3}]
+        assert api["classes"] == [{"name": "Codec", "line": 4}]
+        assert api["constants"] == [{"name": "LIMIT", "line": 2}]
+        # Piped source has no file
+        assert "file" not in data["tree"]
+
+    def test_signature(self, piped):
+        result = display_signature("-:make_client", format="compact")
+        assert result == (
+            "make_client(base_url: str, *, retries: int=LIMIT) -> httpx.Client"
+        )
+
+    def test_missing_object(self, piped):
+        assert "signature not available" in display_signature("-:missing")
+
+    def test_cli_sig(self, piped, capsys):
+        argv = ["pretty-mod", "sig", "-:make_client", "-o", "json"]
+        with patch.object(sys, "argv", argv):
+            main()
+        assert json.loads(capsys.readouterr().out)["name"] == "make_client"
+
+
+class TestEntryPoints:
+    """Console scripts are listed in trees and resolve to their functions"""
+
+    def test_tree_lists_scripts(self, capfd):
+        display_tree("pytest", 0, quiet=True)
+        out = capfd.readouterr().out
+        assert "scripts:" in out
+        assert "pytest → pytest:console_main" in out
+
+    def test_json_tree_scripts(self, capfd):
+        display_tree("pytest", 0, quiet=True, format="json")
+        data = json.loads(capfd.readouterr().out)
+        assert data["tree"]["scripts"]["pytest"] == "pytest:console_main"
+
+    def test_submodules_have_no_scripts(self, capfd):
+        display_tree("_pytest.config", 0, quiet=True, format="json")
+        assert "scripts" not in json.loads(capfd.readouterr().out)["tree"]
+
+    def test_signature_of_entry_point(self):
+        result = display_signature("pytest:pytest", format="compact", entry_point=True)
+        assert result.startswith("console_main(")
+
+    def test_unknown_script(self):
+        with pytest.raises(ValueError, match="no script 'nope'.*pytest"):
+            display_signature("pytest:nope", entry_point=True)
+
+    def test_invalid_spec(self):
+        with pytest.raises(ValueError, match="distribution:script"):
+            display_signature("pytest", entry_point=True)
+
+    def test_cli(self, capsys):
+        argv = ["pretty-mod", "sig", "--entry-point", "pytest:py.test", "-o", "compact"]
+        with patch.object(sys, "argv", argv):
+            main()
+        assert capsys.readouterr().out.startswith("console_main(")
+
 
 class TestCLIMain:
     def test_main_with_help(self):
@@ -76,6 +237,78 @@ class TestPackageDownload:
         # Just test that it doesn't raise an exception
         display_tree("toml.decoder", 1, quiet=True)
 
+    def test_auto_download_pep508_requirement(self):
+        """Test that full PEP 508 requirement strings are accepted as specs."""
+        # Specifier sets, extras, and markers are parsed rather than split ad hoc
+        display_tree("toml>=0.10,<0.11; python_version >= '3.9'", 1, quiet=True)
+        display_tree("toml[extra] (==0.10.2)", 1, quiet=True)
+
+    @pytest.mark.parametrize("spec", ["toml==0.10.2:loads", "toml:loads==0.10.2"])
+    def test_pip_style_pin(self, spec):
+        """`pkg==X.Y.Z` pins exactly like `pkg@X.Y.Z`."""
+        result = display_signature(spec, quiet=True, format="compact")
+        assert result.startswith("loads(")
+
+    def test_pip_style_pin_with_extras(self):
+        display_tree("toml[extra]==0.10.2", 1, quiet=True)
+
+    def test_concurrent_downloads(self):
+        """Threads downloading at once don't clobber each other's sys.path entries."""
+        specs = ["toml@0.10.2:loads", "toml@0.10.1:loads", "toml==0.10.2:dumps"] * 3
+        sys_path_before = list(sys.path)
+        with ThreadPoolExecutor(max_workers=len(specs)) as pool:
+            results = list(
+                pool.map(
+                    lambda spec: display_signature(spec, quiet=True, format="compact"),
+                    specs,
+                )
+            )
+        for spec, result in zip(specs, results):
+            assert result.startswith(spec.rsplit(":", 1)[-1] + "("), result
+        assert sys.path == sys_path_before
+
+    def test_conflicting_pins(self):
+        with pytest.raises(ValueError, match="Conflicting version pins"):
+            display_signature("toml@0.10.1==0.10.2:loads", quiet=True)
+        with pytest.raises(ValueError, match="Conflicting version pins"):
+            display_tree("toml@0.10.1==0.10.2", 1, quiet=True)
+
+    @pytest.mark.parametrize(
+        "module_path",
+        ["google.cloud.storage", "azure.storage.blob", "opentelemetry.sdk"],
+    )
+    def test_auto_download_namespace_package(self, module_path, capsys):
+        """Namespace modules fall back to the dashed distribution providing them."""
+        display_tree(module_path, 1, quiet=True)
+        captured = capsys.readouterr()
+        assert "Cannot explore" not in captured.out
+        assert module_path.rsplit(".", 1)[-1] in captured.out
+
+    def test_download_by_distribution_name(self, capsys):
+        """A dist installing into a namespace explores its package, not the namespace."""
+        display_tree("azure-identity", 1, quiet=True)
+        out = capsys.readouterr().out
+        assert "Cannot explore" not in out
+        assert "DefaultAzureCredential" in out
+
+        sig = display_signature(
+            "azure-identity::azure.identity:DefaultAzureCredential", quiet=True
+        )
+        assert "signature not available" not in sig
+
+    def test_download_namespace_without_distribution(self, capsys):
+        display_tree("google.pretty_mod_absent", 1, quiet=True)
+        captured = capsys.readouterr()
+        assert "No distribution provides module" in captured.out
+        assert "tried google, google-pretty_mod_absent" in captured.out
+
+    def test_download_tries_only_the_base_package(self, capsys):
+        """Dashed names are only guessed under namespace roots like google or azure."""
+        display_tree("pretty_mod_missing_pkg.sub.mod", 1, quiet=True)
+        out = capsys.readouterr().out
+        assert "Cannot explore pretty_mod_missing_pkg.sub.mod" in out
+        assert "No distribution provides module" not in out
+
     def test_download_with_quiet_flag(self, capsys):
         """Test that --quiet suppresses download messages."""
         # Use a package that's unlikely to be installed
@@ -115,3 +348,176 @@ class TestPackageDownload:
                 with pytest.raises(SystemExit) as exc_info:
                     main()
                 assert exc_info.value.code == 1  # type: ignore[attr-defined]
+
+
+class TestProjectTree:
+    def test_tree_dot_explores_src_layout(self, tmp_path, monkeypatch, capfd):
+        (tmp_path / "pyproject.toml").write_text('[project]\nname = "my-tool"\n')
+        package = tmp_path / "src" / "my_tool"
+        package.mkdir(parents=True)
+        (package / "__init__.py").write_text("def run(argv: list[str]) -> int: ...\n")
+        monkeypatch.chdir(tmp_path)
+
+        display_tree(".", 1)
+        out = capfd.readouterr().out
+        assert out.startswith("📦 my_tool")
+        assert "run" in out
+
+    def test_project_with_several_packages(self, tmp_path, capfd):
+        (tmp_path / "pyproject.toml").write_text('[project]\nname = "suite"\n')
+        for name in ("alpha", "beta"):
+            (tmp_path / name).mkdir()
+            (tmp_path / name / "__init__.py").write_text(f"def {name}_main(): ...\n")
+
+        display_tree(str(tmp_path), 1)
+        out = capfd.readouterr().out
+        assert out.startswith("📦 suite")
+        assert "📦 alpha" in out
+        assert "beta_main" in out
+
+    def test_concurrent_trees_share_the_source_root(self, tmp_path, capfd):
+        """Threads exploring one project at once share its sys.path entry."""
+        (tmp_path / "pyproject.toml").write_text('[project]\nname = "shared"\n')
+        (tmp_path / "shared").mkdir()
+        (tmp_path / "shared" / "__init__.py").write_text("def shared_main(): ...\n")
+        outputs = [tmp_path / f"tree-{i}.json" for i in range(8)]
+        sys_path_before = list(sys.path)
+
+        def explore(output):
+            display_tree(str(tmp_path), 1, format="json", json_out=str(output))
+
+        with ThreadPoolExecutor(max_workers=len(outputs)) as pool:
+            list(pool.map(explore, outputs))
+        capfd.readouterr()
+        for output in outputs:
+            tree = json.loads(output.read_text())["tree"]
+            assert tree["api"]["functions"] == [{"name": "shared_main", "line": 1}]
+        assert sys.path == sys_path_before
+
+
+class TestDistributionNames:
+    def _install(self, make_package, dist, files):
+        dist_info = f"{dist.replace('-', '_')}-1.0.dist-info"
+        make_package(
+            {
+                **files,
+                f"{dist_info}/METADATA": f"Name: {dist}\nVersion: 1.0\n",
+                f"{dist_info}/RECORD": "".join(f"{path},,\n" for path in files),
+            }
+        )
+
+    def test_package_inside_namespace(self, make_package, capfd):
+        self._install(
+            make_package,
+            "acme-widgets",
+            {"acme/widgets/__init__.py": "def make_widget(size: int): ...\n"},
+        )
+
+        display_tree("acme-widgets", 1, format="json")
+        data = json.loads(capfd.readouterr().out)
+        assert data["module"] == "acme.widgets"
+        assert data["tree"]["api"]["functions"] == [{"name": "make_widget", "line": 1}]
+        assert data["dist"]["name"] == "acme-widgets"
+
+    def test_several_packages(self, make_package, capfd):
+        self._install(
+            make_package,
+            "acme-suite",
+            {
+                "acme_alpha/__init__.py": "def alpha_main(): ...\n",
+                "acme_beta.py": "def beta_main(): ...\n",
+            },
+        )
+
+        display_tree("acme-suite", 1)
+        out = capfd.readouterr().out
+        assert out.startswith("📦 acme-suite")
+        assert "📦 acme_alpha" in out
+        assert "beta_main" in out
+
+
+class TestApiDiff:
+    def test_local_project_against_release(self, tmp_path):
+        from pretty_mod.explorer import diff_api
+
+        (tmp_path / "pyproject.toml").write_text('[project]\nname = "tinynetrc"\n')
+        (tmp_path / "tinynetrc.py").write_text(
+            "class Netrc:\n"
+            "    def __init__(self, file=None): ...\n"
+            "def brand_new(path: str) -> None: ...\n"
+        )
+        report = diff_api(str(tmp_path), "tinynetrc@1.3.1", quiet=True)
+        assert not report
+        assert "tinynetrc:brand_new" in report.added
+        assert report.local.endswith("(local)")
+        assert report.release == "tinynetrc 1.3.1 (PyPI)"
+        assert json.loads(report.to_json())["added"] == report.added
+
+    def test_exit_status_reflects_changes(self, capsys):
+        with patch.object(sys, "argv", ["pretty-mod", "diff", ".", "pkg@latest"]):
+            with patch("pretty_mod.cli.diff_api") as mock_diff:
+                mock_diff.return_value.__bool__.return_value = False
+                with pytest.raises(SystemExit) as exc_info:
+                    main()
+                assert exc_info.value.code == 1  # type: ignore[attr-defined]
+
+                mock_diff.return_value.__bool__.return_value = True
+                main()
+
+
+class TestOtherInterpreter:
+    def test_python_option_uses_target_sys_path(self, tmp_path, monkeypatch, capfd):
+        # Only the target interpreter sees this directory (PYTHONPATH is read at startup)
+        package = tmp_path / "elsewhere_only"
+        package.mkdir()
+        (package / "__init__.py").write_text("def ping(host: str) -> bool: ...\n")
+        monkeypatch.setenv("PYTHONPATH", str(tmp_path))
+
+        display_tree("elsewhere_only", 1, python=sys.executable)
+        assert "ping" in capfd.readouterr().out
+
+        result = display_signature("elsewhere_only:ping", python=sys.executable)
+        assert "host: str" in result
+
+    def test_python_option_missing_interpreter(self):
+        with pytest.raises(FileNotFoundError):
+            display_tree("json", 1, python="/nonexistent/bin/python")
+
+
+class TestBrowse:
+    def test_prints_static_tree_without_a_terminal(self, capfd):
+        # Under pytest stdout is captured, so browse degrades to the tree
+        with patch.object(sys, "argv", ["pretty-mod", "browse", "json"]):
+            main()
+        out = capfd.readouterr().out
+        assert "📦 json" in out
+        assert "loads" in out
+
+
+class TestAsciiFallback:
+    """Icons follow what the output encoding can represent"""
+
+    def run_tree(self, **env):
+        base = {k: v for k, v in os.environ.items() if k != "PRETTY_MOD_ASCII"}
+        result = subprocess.run(
+            [sys.executable, "-m", "pretty_mod", "tree", "json", "--depth", "0"],
+            capture_output=True,
+            env={**base, "PRETTY_MOD_NO_COLOR": "1", **env},
+        )
+        assert result.returncode == 0
+        return result.stdout.decode("utf-8")
+
+    def test_ascii_encoding_switches_icons(self):
+        assert self.run_tree(PYTHONIOENCODING="ascii").startswith("[M] json")
+
+    def test_utf8_redirect_keeps_icons(self):
+        output = self.run_tree(PYTHONIOENCODING="utf-8", LC_ALL="C")
+        assert output.startswith("📦 json")
+
+    def test_explicit_override(self):
+        assert self.run_tree(PYTHONIOENCODING="ascii", PRETTY_MOD_ASCII="0").startswith(
+            "📦 json"
+        )
+        assert self.run_tree(PYTHONIOENCODING="utf-8", PRETTY_MOD_ASCII="1").startswith(
+            "[M] json"
+        )
@@ -0,0 +1,115 @@
+import json
+import os
+import stat
+import sys
+import time
+from socket import AF_UNIX
+from socket import socket as raw_socket
+
+import pytest
+from pretty_mod import display_signature, query_daemon
+
+pytestmark = pytest.mark.skipif(
+    sys.platform == "win32", reason="the daemon listens on a unix socket"
+)
+
+
+@pytest.fixture
+def daemon(tmp_path, monkeypatch):
+    """A daemon started on first use, on a socket of its own, that sees `tmp_path`"""
+    monkeypatch.setenv("PYTHONPATH", str(tmp_path))
+    monkeypatch.syspath_prepend(str(tmp_path))
+    socket = str(tmp_path / "pm.sock")
+    yield lambda request: query_daemon(request, socket)
+    if os.path.exists(socket):
+        query_daemon({"op": "shutdown"}, socket, spawn=False)
+
+
+class TestDaemon:
+    def test_spawns_on_first_request(self, daemon):
+        assert daemon({"op": "ping"}) == "pong"
+
+    def test_sig_matches_one_shot(self, daemon):
+        request = {"op": "sig", "path": "json:loads", "format": "compact"}
+        assert daemon(request) == display_signature("json:loads", format="compact")
+
+    def test_tree_json(self, daemon):
+        result = daemon({"op": "tree", "module": "json", "depth": 1, "format": "json"})
+        tree = json.loads(result)
+        assert tree["module"] == "json"
+        assert "loads" in [entry["name"] for entry in tree["tree"]["api"]["functions"]]
+
+    def test_search(self, daemon):
+        matches = daemon({"op": "search", "module": "json", "query": "DECODE"})
+        assert "json.decoder:JSONDecodeError" in matches
+        assert "json:loads" not in matches
+
+    def test_reparses_changed_files(self, daemon, tmp_path):
+        module = tmp_path / "warm_mod.py"
+        module.write_text("def f(a): ...\n")
+        request = {"op": "sig", "path": "warm_mod:f", "format": "compact"}
+        assert daemon(request) == "f(a)"
+
+        module.write_text("def f(a, b): ...\n")
+        # Coarse filesystem clocks could otherwise leave the modification time unchanged
+        later = time.time() + 5
+        os.utime(module, (later, later))
+        assert daemon(request) == "f(a, b)"
+
+    def test_reparses_modules_reading_changed_files(self, daemon, tmp_path):
+        package = tmp_path / "warm_pkg"
+        package.mkdir()
+        (package / "__init__.py").write_text(
+            "from . import core\n__all__ = ['top'] + core.__all__\n"
+        )
+        core = package / "core.py"
+        core.write_text("__all__ = ['a']\n")
+        request = {"op": "tree", "module": "warm_pkg", "depth": 0, "format": "json"}
+        assert json.loads(daemon(request))["tree"]["api"]["all"] == ["top", "a"]
+
+        # Only the submodule changes; the package's own file is untouched
+        core.write_text("__all__ = ['a', 'b']\n")
+        later = time.time() + 5
+        os.utime(core, (later, later))
+        assert json.loads(daemon(request))["tree"]["api"]["all"] == ["top", "a", "b"]
+
+    def test_errors_are_raised(self, daemon):
+        with pytest.raises(RuntimeError, match="Unknown op"):
+            daemon({"op": "nope"})
+        with pytest.raises(RuntimeError, match="path"):
+            daemon({"op": "sig"})
+
+    def test_idle_client_does_not_block_others(self, daemon, tmp_path):
+        assert daemon({"op": "ping"}) == "pong"
+        idle = raw_socket(AF_UNIX)
+        idle.connect(str(tmp_path / "pm.sock"))
+        idle.sendall(b'{"op": "pi')
+        try:
+            started = time.time()
+            assert daemon({"op": "ping"}) == "pong"
+            assert time.time() - started < 5
+        finally:
+            idle.close()
+
+    def test_socket_is_private(self, daemon, tmp_path):
+        assert daemon({"op": "ping"}) == "pong"
+        assert stat.S_IMODE(os.stat(tmp_path / "pm.sock").st_mode) == 0o600
+
+    def test_refuses_a_socket_dir_others_can_write(self, tmp_path):
+        shared = tmp_path / "shared"
+        shared.mkdir()
+        shared.chmod(0o777)
+        with pytest.raises(PermissionError, match="only you can write to"):
+            query_daemon({"op": "ping"}, str(shared / "pm.sock"))
+        assert not (shared / "pm.sock").exists()
+
+    def test_shutdown_removes_socket(self, tmp_path):
+        socket = str(tmp_path / "pm.sock")
+        assert query_daemon({"op": "ping"}, socket) == "pong"
+        query_daemon({"op": "shutdown"}, socket, spawn=False)
+        deadline = time.time() + 5
+        while os.path.exists(socket) and time.time() < deadline:
+            time.sleep(0.05)
+        assert not os.path.exists(socket)
+        with pytest.raises(ConnectionError):
+            query_daemon({"op": "ping"}, socket, spawn=False)
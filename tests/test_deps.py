@@ -0,0 +1,98 @@
+import json
+import sys
+import zipfile
+from unittest.mock import patch
+
+import pytest
+from pretty_mod.cli import main
+from pretty_mod.explorer import display_deps
+
+PYPROJECT = """
+[project]
+name = "demo"
+dependencies = [
+    "demo-helpers>=1.0",
+    "broken @ file:///nonexistent/broken-1.0-py3-none-any.whl",
+]
+"""
+
+
+@pytest.fixture
+def project(make_package):
+    return make_package(
+        {
+            # Importable but not installed, so it maps to its normalized name
+            "demo_helpers/__init__.py": "def slugify(text: str) -> str: ...\n",
+            "pyproject.toml": PYPROJECT,
+        }
+    )
+
+
+class TestDisplayDeps:
+    def test_pretty_collects_failures(self, project):
+        result = display_deps(str(project / "pyproject.toml"), quiet=True)
+        assert "📦 demo_helpers" in result
+        assert "slugify" in result
+        # The broken dependency is reported, not fatal
+        assert "Could not explore 1 of 2 dependencies" in result
+        assert "broken @ file:///nonexistent" in result
+
+    def test_json(self, project):
+        entries = json.loads(
+            display_deps(str(project / "pyproject.toml"), quiet=True, format="json")
+        )
+        assert [entry["distribution"] for entry in entries] == [
+            "demo-helpers",
+            "broken",
+        ]
+        assert entries[0]["module"] == "demo_helpers"
+        assert [f["name"] for f in entries[0]["tree"]["api"]["functions"]] == ["slugify"]
+        assert "error" in entries[1]
+
+    def test_requirements_file(self, project):
+        (project / "requirements.txt").write_text(
+            "# runtime\ndemo-helpers  # helpers\n--index-url https://example.com\n"
+        )
+        result = display_deps(str(project / "requirements.txt"), quiet=True)
+        assert "📦 demo_helpers" in result
+        assert "Could not explore" not in result
+
+    def test_cli(self, project, capsys):
+        with patch.object(
+            sys,
+            "argv",
+            ["pretty-mod", "deps", str(project / "pyproject.toml"), "-q"],
+        ):
+            main()
+        assert "slugify" in capsys.readouterr().out
+
+
+def make_wheel(directory, name, source):
+    wheel = directory / f"{name}-1.0-py3-none-any.whl"
+    with zipfile.ZipFile(wheel, "w") as archive:
+        archive.writestr(f"{name}/__init__.py", source)
+    return wheel
+
+
+class TestConcurrentDownloads:
+    def test_missing_dependencies_download_in_one_batch(self, tmp_path, monkeypatch):
+        monkeypatch.setenv("PRETTY_MOD_MAX_DOWNLOADS", "2")
+        alpha = make_wheel(tmp_path, "alpha_pkg", "def first(): ...\n")
+        beta = make_wheel(tmp_path, "beta_pkg", "def second(): ...\n")
+        (tmp_path / "requirements.txt").write_text(
+            f"alpha-pkg @ file://{alpha}\n"
+            "broken @ file:///nonexistent/broken-1.0-py3-none-any.whl\n"
+            f"beta-pkg @ file://{beta}\n"
+        )
+        entries = json.loads(
+            display_deps(str(tmp_path / "requirements.txt"), quiet=True, format="json")
+        )
+        # Input order is kept and one failure does not affect the others
+        assert [entry["distribution"] for entry in entries] == [
+            "alpha-pkg",
+            "broken",
+            "beta-pkg",
+        ]
+        assert [f["name"] for f in entries[0]["tree"]["api"]["functions"]] == ["first"]
+        assert "error" in entries[1]
+        assert [f["name"] for f in entries[2]["tree"]["api"]["functions"]] == ["second"]
@@ -1,4 +1,4 @@
-from pretty_mod import display_tree
+from pretty_mod import display_signature, display_tree
 
 
 class TestDoubleColonSyntax:
@@ -17,3 +17,32 @@ class TestDoubleColonSyntax:
         """Test that regular module syntax still works."""
         # Regular module exploration should work as before
         display_tree("json", max_depth=0, quiet=True)
+
+
+class TestDoubleColonSignature:
+    def test_double_colon_sig(self):
+        """Test that package::module:object syntax downloads the named distribution."""
+        # The import name is bs4, but the distribution is beautifulsoup4
+        result = display_signature("beautifulsoup4::bs4:BeautifulSoup", quiet=True)
+
+        assert "signature not available" not in result
+        assert "📎 BeautifulSoup" in result
+        assert "markup" in result
+
+    def test_double_colon_sig_with_version(self):
+        """Test that a trailing @version is applied to the overridden package."""
+        result = display_signature(
+            "beautifulsoup4::bs4:BeautifulSoup@4.12.3", quiet=True
+        )
+
+        assert "signature not available" not in result
+        assert "📎 BeautifulSoup" in result
+
+    def test_double_colon_sig_not_available_name(self):
+        """Test that the version is stripped from the reported object name."""
+        result = display_signature(
+            "beautifulsoup4::bs4:DefinitelyNotARealName@4.12.3", quiet=True
+        )
+
+        assert "signature not available" in result
+        assert "📎 DefinitelyNotARealName (signature not available)" in result
@@ -1,3 +1,4 @@
+import json
 import sys
 
 import pytest
@@ -40,6 +41,29 @@ class TestImportObject:
         with pytest.raises(ImportError):
             import_object("sys.nonexistent_attribute")
 
+    def test_pin_matches_installed_version(self):
+        pin = pytest.__version__
+        assert import_object(f"pytest@{pin}:main") is pytest.main
+        assert import_object(f"pytest:main@{pin}") is pytest.main
+
+    def test_pin_differs_from_installed_version(self):
+        with pytest.raises(ImportError, match="is installed, but 0.0.1 was requested"):
+            import_object("pytest@0.0.1:main")
+        with pytest.raises(ImportError, match="is installed"):
+            import_object("pytest@0.0.1:main", download=True)
+
+    def test_pin_with_download(self):
+        try:
+            import tinynetrc  # noqa: F401
+
+            pytest.skip("tinynetrc is installed locally")
+        except ImportError:
+            pass
+        with pytest.raises(ImportError):
+            import_object("tinynetrc@1.3.1:Netrc")
+        netrc = import_object("tinynetrc@1.3.1:Netrc", download=True, quiet=True)
+        assert netrc.__name__ == "Netrc"
+
 
 class TestDisplaySignature:
     def test_display_signature_simple_function(self):
@@ -94,3 +118,847 @@ class TestIntegration:
         # Verify it worked
         assert "📦 json" in result
         assert explorer.tree  # Tree should now be populated
+
+    def test_get_tree_string_json(self):
+        explorer = ModuleTreeExplorer("json", max_depth=1)
+        data = json.loads(explorer.get_tree_string(format="json"))
+        assert data["module"] == "json"
+        assert "loads" in [entry["name"] for entry in data["tree"]["api"]["functions"]]
+        assert "dist" not in explorer.tree
+
+    def test_get_tree_string_unknown_format(self):
+        from pretty_mod.explorer import display_tree
+
+        explorer = ModuleTreeExplorer("json", max_depth=1)
+        with pytest.raises(ValueError, match="Unknown format 'yaml'"):
+            explorer.get_tree_string(format="yaml")
+        with pytest.raises(ValueError, match="Unknown format 'yaml'"):
+            display_tree("json", format="yaml")
+
+    def test_runtime_alias_module(self):
+        """os.path is assigned at runtime, so it's explored from its __file__"""
+        tree = ModuleTreeExplorer("os.path", max_depth=1).explore()
+        assert "join" in tree["api"]["functions"]
+
+        result = display_signature("os.path:join")
+        assert "signature not available" not in result
+        assert "join(" in result
+
+    def test_missing_submodules_are_not_imported(self, make_package, tmp_path):
+        """Only aliases and children of plain module files are looked up at runtime"""
+        marker = tmp_path / "imported"
+        make_package({"noisy/__init__.py": f"open({str(marker)!r}, 'w').close()\n"})
+
+        with pytest.raises(ImportError):
+            ModuleTreeExplorer("noisy.missing", max_depth=1).explore()
+        assert not marker.exists()
+
+
+    def test_depth_is_relative_to_dotted_module(self, make_package):
+        """`pkg.sub --depth 1` matches the `sub` section of `pkg --depth 2`"""
+        make_package(
+            {
+                "depthpkg/__init__.py": "",
+                "depthpkg/sub/__init__.py": "def top(): ...\n",
+                "depthpkg/sub/leaf.py": "def leaf(): ...\n",
+                "depthpkg/sub/inner/__init__.py": "def inner(): ...\n",
+                "depthpkg/sub/inner/deep.py": "def deep(): ...\n",
+            }
+        )
+
+        dotted = ModuleTreeExplorer("depthpkg.sub", max_depth=1).explore()
+        whole = ModuleTreeExplorer("depthpkg", max_depth=2).explore()
+        section = whole["submodules"]["sub"]
+        assert dotted["api"] == section["api"]
+        assert sorted(dotted["submodules"]) == sorted(section["submodules"]) == ["inner", "leaf"]
+        assert dotted["submodules"]["inner"]["submodules"] == {}
+
+
+    def test_repr_html(self):
+        explorer = ModuleTreeExplorer("json", max_depth=1)
+        assert repr(explorer) == "ModuleTreeExplorer('json', max_depth=1)"
+        html = explorer._repr_html_()
+        assert html.startswith('<div class="pretty-mod"><details open>')
+        assert "<b>decoder</b>" in html
+        assert "loads" in html
+        # Once explored, repr falls back to the plain tree
+        assert repr(explorer) == explorer.get_tree_string()
+
+    def test_signature_repr_html(self):
+        from pretty_mod import get_signature
+
+        html = get_signature("json:loads")._repr_html_()
+        assert html.startswith("<code><b>loads</b>(s, *, cls=None")
+        assert "\x1b" not in html
+
+
+class TestAbstractClasses:
+    SOURCE = """
+from abc import ABC, ABCMeta, abstractmethod
+
+
+class Runner(ABC):
+    def run(self):
+        pass
+
+
+class Plugin(metaclass=ABCMeta):
+    pass
+
+
+class Task:
+    @abstractmethod
+    def execute(self):
+        pass
+
+
+class Concrete(Runner):
+    def run(self):
+        pass
+"""
+
+    def test_abstract_classes_are_flagged(self, make_package):
+        make_package({"contracts.py": self.SOURCE})
+
+        explorer = ModuleTreeExplorer("contracts", max_depth=1)
+        tree = explorer.explore()
+        assert tree["api"]["abstract_classes"] == ["Runner", "Plugin", "Task"]
+
+        tree_string = explorer.get_tree_string()
+        assert "Runner (abstract)" in tree_string
+        assert "Plugin (abstract)" in tree_string
+        assert "Task (abstract)" in tree_string
+        assert "Concrete (abstract)" not in tree_string
+
+
+class TestStubModules:
+    """Compiled extensions documented only by `.pyi` stubs (e.g., after an sdist merge)"""
+
+    def test_stubs_stand_in_for_compiled_modules(self, make_package):
+        make_package(
+            {
+                "fastjson/__init__.py": "from .fastjson import *\n",
+                "fastjson/__init__.pyi": "def loads(s: str | bytes) -> object: ...\n",
+                "fastjson/fastjson.cpython-312-x86_64-linux-gnu.so": b"",
+                "fastjson/codec.pyi": "class Encoder: ...\n",
+            }
+        )
+
+        tree = ModuleTreeExplorer("fastjson", max_depth=1).explore()
+        assert tree["api"]["functions"] == ["loads"]
+        assert tree["submodules"]["codec"]["api"]["classes"] == ["Encoder"]
+
+        result = display_signature("fastjson:loads")
+        assert "s: str | bytes" in result
+
+    def test_stub_keeps_the_modules_own_api(self, make_package):
+        make_package(
+            {
+                "fastyaml/__init__.py": (
+                    "from ._native import *\n"
+                    "__all__ = ['load', 'VERSION']\n"
+                    "VERSION = '1.0'\n"
+                ),
+                "fastyaml/__init__.pyi": "def load(s: str) -> object: ...\n",
+                "fastyaml/_native.cpython-312-x86_64-linux-gnu.so": b"",
+            }
+        )
+
+        api = ModuleTreeExplorer("fastyaml", max_depth=1).explore()["api"]
+        assert api["functions"] == ["load"]
+        assert api["constants"] == ["VERSION"]
+        assert api["all"] == ["load", "VERSION"]
+
+
+class TestUnpackedKwargs:
+    """`**kwargs: Unpack[TypedDict]` lists the TypedDict's keys"""
+
+    @pytest.fixture
+    def client(self, make_package):
+        make_package(
+            {
+                "tdclient/__init__.py": "from .api import request\n",
+                "tdclient/_types.py": (
+                    "from typing import NotRequired, TypedDict\n"
+                    "class RequestOptions(TypedDict, total=False):\n"
+                    "    timeout: float\n"
+                    "    headers: dict[str, str]\n"
+                ),
+                "tdclient/api.py": (
+                    "from typing import Unpack\n"
+                    "from ._types import RequestOptions\n"
+                    "def request(url: str, **kwargs: Unpack[RequestOptions]) -> bytes: ...\n"
+                ),
+            }
+        )
+
+    def test_pretty(self, client):
+        result = display_signature("tdclient.api:request")
+        assert "**kwargs: Unpack[RequestOptions]" in result
+        assert "timeout: NotRequired[float]" in result
+        assert "headers: NotRequired[dict[str, str]]" in result
+
+    def test_json_nests_fields(self, client):
+        data = json.loads(display_signature("tdclient.api:request", format="json"))
+        assert data["kwargs"] == {
+            "parameter": "kwargs",
+            "typed_dict": "RequestOptions",
+            "fields": [
+                {"name": "timeout", "annotation": "float", "required": False},
+                {"name": "headers", "annotation": "dict[str, str]", "required": False},
+            ],
+        }
+
+
+class TestStubPackages:
+    """PEP 561 `<name>-stubs` packages add types to an untyped implementation"""
+
+    @pytest.fixture
+    def untyped(self, make_package, monkeypatch):
+        make_package(
+            {
+                "untyped/__init__.py": (
+                    "def load(path, *, strict=False):\n    pass\n"
+                    "def _runtime_only():\n    pass\n"
+                ),
+                "untyped-stubs/__init__.pyi": (
+                    "def load(path: str, *, strict: bool = ...) -> dict[str, int]: ...\n"
+                ),
+            }
+        )
+        monkeypatch.delenv("PRETTY_MOD_PREFER", raising=False)
+
+    def test_merge(self, untyped):
+        result = display_signature("untyped:load", format="compact")
+        assert result == "load(path: str, *, strict: bool=False) -> dict[str, int]"
+        tree = ModuleTreeExplorer("untyped", max_depth=1).explore()
+        assert tree["api"]["functions"] == ["load", "_runtime_only"]
+
+    def test_prefer_implementation(self, untyped, monkeypatch):
+        monkeypatch.setenv("PRETTY_MOD_PREFER", "implementation")
+        result = display_signature("untyped:load", format="compact")
+        assert result == "load(path, *, strict=False)"
+
+    def test_prefer_stubs(self, untyped, monkeypatch):
+        monkeypatch.setenv("PRETTY_MOD_PREFER", "stubs")
+        tree = ModuleTreeExplorer("untyped", max_depth=1).explore()
+        assert tree["api"]["functions"] == ["load"]
+
+
+class TestTimeBudget:
+    """An exhausted `timeout_seconds` budget returns a partial, marked tree"""
+
+    @pytest.fixture
+    def wide(self, make_package):
+        files = {f"widepkg/mod{i}.py": f"def f{i}(): ...\n" for i in range(3)}
+        make_package({"widepkg/__init__.py": "def top(): ...\n", **files})
+
+    def test_zero_budget_truncates(self, wide):
+        tree = ModuleTreeExplorer("widepkg", max_depth=1, timeout_seconds=0).explore()
+        assert tree["truncated"] is True
+        assert tree["api"]["functions"] == ["top"]
+        assert tree["submodules"] == {}
+
+    def test_generous_budget_is_complete(self, wide):
+        tree = ModuleTreeExplorer("widepkg", max_depth=1, timeout_seconds=60).explore()
+        assert "truncated" not in tree
+        assert sorted(tree["submodules"]) == ["mod0", "mod1", "mod2"]
+
+    def test_json_marker(self, wide, capfd):
+        from pretty_mod.explorer import display_tree
+
+        display_tree("widepkg", max_depth=1, format="json", timeout_seconds=0)
+        data = json.loads(capfd.readouterr().out)
+        assert data["tree"]["truncated"] is True
+
+    def test_negative_budget_rejected(self):
+        with pytest.raises(ValueError):
+            ModuleTreeExplorer("json", timeout_seconds=-1)
+
+
+class TestHideEmpty:
+    """`hide_empty` prunes submodules with no public API anywhere below them"""
+
+    @pytest.fixture
+    def sparse(self, make_package):
+        make_package(
+            {
+                "sparsepkg/__init__.py": "def top(): ...\n",
+                "sparsepkg/_compat.py": "",
+                "sparsepkg/private.py": "def _helper(): ...\n",
+                "sparsepkg/internal/__init__.py": "",
+                "sparsepkg/internal/deep/__init__.py": "",
+                "sparsepkg/internal/deep/api.py": "def useful(): ...\n",
+                "sparsepkg/empty/__init__.py": "",
+                "sparsepkg/empty/nested/__init__.py": "",
+            }
+        )
+
+    def test_prunes_empty_branches(self, sparse):
+        tree = ModuleTreeExplorer("sparsepkg", max_depth=3, hide_empty=True).explore()
+        assert sorted(tree["submodules"]) == ["internal"]
+        assert "api" in tree["submodules"]["internal"]["submodules"]["deep"]["submodules"]
+        assert tree["hidden_empty"] == 3
+
+    def test_default_keeps_everything(self, sparse):
+        tree = ModuleTreeExplorer("sparsepkg", max_depth=3).explore()
+        assert "private" in tree["submodules"]
+        assert "hidden_empty" not in tree
+
+    def test_hidden_count_is_displayed(self, sparse):
+        output = ModuleTreeExplorer("sparsepkg", max_depth=3, hide_empty=True).get_tree_string()
+        assert "3 empty submodules hidden" in output
+        assert "private" not in output
+
+
+class TestAggregatedAll:
+    """`__all__` built from submodules' `__all__` lists is resolved statically"""
+
+    @pytest.fixture
+    def aggregated(self, make_package):
+        make_package(
+            {
+                "aggpkg/core.py": (
+                    "__all__ = ['load', 'dump']\ndef load(): ...\ndef dump(): ...\n"
+                ),
+                "aggpkg/utils.py": "__all__ = ['retry']\ndef retry(): ...\n",
+                "aggpkg/__init__.py": (
+                    "from . import utils\n"
+                    "from .core import *\n"
+                    "from .core import __all__ as _core_all\n"
+                    "__all__ = list(_core_all) + utils.__all__ + ['VERSION']\n"
+                    "VERSION = '1.0'\n"
+                ),
+                "aggpkg/dynamic.py": "__all__ = [n for n in dir() if n.isupper()]\n",
+            }
+        )
+
+    def test_root_exports_are_concatenated(self, aggregated):
+        tree = ModuleTreeExplorer("aggpkg", max_depth=1).explore()
+        assert tree["api"]["all"] == ["load", "dump", "retry", "VERSION"]
+        assert tree["api"]["constants"] == ["VERSION"]
+
+    def test_unresolvable_all_is_marked_dynamic(self, aggregated):
+        explorer = ModuleTreeExplorer("aggpkg", max_depth=1)
+        assert explorer.explore()["submodules"]["dynamic"]["api"]["dynamic_all"] is True
+        assert "__all__: (computed at import time)" in explorer.get_tree_string()
+
+
+class TestClassDepth:
+    """`class_depth=1` nests each class's public methods under it"""
+
+    @pytest.fixture
+    def shapes(self, make_package):
+        big = "".join(f"    def m{i:02}(self): ...\n" for i in range(40))
+        make_package(
+            {
+                "shapemod.py": (
+                    "class Circle:\n"
+                    "    def __init__(self, r): ...\n"
+                    "    def area(self) -> float: ...\n"
+                    "    @property\n"
+                    "    def radius(self): ...\n"
+                    "    @radius.setter\n"
+                    "    def radius(self, value): ...\n"
+                    "    def _scale(self): ...\n"
+                    f"class Big:\n{big}"
+                )
+            }
+        )
+
+    def test_methods_nested_under_classes(self, shapes):
+        tree = ModuleTreeExplorer("shapemod", max_depth=0, class_depth=1).explore()
+        methods = tree["api"]["class_methods"]
+        assert methods["Circle"] == {"methods": ["area", "radius"]}
+        assert len(methods["Big"]["methods"]) == 30
+        assert methods["Big"]["more"] == 10
+        assert "class_methods" not in ModuleTreeExplorer("shapemod", max_depth=0).explore()["api"]
+
+    def test_pretty_output(self, shapes):
+        output = ModuleTreeExplorer("shapemod", max_depth=0, class_depth=1).get_tree_string()
+        assert "Circle: area, radius" in output
+        assert "… 10 more" in output
+
+    def test_json_nests_methods_in_class_objects(self, shapes):
+        explorer = ModuleTreeExplorer("shapemod", max_depth=0, class_depth=1)
+        api = json.loads(explorer.get_tree_string(format="json"))["tree"]["api"]
+        classes = {entry["name"]: entry for entry in api["classes"]}
+        assert classes["Circle"]["methods"] == [{"name": "area"}, {"name": "radius"}]
+        assert len(classes["Big"]["methods"]) == 30
+        assert classes["Big"]["more"] == 10
+        assert "class_methods" not in api
+
+    def test_signatures(self, shapes):
+        explorer = ModuleTreeExplorer(
+            "shapemod", max_depth=0, class_depth=1, signatures=True
+        )
+        api = json.loads(explorer.get_tree_string(format="json"))["tree"]["api"]
+        circle = next(entry for entry in api["classes"] if entry["name"] == "Circle")
+        assert circle["methods"][0] == {
+            "name": "area",
+            "signature": "area(self) -> float",
+        }
+
+        output = explorer.get_tree_string()
+        assert "Circle: area" not in output
+        assert "area(self) -> float" in output
+        assert "m00(self)" in output
+
+    def test_invalid_class_depth(self):
+        with pytest.raises(ValueError, match="class_depth"):
+            ModuleTreeExplorer("json", class_depth=2)
+
+
+class TestExternalImports:
+    """Third-party imports are aggregated across a package's modules"""
+
+    @pytest.fixture
+    def client(self, make_package):
+        make_package(
+            {
+                "extpkg/__init__.py": (
+                    "from __future__ import annotations\n"
+                    "import json\n"
+                    "from httpx import URL, Timeout\n"
+                    "from .client import Client\n"
+                ),
+                "extpkg/client.py": (
+                    "import dataclasses\n"
+                    "import yaml.constructor\n"
+                    "from httpx._types import Headers\n"
+                    "from extpkg import URL\n"
+                ),
+            }
+        )
+
+    def test_json_report(self, client):
+        from pretty_mod.explorer import display_external_imports
+
+        report = json.loads(display_external_imports("extpkg", format="json"))
+        assert [p["package"] for p in report["packages"]] == ["httpx", "yaml"]
+        httpx = report["packages"][0]
+        assert httpx["uses"] == 3
+        assert httpx["modules"] == [
+            {"module": "extpkg", "uses": 2},
+            {"module": "extpkg.client", "uses": 1},
+        ]
+
+    def test_pretty_report(self, client):
+        from pretty_mod.explorer import display_external_imports
+
+        output = display_external_imports("extpkg")
+        assert "extpkg external imports" in output
+        assert "extpkg.client (1)" in output
+        assert "json" not in output
+
+
+class TestStubDrift:
+    """Stubs that disagree with their implementation are reported per module"""
+
+    @pytest.fixture
+    def drifted(self, make_package):
+        make_package(
+            {
+                "driftpkg/__init__.py": (
+                    "def load(path, *, strict=False):\n    pass\n"
+                    "def dump(obj):\n    pass\n"
+                ),
+                "driftpkg/__init__.pyi": (
+                    "def load(path: str, *, strict: bool = ...) -> dict: ...\n"
+                    "def dumps(obj: object) -> str: ...\n"
+                ),
+                "driftpkg/codec.py": "def encode(data, level=1) -> bytes:\n    pass\n",
+                "driftpkg-stubs/codec.pyi": (
+                    "def encode(data: str, level: int) -> str: ...\n"
+                ),
+            }
+        )
+
+    def test_reports(self, drifted):
+        from pretty_mod.explorer import check_stubs
+
+        reports = {report.module: report for report in check_stubs("driftpkg")}
+        assert reports["driftpkg"].mismatched == []
+        assert reports["driftpkg"].stub_only == ["dumps"]
+        assert reports["driftpkg"].implementation_only == ["dump"]
+        assert reports["driftpkg.codec"].mismatched == [
+            "encode: level has a default only in the implementation",
+            "encode: returns 'bytes' in implementation, 'str' in stub",
+        ]
+        assert not any(reports.values())
+
+        with pytest.raises(AssertionError, match="driftpkg.codec"):
+            check_stubs("driftpkg", strict=True)
+
+    def test_json(self, drifted):
+        from pretty_mod.explorer import display_stub_drift
+
+        data = json.loads(display_stub_drift("driftpkg", format="json"))
+        assert [report["module"] for report in data] == ["driftpkg", "driftpkg.codec"]
+        assert data[1]["ok"] is False
+
+
+class TestAnnotationCoverage:
+    """Public functions counted by how completely they are annotated"""
+
+    @pytest.fixture
+    def partly_typed(self, make_package):
+        make_package(
+            {
+                "partlytyped/__init__.py": (
+                    "def load(path: str) -> dict: ...\n"
+                    "def dump(obj: object, fp): ...\n"
+                    "def _helper(x): ...\n"
+                ),
+                "partlytyped/codec.py": (
+                    "class Codec:\n"
+                    "    def __init__(self, level: int): ...\n"
+                    "    def encode(self, data, level=1): ...\n"
+                ),
+            }
+        )
+
+    def test_json(self, partly_typed):
+        from pretty_mod.explorer import display_coverage
+
+        data = json.loads(display_coverage("partlytyped", format="json"))
+        assert data["total"] == {"full": 2, "partial": 1, "unannotated": 1}
+        assert [m["module"] for m in data["modules"]] == [
+            "partlytyped",
+            "partlytyped.codec",
+        ]
+        assert data["least_annotated"][0] == {
+            "name": "partlytyped.codec:Codec.encode",
+            "missing": 3,
+        }
+
+    def test_pretty(self, partly_typed):
+        from pretty_mod.explorer import display_coverage
+
+        result = display_coverage("partlytyped")
+        assert "annotation coverage" in result
+        assert "partlytyped:dump (2 missing)" in result
+
+
+class TestExplicitReexports:
+    """`from .x import y as y` re-exports names in modules without `__all__`"""
+
+    @pytest.fixture
+    def stub_style(self, make_package):
+        make_package(
+            {
+                "stubstyle/__init__.py": (
+                    "import json as json\n"
+                    "from ._impl import load as load\n"
+                    "from ._impl import Reader as Reader\n"
+                    "from ._impl import helper\n"
+                    "from ._impl import dump as _dump\n"
+                ),
+                "stubstyle/_impl.py": (
+                    "def load(path: str, *, strict: bool = False) -> dict: ...\n"
+                    "def dump(obj): ...\n"
+                    "def helper(): ...\n"
+                    "class Reader: ...\n"
+                ),
+            }
+        )
+
+    def test_tree_lists_reexports(self, stub_style):
+        tree = ModuleTreeExplorer("stubstyle", max_depth=0).explore()
+        assert tree["api"]["reexports"] == ["Reader", "json", "load"]
+        assert "re-exports: Reader, json, load" in ModuleTreeExplorer(
+            "stubstyle", max_depth=0
+        ).get_tree_string()
+
+    def test_signature_resolves_through_reexport(self, stub_style):
+        result = display_signature("stubstyle:load", format="compact")
+        assert result == "load(path: str, *, strict: bool=False) -> dict"
+
+    def test_import_map(self, stub_style):
+        assert "imports" not in ModuleTreeExplorer("stubstyle", max_depth=0).explore()["api"]
+        tree = ModuleTreeExplorer("stubstyle", max_depth=0, include_imports=True).explore()
+        imports = tree["api"]["imports"]
+        assert imports["_dump"] == {"from": "_impl", "name": "dump", "level": 1}
+        assert imports["json"] == {"from": None, "name": "json", "level": 0}
+
+    def test_import_map_records_relative_level(self, make_package):
+        make_package(
+            {
+                "levels/__init__.py": "",
+                "levels/core.py": "def f(): ...\n",
+                "levels/sub/__init__.py": "from ..core import f\nfrom . import mod\n",
+                "levels/sub/mod.py": "",
+            }
+        )
+        tree = ModuleTreeExplorer("levels.sub", max_depth=0, include_imports=True).explore()
+        imports = tree["api"]["imports"]
+        assert imports["f"] == {"from": "core", "name": "f", "level": 2}
+        assert imports["mod"] == {"from": None, "name": "mod", "level": 1}
+
+    def test_import_map_json(self, stub_style, capfd):
+        from pretty_mod.explorer import display_tree
+
+        display_tree("stubstyle", max_depth=0, format="json", include_imports=True)
+        data = json.loads(capfd.readouterr().out)
+        assert data["tree"]["api"]["imports"]["helper"]["from"] == "_impl"
+
+    def test_check_exports(self, stub_style):
+        from pretty_mod.explorer import check_exports
+
+        assert check_exports("stubstyle", ["load", "Reader"])
+
+
+class TestDottedSignaturePaths:
+    """Dot-only sig paths try every split point, longest module first"""
+
+    @pytest.fixture
+    def dotted(self, make_package):
+        make_package(
+            {
+                "dottedpkg/__init__.py": (
+                    "class Client:\n"
+                    "    def send(self, payload: bytes, *, retries: int = 3) -> int: ...\n"
+                    "\n"
+                    "class sub:\n"
+                    "    def func(self, shadowed): ...\n"
+                ),
+                "dottedpkg/sub.py": "def func(value: str) -> str: ...\n",
+            }
+        )
+
+    def test_method_path(self, dotted):
+        result = display_signature("dottedpkg.Client.send", format="compact")
+        assert result == "send(self, payload: bytes, *, retries: int=3) -> int"
+
+    def test_submodule_wins_over_same_named_attribute(self, dotted):
+        result = display_signature("dottedpkg.sub.func", format="compact")
+        assert result == "func(value: str) -> str"
+
+    def test_colon_syntax_reaches_the_attribute(self, dotted):
+        result = display_signature("dottedpkg:sub.func", format="compact")
+        assert result == "func(self, shadowed)"
+
+
+class TestParamDocs:
+    """`param_docs=True` describes parameters from numpy- or Google-style docstrings"""
+
+    @pytest.fixture
+    def documented(self, make_package):
+        make_package(
+            {
+                "documented.py": (
+                    "def fetch(url: str, retries: int = 3, **kwargs):\n"
+                    '    """Fetch a URL.\n'
+                    "\n"
+                    "    Args:\n"
+                    "        url (str): Where to fetch from.\n"
+                    "        retries: How often to retry.\n"
+                    "        **kwargs: Passed to the transport.\n"
+                    '    """\n'
+                    "\n"
+                    "def join(a, axis=0):\n"
+                    '    """Join arrays.\n'
+                    "\n"
+                    "    Parameters\n"
+                    "    ----------\n"
+                    "    a : sequence\n"
+                    "        The arrays to join.\n"
+                    "    axis : int, optional\n"
+                    "        The axis to join along.\n"
+                    '    """\n'
+                    "\n"
+                    "def plain(x):\n"
+                    '    """Do something.\n'
+                    "\n"
+                    "    :param x: reST is not recognized.\n"
+                    '    """\n'
+                )
+            }
+        )
+
+    def test_off_by_default(self, documented):
+        assert "Where to fetch from." not in display_signature("documented:fetch")
+        data = json.loads(display_signature("documented:fetch", format="json"))
+        assert "param_docs" not in data
+
+    def test_google_style(self, documented):
+        result = display_signature("documented:fetch", param_docs=True)
+        assert "Where to fetch from." in result
+        assert "Passed to the transport." in result
+
+    def test_numpy_style_json(self, documented):
+        data = json.loads(
+            display_signature("documented:join", format="json", param_docs=True)
+        )
+        assert data["param_docs"] == {
+            "a": "The arrays to join.",
+            "axis": "The axis to join along.",
+        }
+
+    def test_unrecognized_style_degrades(self, documented):
+        data = json.loads(
+            display_signature("documented:plain", format="json", param_docs=True)
+        )
+        assert "param_docs" not in data
+        assert data["parameters"] == "x"
+
+
+class TestModuleSignaturePaths:
+    """A sig path naming a module shows a summary of it instead of a signature"""
+
+    @pytest.fixture
+    def package(self, make_package):
+        make_package(
+            {
+                "modsigpkg/__init__.py": (
+                    "from . import flows\n"
+                    "from .worker import worker\n"
+                    "def runner(): ...\n"
+                ),
+                "modsigpkg/flows/__init__.py": (
+                    '"""Flows: the unit of orchestration.\n\nMore detail.\n"""\n'
+                    "__all__ = ['flow', 'Flow']\n"
+                    "def flow(fn=None): ...\n"
+                    "class Flow: ...\n"
+                    "RETRIES = 3\n"
+                ),
+                "modsigpkg/flows/engine.py": "def run(): ...\n",
+                "modsigpkg/runner.py": "def shadowed(): ...\n",
+                "modsigpkg/worker.py": "def worker(jobs: int): ...\n",
+            }
+        )
+
+    def test_pretty(self, package):
+        result = display_signature("modsigpkg:flows")
+        assert "signature not available" not in result
+        assert "Flows: the unit of orchestration." in result
+        assert "1 function, 1 class" in result
+        assert "1 submodule" in result
+        assert "flow, Flow" in result
+        assert "try `pretty-mod tree modsigpkg.flows`" in result
+
+    def test_json_kind(self, package):
+        data = json.loads(display_signature("modsigpkg.flows", format="json"))
+        assert data["kind"] == "module"
+        assert data["name"] == "modsigpkg.flows"
+        assert data["exports"] == ["flow", "Flow"]
+        assert data["submodules"] == 1
+
+    def test_attribute_wins_over_submodule(self, package):
+        result = display_signature("modsigpkg:runner", format="compact")
+        assert result == "runner()"
+
+    def test_reexported_attribute_wins_over_submodule(self, package):
+        result = display_signature("modsigpkg:worker", format="compact")
+        assert result == "worker(jobs: int)"
+
+
+class TestAmbiguousNames:
+    """A name several submodules define is resolved by re-export, or its candidates listed"""
+
+    @pytest.fixture
+    def package(self, make_package):
+        make_package(
+            {
+                "ambpkg/__init__.py": "from .models import connect\n",
+                "ambpkg/models.py": "def connect(url: str): ...\ndef helper(x): ...\n",
+                "ambpkg/legacy.py": (
+                    "def connect(host, port): ...\ndef helper(y): ...\n"
+                ),
+            }
+        )
+
+    def test_reexported_definition_wins(self, package):
+        result = display_signature("ambpkg:connect", format="compact")
+        assert result == "connect(url: str)"
+
+    def test_reexported_json_lists_candidates(self, package):
+        data = json.loads(display_signature("ambpkg:connect", format="json"))
+        assert data["parameters"] == "url: str"
+        assert sorted(data["candidates"]) == [
+            "ambpkg.legacy:connect",
+            "ambpkg.models:connect",
+        ]
+
+    def test_candidates_listed(self, package):
+        result = display_signature("ambpkg:helper")
+        assert "ambiguous" in result
+        assert "ambpkg.legacy:helper" in result
+        assert "ambpkg.models:helper" in result
+
+    def test_candidates_json(self, package):
+        data = json.loads(display_signature("ambpkg:helper", format="json"))
+        assert data["available"] is False
+        assert sorted(data["candidates"]) == [
+            "ambpkg.legacy:helper",
+            "ambpkg.models:helper",
+        ]
+
+    def test_qualified_path_disambiguates(self, package):
+        result = display_signature("ambpkg.legacy:helper", format="compact")
+        assert result == "helper(y)"
+
+
+class TestShowSource:
+    """`show_source` adds the definition's source, found through re-exports"""
+
+    @pytest.fixture
+    def package(self, make_package):
+        make_package(
+            {
+                "srcpkg/__init__.py": "from .core import Client, retry\n",
+                "srcpkg/core.py": (
+                    "def traced(fn):\n"
+                    "    return fn\n"
+                    "\n"
+                    "\n"
+                    "@traced\n"
+                    "def retry(times: int):\n"
+                    "    for attempt in range(times):\n"
+                    "        print(attempt)\n"
+                    "    return times\n"
+                    "\n"
+                    "\n"
+                    "class Client:\n"
+                    "    @traced\n"
+                    "    def close(self, force: bool) -> None:\n"
+                    "        self.force = force\n"
+                ),
+            }
+        )
+
+    def test_fenced_below_signature(self, package):
+        result = display_signature("srcpkg:retry", format="compact", show_source=True)
+        assert result == (
+            "retry(times: int)\n"
+            "```python\n"
+            "@traced\n"
+            "def retry(times: int):\n"
+            "    for attempt in range(times):\n"
+            "        print(attempt)\n"
+            "    return times\n"
+            "```"
+        )
+
+    def test_method_is_dedented(self, package):
+        result = display_signature(
+            "srcpkg.core:Client.close", format="json", show_source=True
+        )
+        data = json.loads(result)
+        assert data["source_code"] == (
+            "@traced\ndef close(self, force: bool) -> None:\n    self.force = force"
+        )
+
+    def test_truncated(self, package):
+        data = json.loads(
+            display_signature(
+                "srcpkg:retry", format="json", show_source=True, source_lines=2
+            )
+        )
+        assert data["source_code"] == (
+            "@traced\ndef retry(times: int):\n# ... (3 more lines)"
+        )
+
+    def test_off_by_default(self, package):
+        data = json.loads(display_signature("srcpkg:retry", format="json"))
+        assert "source_code" not in data
@@ -0,0 +1,59 @@
+"""Golden-file tests: identical input must produce byte-identical output.
+
+Regenerate the files after an intentional output change with:
+
+    PRETTY_MOD_UPDATE_GOLDEN=1 pytest tests/test_golden.py
+"""
+
+import os
+from pathlib import Path
+
+import pytest
+from pretty_mod import display_signature, display_tree
+from pretty_mod.explorer import ModuleTreeExplorer
+
+FIXTURES = Path(__file__).parent / "fixtures"
+GOLDEN = Path(__file__).parent / "golden"
+
+
+@pytest.fixture(autouse=True)
+def fixture_package(monkeypatch):
+    monkeypatch.syspath_prepend(str(FIXTURES))
+
+
+def assert_golden(name: str, actual: str):
+    actual = actual.rstrip("\n") + "\n"
+    path = GOLDEN / name
+    if os.environ.get("PRETTY_MOD_UPDATE_GOLDEN"):
+        path.write_text(actual, encoding="utf-8")
+    assert actual == path.read_text(encoding="utf-8")
+
+
+def tree_json(capfd) -> str:
+    display_tree("goldpkg", 2, format="json")
+    # Module files are absolute paths; keep the golden file independent of the checkout
+    return capfd.readouterr().out.replace(str(FIXTURES), "<fixtures>")
+
+
+class TestGoldenOutput:
+    def test_tree_pretty(self):
+        first = ModuleTreeExplorer("goldpkg", max_depth=2).get_tree_string()
+        second = ModuleTreeExplorer("goldpkg", max_depth=2).get_tree_string()
+        assert first == second
+        assert_golden("goldpkg_tree.txt", first)
+
+    def test_tree_json(self, capfd):
+        first = tree_json(capfd)
+        assert first == tree_json(capfd)
+        assert_golden("goldpkg_tree.json", first)
+
+    @pytest.mark.parametrize(
+        "format, golden",
+        [("pretty", "goldpkg_signature.txt"), ("json", "goldpkg_signature.json")],
+    )
+    def test_signature(self, format, golden):
+        first = display_signature("goldpkg.util.strings:slugify", format=format)
+        assert first == display_signature(
+            "goldpkg.util.strings:slugify", format=format
+        )
+        assert_golden(golden, first)
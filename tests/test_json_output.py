@@ -5,6 +5,8 @@ import os
 import subprocess
 import sys
 
+import pytest
+
 
 def test_tree_json_output():
     """Test tree command with JSON output."""
@@ -28,8 +30,10 @@ def test_tree_json_output():
 
     # Check some expected content
     api = data["tree"]["api"]
-    assert "dump" in api["functions"]
-    assert "loads" in api["functions"]
+    functions = {entry["name"]: entry for entry in api["functions"]}
+    assert "dump" in functions
+    assert functions["loads"]["line"] > 0
+    assert data["tree"]["file"].endswith("__init__.py")
     assert "JSONEncoder" in api["all"]
 
 
@@ -101,3 +105,181 @@ def test_default_output_unchanged():
     assert result_sig.returncode == 0
     assert "📎 dumps" in result_sig.stdout
     assert "├──  Parameters:" in result_sig.stdout
+
+
+def test_tree_json_includes_dist_metadata():
+    """Test that downloaded or installed packages carry distribution metadata."""
+    result = subprocess.run(
+        [sys.executable, "-m", "pretty_mod", "tree", "toml@0.10.2", "-o", "json", "-q"],
+        capture_output=True,
+        text=True,
+    )
+
+    assert result.returncode == 0
+
+    data = json.loads(result.stdout)
+    assert "dist" in data
+    dist = data["dist"]
+    assert dist["name"] == "toml"
+    assert dist["version"]
+    assert "summary" in dist
+    assert "requires_python" in dist
+    assert "license" in dist
+
+    # Metadata is hoisted out of the module tree itself
+    assert "dist" not in data["tree"]
+
+
+def test_tree_json_omits_dist_without_metadata():
+    """Test that modules without a distribution (e.g. stdlib) omit the dist object."""
+    result = subprocess.run(
+        [sys.executable, "-m", "pretty_mod", "tree", "json", "-o", "json"],
+        capture_output=True,
+        text=True,
+    )
+
+    assert result.returncode == 0
+
+    data = json.loads(result.stdout)
+    assert "dist" not in data
+
+
+JSON_TYPES = {
+    "object": dict,
+    "array": list,
+    "string": str,
+    "integer": int,
+    "number": (int, float),
+    "boolean": bool,
+    "null": type(None),
+}
+
+
+def is_json_type(data, name):
+    # bool is an int subclass, but not a JSON integer
+    if isinstance(data, bool):
+        return name == "boolean"
+    return isinstance(data, JSON_TYPES[name])
+
+
+def schema_errors(data, schema, root, path="$"):
+    """Where `data` breaks `schema`, for the JSON Schema keywords get_schema uses."""
+    if "$ref" in schema:
+        name = schema["$ref"].removeprefix("#/$defs/")
+        return schema_errors(data, root["$defs"][name], root, path)
+    if "oneOf" in schema:
+        matching = [
+            option
+            for option in schema["oneOf"]
+            if not schema_errors(data, option, root, path)
+        ]
+        if len(matching) != 1:
+            return [f"{path}: matches {len(matching)} of oneOf"]
+        return []
+    if "const" in schema and data != schema["const"]:
+        return [f"{path}: expected {schema['const']!r}"]
+    if "enum" in schema and data not in schema["enum"]:
+        return [f"{path}: {data!r} not in {schema['enum']}"]
+    types = schema.get("type", [])
+    types = [types] if isinstance(types, str) else types
+    if types and not any(is_json_type(data, t) for t in types):
+        return [f"{path}: expected {' or '.join(types)}, got {data!r}"]
+    errors = []
+    if "minimum" in schema and data < schema["minimum"]:
+        errors.append(f"{path}: {data} is below {schema['minimum']}")
+    if isinstance(data, dict):
+        properties = schema.get("properties", {})
+        for key in schema.get("required", []):
+            if key not in data:
+                errors.append(f"{path}: missing {key}")
+        for key, value in data.items():
+            extra = schema.get("additionalProperties", True)
+            item_schema = properties.get(key, extra)
+            if item_schema is False:
+                errors.append(f"{path}: unexpected {key}")
+            elif item_schema is not True:
+                errors += schema_errors(value, item_schema, root, f"{path}.{key}")
+    if isinstance(data, list) and "items" in schema:
+        for i, item in enumerate(data):
+            errors += schema_errors(item, schema["items"], root, f"{path}[{i}]")
+    return errors
+
+
+class TestSchemas:
+    """Machine-readable outputs validate against get_schema."""
+
+    @pytest.fixture
+    def validate(self):
+        from pretty_mod.explorer import get_schema
+
+        def validate(kind, data):
+            schema = get_schema(kind)
+            assert schema_errors(data, schema, schema) == []
+
+        return validate
+
+    def test_validator_reports_mismatches(self):
+        from pretty_mod.explorer import get_schema
+
+        schema = get_schema("signature")
+        assert schema_errors({"name": 1}, schema, schema)
+
+    def _cli_json(self, *args):
+        result = subprocess.run(
+            [sys.executable, "-m", "pretty_mod", *args, "-o", "json"],
+            capture_output=True,
+            text=True,
+        )
+        assert result.returncode == 0, result.stderr
+        return json.loads(result.stdout)
+
+    def test_tree(self, validate):
+        validate("tree", self._cli_json("tree", "json"))
+        validate(
+            "tree", self._cli_json("tree", "json", "--imports", "--class-depth", "1")
+        )
+        validate(
+            "tree",
+            self._cli_json("tree", "json", "--class-depth", "1", "--signatures"),
+        )
+
+    def test_signature(self, validate):
+        validate("signature", self._cli_json("sig", "json:dumps"))
+        validate("signature", self._cli_json("sig", "sys:maxsize"))
+        validate("signature", self._cli_json("sig", "json:decoder"))
+
+    def test_methods(self, validate):
+        from pretty_mod.explorer import display_methods
+
+        data = json.loads(display_methods("json:JSONDecoder", format="json"))
+        validate("methods", data)
+
+    def test_coverage_and_imports(self, validate, make_package):
+        from pretty_mod.explorer import display_coverage, display_external_imports
+
+        make_package(
+            {
+                "schemapkg/__init__.py": (
+                    "import httpx\n"
+                    "def load(path: str) -> dict: ...\n"
+                    "def dump(obj, fp): ...\n"
+                )
+            }
+        )
+
+        validate("coverage", json.loads(display_coverage("schemapkg", format="json")))
+        validate(
+            "imports",
+            json.loads(display_external_imports("schemapkg", format="json")),
+        )
+
+    def test_schema_version(self):
+        from pretty_mod.explorer import get_schema
+
+        assert isinstance(get_schema("diff")["schema_version"], int)
+
+    def test_unknown_kind(self):
+        from pretty_mod.explorer import get_schema
+
+        with pytest.raises(ValueError, match="Unknown schema"):
+            get_schema("nope")
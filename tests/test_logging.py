@@ -0,0 +1,52 @@
+"""Test that diagnostics are routed through Python's logging module."""
+
+import logging
+import os
+import subprocess
+import sys
+
+from pretty_mod import display_signature, display_tree
+
+
+def test_debug_records_on_pretty_mod_logger(caplog):
+    """Test that resolution traces are emitted on the pretty_mod logger."""
+    with caplog.at_level(logging.DEBUG, logger="pretty_mod"):
+        display_signature("json:dumps")
+
+    records = [r for r in caplog.records if r.name == "pretty_mod"]
+    assert any(r.levelno == logging.DEBUG for r in records)
+    assert any("json" in r.getMessage() for r in records)
+
+
+def test_exploration_summary_is_info(caplog):
+    """Test that an exploration summary is logged at INFO level."""
+    with caplog.at_level(logging.INFO, logger="pretty_mod"):
+        display_tree("json", 1)
+
+    summaries = [
+        r
+        for r in caplog.records
+        if r.name == "pretty_mod" and r.levelno == logging.INFO
+    ]
+    assert any("Explored json" in r.getMessage() for r in summaries)
+
+
+def test_no_records_when_disabled(caplog):
+    """Test that nothing is emitted below the configured level."""
+    with caplog.at_level(logging.WARNING, logger="pretty_mod"):
+        display_signature("json:dumps")
+
+    assert not [r for r in caplog.records if r.name == "pretty_mod"]
+
+
+def test_debug_env_var_prints_to_stderr():
+    """Test that PRETTY_MOD_DEBUG still works as a convenience."""
+    result = subprocess.run(
+        [sys.executable, "-m", "pretty_mod", "sig", "json:dumps"],
+        capture_output=True,
+        text=True,
+        env={**os.environ, "PRETTY_MOD_DEBUG": "1"},
+    )
+
+    assert result.returncode == 0
+    assert "[DEBUG]" in result.stderr
@@ -0,0 +1,66 @@
+"""Test the quiet / normal / verbose stderr behavior."""
+
+import os
+import subprocess
+import sys
+
+import pytest
+
+
+def run_cli(*args: str) -> subprocess.CompletedProcess:
+    env = {k: v for k, v in os.environ.items() if k != "PRETTY_MOD_DEBUG"}
+    return subprocess.run(
+        [sys.executable, "-m", "pretty_mod", *args],
+        capture_output=True,
+        env={**env, "PRETTY_MOD_NO_COLOR": "1"},
+    )
+
+
+@pytest.mark.parametrize(
+    "args",
+    [
+        ("tree", "json", "--depth", "1"),
+        ("tree", "this-package-definitely-does-not-exist-12345", "--depth", "1"),
+        ("sig", "json:dumps"),
+        ("sig", "this_package_definitely_does_not_exist_12345:func"),
+    ],
+    ids=["tree-found", "tree-not-found", "sig-found", "sig-not-found"],
+)
+def test_quiet_writes_nothing_to_stderr(args):
+    """Test that quiet mode leaves stderr byte-empty on every path."""
+    result = run_cli(*args, "--quiet")
+
+    assert result.returncode == 0
+    assert result.stderr == b""
+
+
+def test_normal_shows_download_notice():
+    """Test that normal mode shows the one-line download notice."""
+    result = run_cli("tree", "this-package-definitely-does-not-exist-12345")
+
+    assert "not found locally" in result.stderr.decode()
+
+
+def test_verbose_shows_exploration_details():
+    """Test that verbose mode reports per-step details."""
+    result = run_cli("tree", "json", "--depth", "1", "--verbose")
+
+    assert result.returncode == 0
+    assert "explored" in result.stderr.decode()
+
+
+def test_verbose_shows_download_details():
+    """Test that verbose mode reports the resolved version and download URL."""
+    result = run_cli("sig", "toml@0.10.2:loads", "--verbose")
+
+    stderr = result.stderr.decode()
+    assert result.returncode == 0
+    if "not found locally" in stderr:
+        assert "resolved toml to version 0.10.2" in stderr
+        assert "download URL:" in stderr
+
+
+def test_quiet_and_verbose_are_exclusive():
+    result = run_cli("tree", "json", "--quiet", "--verbose")
+
+    assert result.returncode == 2